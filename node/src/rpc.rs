@@ -59,6 +59,7 @@ where
     C::Api: subtensor_custom_rpc_runtime_api::DelegateInfoRuntimeApi<Block>,
     C::Api: subtensor_custom_rpc_runtime_api::NeuronInfoRuntimeApi<Block>,
     C::Api: subtensor_custom_rpc_runtime_api::SubnetInfoRuntimeApi<Block>,
+    C::Api: subtensor_custom_rpc_runtime_api::StakeInfoRuntimeApi<Block>,
     C::Api: subtensor_custom_rpc_runtime_api::SubnetRegistrationRuntimeApi<Block>,
     C::Api: subtensor_custom_rpc_runtime_api::ColdkeySwapRuntimeApi<Block>,
     C::Api: subtensor_custom_rpc_runtime_api::SubtensorRuntimeApi<Block>,
@@ -4,6 +4,8 @@ pub use pallet::*;
 pub mod weights;
 pub use weights::WeightInfo;
 
+extern crate alloc;
+use alloc::vec::Vec;
 use sp_runtime::DispatchError;
 use sp_runtime::{traits::Member, RuntimeAppPublic};
 
@@ -69,6 +71,12 @@ pub mod pallet {
         MaxValidatorsLargerThanMaxUIds,
         /// The maximum number of subnet validators must be more than the current number of UIDs already in the subnet.
         MaxAllowedUIdsLessThanCurrentUIds,
+        /// The list of banned weights version keys is too long.
+        TooManyBannedWeightsVersionKeys,
+        /// The epoch history depth is above the maximum.
+        EpochHistoryDepthTooHigh,
+        /// The weights version key would be above the maximum weights version key of the subnet.
+        WeightsVersionKeyAboveMax,
     }
 
     /// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -187,7 +195,8 @@ pub mod pallet {
             Ok(())
         }
 
-        /// The extrinsic sets the weights version key for a subnet.
+        /// The extrinsic sets the weights version key for a subnet, which may not be above the
+        /// maximum weights version key of the subnet.
         /// It is only callable by the root account or subnet owner.
         /// The extrinsic will call the Subtensor pallet to set the weights version key.
         #[pallet::call_index(6)]
@@ -203,6 +212,11 @@ pub mod pallet {
                 T::Subtensor::if_subnet_exist(netuid),
                 Error::<T>::SubnetDoesNotExist
             );
+            let weights_max_version_key = T::Subtensor::get_weights_max_version_key(netuid);
+            ensure!(
+                weights_max_version_key == 0 || weights_version_key <= weights_max_version_key,
+                Error::<T>::WeightsVersionKeyAboveMax
+            );
             T::Subtensor::set_weights_version_key(netuid, weights_version_key);
             log::info!(
                 "WeightsVersionKeySet( netuid: {:?} weights_version_key: {:?} ) ",
@@ -1035,6 +1049,279 @@ pub mod pallet {
             T::Subtensor::ensure_subnet_owner_or_root(origin.clone(), netuid)?;
             T::Subtensor::do_set_alpha_values(origin, netuid, alpha_low, alpha_high)
         }
+
+        /// The extrinsic sets the global unbonding period for removed stake.
+        /// It is only callable by the root account.
+        /// The extrinsic will call the Subtensor pallet to set the unbonding period.
+        #[pallet::call_index(52)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_unbonding_period(
+            origin: OriginFor<T>,
+            unbonding_period: u64,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            T::Subtensor::set_unbonding_period(unbonding_period);
+            log::info!(
+                "UnbondingPeriodSet( unbonding_period: {:?} ) ",
+                unbonding_period
+            );
+            Ok(())
+        }
+
+        /// The extrinsic sets the unbonding period for stake removed from hotkeys registered on a subnet.
+        /// It is only callable by the root account.
+        /// The extrinsic will call the Subtensor pallet to set the unbonding period.
+        #[pallet::call_index(53)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_network_unbonding_period(
+            origin: OriginFor<T>,
+            netuid: u16,
+            unbonding_period: u64,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(
+                T::Subtensor::if_subnet_exist(netuid),
+                Error::<T>::SubnetDoesNotExist
+            );
+            T::Subtensor::set_network_unbonding_period(netuid, unbonding_period);
+            log::info!(
+                "NetworkUnbondingPeriodSet( netuid: {:?}, unbonding_period: {:?} ) ",
+                netuid,
+                unbonding_period
+            );
+            Ok(())
+        }
+
+        /// The extrinsic sets the notice period for delegate take increases.
+        /// It is only callable by the root account.
+        /// The extrinsic will call the Subtensor pallet to set the take increase delay.
+        #[pallet::call_index(54)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_take_increase_delay(
+            origin: OriginFor<T>,
+            take_increase_delay: u64,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            T::Subtensor::set_take_increase_delay(take_increase_delay);
+            log::info!(
+                "TakeIncreaseDelaySet( take_increase_delay: {:?} ) ",
+                take_increase_delay
+            );
+            Ok(())
+        }
+
+        /// The extrinsic selects the stake rate limiter, counting operations or summing the moved amount.
+        /// It is only callable by the root account.
+        /// The extrinsic will call the Subtensor pallet to set the stake rate limit mode.
+        #[pallet::call_index(55)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_stake_rate_limit_mode(
+            origin: OriginFor<T>,
+            mode: pallet_subtensor::StakeRateLimitMode,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            T::Subtensor::set_stake_rate_limit_mode(mode);
+            log::info!("StakeRateLimitModeSet( mode: {:?} ) ", mode);
+            Ok(())
+        }
+
+        /// The extrinsic sets the length in blocks of the stake amount window.
+        /// It is only callable by the root account.
+        /// The extrinsic will call the Subtensor pallet to set the stake amount window.
+        #[pallet::call_index(56)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_stake_amount_window(
+            origin: OriginFor<T>,
+            stake_amount_window: u64,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            T::Subtensor::set_stake_amount_window(stake_amount_window);
+            log::info!(
+                "StakeAmountWindowSet( stake_amount_window: {:?} ) ",
+                stake_amount_window
+            );
+            Ok(())
+        }
+
+        /// The extrinsic sets the maximum amount of stake a coldkey may move on a hotkey per window.
+        /// It is only callable by the root account.
+        /// The extrinsic will call the Subtensor pallet to set the maximum stake amount per window.
+        #[pallet::call_index(57)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_max_stake_amount_per_window(
+            origin: OriginFor<T>,
+            max_stake_amount: u64,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            T::Subtensor::set_max_stake_amount_per_window(max_stake_amount);
+            log::info!(
+                "MaxStakeAmountPerWindowSet( max_stake_amount: {:?} ) ",
+                max_stake_amount
+            );
+            Ok(())
+        }
+
+        /// The extrinsic sets the maximum weights version key for a subnet, 0 removes the maximum.
+        /// The maximum may not be below the weights version key of the subnet.
+        /// It is only callable by the root account or subnet owner.
+        /// The extrinsic will call the Subtensor pallet to set the weights max version key.
+        #[pallet::call_index(58)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_weights_max_version_key(
+            origin: OriginFor<T>,
+            netuid: u16,
+            weights_max_version_key: u64,
+        ) -> DispatchResult {
+            T::Subtensor::ensure_subnet_owner_or_root(origin, netuid)?;
+
+            ensure!(
+                T::Subtensor::if_subnet_exist(netuid),
+                Error::<T>::SubnetDoesNotExist
+            );
+            ensure!(
+                weights_max_version_key == 0
+                    || weights_max_version_key >= T::Subtensor::get_weights_version_key(netuid),
+                Error::<T>::WeightsVersionKeyAboveMax
+            );
+            T::Subtensor::set_weights_max_version_key(netuid, weights_max_version_key);
+            log::info!(
+                "WeightsMaxVersionKeySet( netuid: {:?} weights_max_version_key: {:?} ) ",
+                netuid,
+                weights_max_version_key
+            );
+            Ok(())
+        }
+
+        /// The extrinsic sets the weights version keys banned on a subnet, replacing the previous list.
+        /// It is only callable by the root account or subnet owner.
+        /// The extrinsic will call the Subtensor pallet to set the banned weights version keys.
+        #[pallet::call_index(59)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_banned_weights_version_keys(
+            origin: OriginFor<T>,
+            netuid: u16,
+            banned_version_keys: Vec<u64>,
+        ) -> DispatchResult {
+            T::Subtensor::ensure_subnet_owner_or_root(origin, netuid)?;
+
+            ensure!(
+                T::Subtensor::if_subnet_exist(netuid),
+                Error::<T>::SubnetDoesNotExist
+            );
+            ensure!(
+                banned_version_keys.len()
+                    <= pallet_subtensor::MAX_BANNED_WEIGHTS_VERSION_KEYS as usize,
+                Error::<T>::TooManyBannedWeightsVersionKeys
+            );
+            log::info!(
+                "BannedWeightsVersionKeysSet( netuid: {:?} banned_version_keys: {:?} ) ",
+                netuid,
+                banned_version_keys
+            );
+            T::Subtensor::set_banned_weights_version_keys(netuid, banned_version_keys);
+            Ok(())
+        }
+
+        /// The extrinsic sets the weights window for a subnet, the number of blocks before an epoch
+        /// during which weights are accepted. The window closes when the epoch is staged, its
+        /// lead blocks before tempo. 0 accepts weights at any block, those set after the epoch
+        /// was staged only count from the next epoch.
+        /// It is only callable by the root account or subnet owner.
+        /// The extrinsic will call the Subtensor pallet to set the weights window.
+        #[pallet::call_index(60)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_weights_window(
+            origin: OriginFor<T>,
+            netuid: u16,
+            weights_window: u64,
+        ) -> DispatchResult {
+            T::Subtensor::ensure_subnet_owner_or_root(origin, netuid)?;
+
+            ensure!(
+                T::Subtensor::if_subnet_exist(netuid),
+                Error::<T>::SubnetDoesNotExist
+            );
+            T::Subtensor::set_weights_window(netuid, weights_window);
+            log::info!(
+                "WeightsWindowSet( netuid: {:?} weights_window: {:?} ) ",
+                netuid,
+                weights_window
+            );
+            Ok(())
+        }
+
+        /// The extrinsic sets the number of epoch summaries kept for a subnet. 0 disables the
+        /// epoch history. Changing the depth drops the summaries already recorded.
+        /// It is only callable by the root account.
+        /// The extrinsic will call the Subtensor pallet to set the epoch history depth.
+        #[pallet::call_index(61)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_epoch_history_depth(
+            origin: OriginFor<T>,
+            netuid: u16,
+            depth: u16,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            ensure!(
+                T::Subtensor::if_subnet_exist(netuid),
+                Error::<T>::SubnetDoesNotExist
+            );
+            ensure!(
+                depth <= pallet_subtensor::MAX_EPOCH_HISTORY_DEPTH,
+                Error::<T>::EpochHistoryDepthTooHigh
+            );
+            T::Subtensor::set_epoch_history_depth(netuid, depth);
+            log::info!(
+                "EpochHistoryDepthSet( netuid: {:?} depth: {:?} ) ",
+                netuid,
+                depth
+            );
+            Ok(())
+        }
+
+        /// The extrinsic sets the consensus mechanism computing the epoch terms of a subnet.
+        /// It is only callable by the root account or subnet owner.
+        /// The extrinsic will call the Subtensor pallet to set the consensus mechanism.
+        #[pallet::call_index(62)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_consensus_mechanism(
+            origin: OriginFor<T>,
+            netuid: u16,
+            mechanism: pallet_subtensor::ConsensusMechanism,
+        ) -> DispatchResult {
+            T::Subtensor::ensure_subnet_owner_or_root(origin, netuid)?;
+
+            ensure!(
+                T::Subtensor::if_subnet_exist(netuid),
+                Error::<T>::SubnetDoesNotExist
+            );
+            T::Subtensor::set_consensus_mechanism(netuid, mechanism);
+            log::info!(
+                "ConsensusMechanismSet( netuid: {:?} mechanism: {:?} ) ",
+                netuid,
+                mechanism
+            );
+            Ok(())
+        }
+
+        /// The extrinsic sets the maximum number of pending unbonding chunks per coldkey.
+        /// It is only callable by the root account.
+        /// The extrinsic will call the Subtensor pallet to set the maximum unbonding chunks.
+        #[pallet::call_index(63)]
+        #[pallet::weight((0, DispatchClass::Operational, Pays::No))]
+        pub fn sudo_set_max_unbonding_chunks(
+            origin: OriginFor<T>,
+            max_unbonding_chunks: u32,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            T::Subtensor::set_max_unbonding_chunks(max_unbonding_chunks);
+            log::info!(
+                "MaxUnbondingChunksSet( max_unbonding_chunks: {:?} ) ",
+                max_unbonding_chunks
+            );
+            Ok(())
+        }
     }
 }
 
@@ -1137,4 +1424,18 @@ pub trait SubtensorInterface<AccountId, Balance, RuntimeOrigin> {
         alpha_low: u16,
         alpha_high: u16,
     ) -> Result<(), DispatchError>;
+    fn set_unbonding_period(unbonding_period: u64);
+    fn set_network_unbonding_period(netuid: u16, unbonding_period: u64);
+    fn set_max_unbonding_chunks(max_unbonding_chunks: u32);
+    fn set_take_increase_delay(take_increase_delay: u64);
+    fn set_stake_rate_limit_mode(mode: pallet_subtensor::StakeRateLimitMode);
+    fn set_stake_amount_window(stake_amount_window: u64);
+    fn set_max_stake_amount_per_window(max_stake_amount: u64);
+    fn set_weights_max_version_key(netuid: u16, weights_max_version_key: u64);
+    fn set_banned_weights_version_keys(netuid: u16, banned_version_keys: Vec<u64>);
+    fn set_weights_window(netuid: u16, weights_window: u64);
+    fn set_epoch_history_depth(netuid: u16, depth: u16);
+    fn set_consensus_mechanism(netuid: u16, mechanism: pallet_subtensor::ConsensusMechanism);
+    fn get_weights_version_key(netuid: u16) -> u64;
+    fn get_weights_max_version_key(netuid: u16) -> u64;
 }
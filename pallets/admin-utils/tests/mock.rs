@@ -83,6 +83,7 @@ parameter_types! {
     pub const InitialServingRateLimit: u64 = 0; // No limit.
     pub const InitialTxRateLimit: u64 = 0; // Disable rate limit for testing
     pub const InitialTxDelegateTakeRateLimit: u64 = 0; // Disable rate limit for testing
+    pub const InitialTakeIncreaseDelay: u64 = 0; // Apply take increases immediately for testing
     pub const InitialBurn: u64 = 0;
     pub const InitialMinBurn: u64 = 0;
     pub const InitialMaxBurn: u64 = 1_000_000_000;
@@ -110,11 +111,14 @@ parameter_types! {
     pub const InitialSubnetLimit: u16 = 10; // Max 10 subnets.
     pub const InitialNetworkRateLimit: u64 = 0;
     pub const InitialTargetStakesPerInterval: u16 = 1;
+    pub const InitialStakeRateLimitMode: pallet_subtensor::StakeRateLimitMode = pallet_subtensor::StakeRateLimitMode::Count;
+    pub const InitialMaxStakeAmountPerWindow: u64 = u64::MAX; // Uncapped until set by governance
     pub const InitialHotkeySwapCost: u64 = 1_000_000_000;
     pub const InitialAlphaHigh: u16 = 58982; // Represents 0.9 as per the production default
     pub const InitialAlphaLow: u16 = 45875; // Represents 0.7 as per the production default
     pub const InitialLiquidAlphaOn: bool = false; // Default value for LiquidAlphaOn
     pub const InitialBaseDifficulty: u64 = 10_000; // Base difficulty
+    pub const MaxWeightCommits: u32 = 10;
 }
 
 impl pallet_subtensor::Config for Test {
@@ -153,6 +157,7 @@ impl pallet_subtensor::Config for Test {
     type InitialServingRateLimit = InitialServingRateLimit;
     type InitialTxRateLimit = InitialTxRateLimit;
     type InitialTxDelegateTakeRateLimit = InitialTxDelegateTakeRateLimit;
+    type InitialTakeIncreaseDelay = InitialTakeIncreaseDelay;
     type InitialBurn = InitialBurn;
     type InitialMaxBurn = InitialMaxBurn;
     type InitialMinBurn = InitialMinBurn;
@@ -166,11 +171,14 @@ impl pallet_subtensor::Config for Test {
     type InitialSubnetLimit = InitialSubnetLimit;
     type InitialNetworkRateLimit = InitialNetworkRateLimit;
     type InitialTargetStakesPerInterval = InitialTargetStakesPerInterval;
+    type InitialStakeRateLimitMode = InitialStakeRateLimitMode;
+    type InitialMaxStakeAmountPerWindow = InitialMaxStakeAmountPerWindow;
     type HotkeySwapCost = InitialHotkeySwapCost;
     type AlphaHigh = InitialAlphaHigh;
     type AlphaLow = InitialAlphaLow;
     type LiquidAlphaOn = InitialLiquidAlphaOn;
     type InitialBaseDifficulty = InitialBaseDifficulty;
+    type MaxWeightCommits = MaxWeightCommits;
 }
 
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
@@ -485,6 +493,62 @@ impl pallet_admin_utils::SubtensorInterface<AccountId, Balance, RuntimeOrigin> f
     ) -> Result<(), DispatchError> {
         SubtensorModule::do_set_alpha_values(origin, netuid, alpha_low, alpha_high)
     }
+
+    fn set_unbonding_period(unbonding_period: u64) {
+        SubtensorModule::set_unbonding_period(unbonding_period);
+    }
+
+    fn set_network_unbonding_period(netuid: u16, unbonding_period: u64) {
+        SubtensorModule::set_network_unbonding_period(netuid, unbonding_period);
+    }
+
+    fn set_max_unbonding_chunks(max_unbonding_chunks: u32) {
+        SubtensorModule::set_max_unbonding_chunks(max_unbonding_chunks);
+    }
+
+    fn set_take_increase_delay(take_increase_delay: u64) {
+        SubtensorModule::set_take_increase_delay(take_increase_delay);
+    }
+
+    fn set_stake_rate_limit_mode(mode: pallet_subtensor::StakeRateLimitMode) {
+        SubtensorModule::set_stake_rate_limit_mode(mode);
+    }
+
+    fn set_stake_amount_window(stake_amount_window: u64) {
+        SubtensorModule::set_stake_amount_window(stake_amount_window);
+    }
+
+    fn set_max_stake_amount_per_window(max_stake_amount: u64) {
+        SubtensorModule::set_max_stake_amount_per_window(max_stake_amount);
+    }
+
+    fn set_weights_max_version_key(netuid: u16, weights_max_version_key: u64) {
+        SubtensorModule::set_weights_max_version_key(netuid, weights_max_version_key);
+    }
+
+    fn set_banned_weights_version_keys(netuid: u16, banned_version_keys: Vec<u64>) {
+        SubtensorModule::set_banned_weights_version_keys(netuid, banned_version_keys);
+    }
+
+    fn set_weights_window(netuid: u16, weights_window: u64) {
+        SubtensorModule::set_weights_window(netuid, weights_window);
+    }
+
+    fn set_epoch_history_depth(netuid: u16, depth: u16) {
+        SubtensorModule::set_epoch_history_depth(netuid, depth);
+    }
+
+    fn set_consensus_mechanism(netuid: u16, mechanism: pallet_subtensor::ConsensusMechanism) {
+        SubtensorModule::set_consensus_mechanism(netuid, mechanism);
+    }
+
+    fn get_weights_version_key(netuid: u16) -> u64 {
+        SubtensorModule::get_weights_version_key(netuid)
+    }
+
+    fn get_weights_max_version_key(netuid: u16) -> u64 {
+        SubtensorModule::get_weights_max_version_key(netuid)
+    }
 }
 
 impl pallet_admin_utils::Config for Test {
@@ -153,6 +153,22 @@ fn test_sudo_set_weights_version_key() {
             to_be_set
         ));
         assert_eq!(SubtensorModule::get_weights_version_key(netuid), to_be_set);
+
+        // The version key may not be above the maximum version key.
+        SubtensorModule::set_weights_max_version_key(netuid, to_be_set);
+        assert_eq!(
+            AdminUtils::sudo_set_weights_version_key(
+                <<Test as Config>::RuntimeOrigin>::root(),
+                netuid,
+                to_be_set + 1
+            ),
+            Err(Error::<Test>::WeightsVersionKeyAboveMax.into())
+        );
+        assert_ok!(AdminUtils::sudo_set_weights_version_key(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            netuid,
+            to_be_set
+        ));
     });
 }
 
@@ -1361,3 +1377,378 @@ fn test_sudo_get_set_alpha() {
         ));
     });
 }
+
+#[test]
+fn test_sudo_set_unbonding_period() {
+    new_test_ext().execute_with(|| {
+        let to_be_set: u64 = 7200;
+        let init_value: u64 = SubtensorModule::get_unbonding_period();
+        assert_eq!(
+            AdminUtils::sudo_set_unbonding_period(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(1)),
+                to_be_set
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+        assert_eq!(SubtensorModule::get_unbonding_period(), init_value);
+        assert_ok!(AdminUtils::sudo_set_unbonding_period(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            to_be_set
+        ));
+        assert_eq!(SubtensorModule::get_unbonding_period(), to_be_set);
+    });
+}
+
+#[test]
+fn test_sudo_set_network_unbonding_period() {
+    new_test_ext().execute_with(|| {
+        let netuid: u16 = 1;
+        let to_be_set: u64 = 360;
+        add_network(netuid, 10);
+        let init_value: u64 = SubtensorModule::get_network_unbonding_period(netuid);
+        assert_eq!(
+            AdminUtils::sudo_set_network_unbonding_period(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(1)),
+                netuid,
+                to_be_set
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+        assert_eq!(
+            AdminUtils::sudo_set_network_unbonding_period(
+                <<Test as Config>::RuntimeOrigin>::root(),
+                netuid + 1,
+                to_be_set
+            ),
+            Err(Error::<Test>::SubnetDoesNotExist.into())
+        );
+        assert_eq!(
+            SubtensorModule::get_network_unbonding_period(netuid),
+            init_value
+        );
+        assert_ok!(AdminUtils::sudo_set_network_unbonding_period(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            netuid,
+            to_be_set
+        ));
+        assert_eq!(
+            SubtensorModule::get_network_unbonding_period(netuid),
+            to_be_set
+        );
+    });
+}
+
+#[test]
+fn test_sudo_set_max_unbonding_chunks() {
+    new_test_ext().execute_with(|| {
+        let to_be_set: u32 = 8;
+        let init_value: u32 = SubtensorModule::get_max_unbonding_chunks();
+        assert_eq!(
+            AdminUtils::sudo_set_max_unbonding_chunks(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(1)),
+                to_be_set
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+        assert_eq!(SubtensorModule::get_max_unbonding_chunks(), init_value);
+        assert_ok!(AdminUtils::sudo_set_max_unbonding_chunks(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            to_be_set
+        ));
+        assert_eq!(SubtensorModule::get_max_unbonding_chunks(), to_be_set);
+    });
+}
+
+#[test]
+fn test_sudo_set_take_increase_delay() {
+    new_test_ext().execute_with(|| {
+        let to_be_set: u64 = 50400;
+        let init_value: u64 = SubtensorModule::get_take_increase_delay();
+        assert_eq!(
+            AdminUtils::sudo_set_take_increase_delay(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(1)),
+                to_be_set
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+        assert_eq!(SubtensorModule::get_take_increase_delay(), init_value);
+        assert_ok!(AdminUtils::sudo_set_take_increase_delay(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            to_be_set
+        ));
+        assert_eq!(SubtensorModule::get_take_increase_delay(), to_be_set);
+    });
+}
+
+#[test]
+fn test_sudo_set_stake_rate_limit_mode() {
+    new_test_ext().execute_with(|| {
+        let to_be_set = pallet_subtensor::StakeRateLimitMode::Amount;
+        let init_value = SubtensorModule::get_stake_rate_limit_mode();
+        assert_eq!(
+            AdminUtils::sudo_set_stake_rate_limit_mode(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(1)),
+                to_be_set
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+        assert_eq!(SubtensorModule::get_stake_rate_limit_mode(), init_value);
+        assert_ok!(AdminUtils::sudo_set_stake_rate_limit_mode(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            to_be_set
+        ));
+        assert_eq!(SubtensorModule::get_stake_rate_limit_mode(), to_be_set);
+    });
+}
+
+#[test]
+fn test_sudo_set_stake_amount_window() {
+    new_test_ext().execute_with(|| {
+        let to_be_set: u64 = 7200;
+        let init_value: u64 = SubtensorModule::get_stake_amount_window();
+        assert_eq!(
+            AdminUtils::sudo_set_stake_amount_window(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(1)),
+                to_be_set
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+        assert_eq!(SubtensorModule::get_stake_amount_window(), init_value);
+        assert_ok!(AdminUtils::sudo_set_stake_amount_window(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            to_be_set
+        ));
+        assert_eq!(SubtensorModule::get_stake_amount_window(), to_be_set);
+    });
+}
+
+#[test]
+fn test_sudo_set_max_stake_amount_per_window() {
+    new_test_ext().execute_with(|| {
+        let to_be_set: u64 = 1_000_000_000_000;
+        let init_value: u64 = SubtensorModule::get_max_stake_amount_per_window();
+        assert_eq!(
+            AdminUtils::sudo_set_max_stake_amount_per_window(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(1)),
+                to_be_set
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+        assert_eq!(
+            SubtensorModule::get_max_stake_amount_per_window(),
+            init_value
+        );
+        assert_ok!(AdminUtils::sudo_set_max_stake_amount_per_window(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            to_be_set
+        ));
+        assert_eq!(
+            SubtensorModule::get_max_stake_amount_per_window(),
+            to_be_set
+        );
+    });
+}
+
+#[test]
+fn test_sudo_set_weights_max_version_key() {
+    new_test_ext().execute_with(|| {
+        let netuid: u16 = 1;
+        let to_be_set: u64 = 20;
+        add_network(netuid, 10);
+        let init_value: u64 = SubtensorModule::get_weights_max_version_key(netuid);
+        assert_eq!(
+            AdminUtils::sudo_set_weights_max_version_key(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(1)),
+                netuid,
+                to_be_set
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+        assert_eq!(
+            AdminUtils::sudo_set_weights_max_version_key(
+                <<Test as Config>::RuntimeOrigin>::root(),
+                netuid + 1,
+                to_be_set
+            ),
+            Err(Error::<Test>::SubnetDoesNotExist.into())
+        );
+        assert_eq!(
+            SubtensorModule::get_weights_max_version_key(netuid),
+            init_value
+        );
+        assert_ok!(AdminUtils::sudo_set_weights_max_version_key(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            netuid,
+            to_be_set
+        ));
+        assert_eq!(
+            SubtensorModule::get_weights_max_version_key(netuid),
+            to_be_set
+        );
+
+        // The maximum may not be below the version key, but can still be removed.
+        SubtensorModule::set_weights_version_key(netuid, to_be_set);
+        assert_eq!(
+            AdminUtils::sudo_set_weights_max_version_key(
+                <<Test as Config>::RuntimeOrigin>::root(),
+                netuid,
+                to_be_set - 1
+            ),
+            Err(Error::<Test>::WeightsVersionKeyAboveMax.into())
+        );
+        assert_ok!(AdminUtils::sudo_set_weights_max_version_key(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            netuid,
+            0
+        ));
+    });
+}
+
+#[test]
+fn test_sudo_set_banned_weights_version_keys() {
+    new_test_ext().execute_with(|| {
+        let netuid: u16 = 1;
+        add_network(netuid, 10);
+        assert_eq!(
+            AdminUtils::sudo_set_banned_weights_version_keys(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(1)),
+                netuid,
+                vec![7]
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+        assert_eq!(
+            AdminUtils::sudo_set_banned_weights_version_keys(
+                <<Test as Config>::RuntimeOrigin>::root(),
+                netuid + 1,
+                vec![7]
+            ),
+            Err(Error::<Test>::SubnetDoesNotExist.into())
+        );
+        assert_eq!(
+            AdminUtils::sudo_set_banned_weights_version_keys(
+                <<Test as Config>::RuntimeOrigin>::root(),
+                netuid,
+                (0..=pallet_subtensor::MAX_BANNED_WEIGHTS_VERSION_KEYS as u64).collect()
+            ),
+            Err(Error::<Test>::TooManyBannedWeightsVersionKeys.into())
+        );
+        assert!(SubtensorModule::get_banned_weights_version_keys(netuid).is_empty());
+        assert_ok!(AdminUtils::sudo_set_banned_weights_version_keys(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            netuid,
+            vec![9, 7]
+        ));
+        assert_eq!(
+            SubtensorModule::get_banned_weights_version_keys(netuid),
+            vec![7, 9]
+        );
+    });
+}
+
+#[test]
+fn test_sudo_set_weights_window() {
+    new_test_ext().execute_with(|| {
+        let netuid: u16 = 1;
+        let to_be_set: u64 = 5;
+        add_network(netuid, 10);
+        let init_value: u64 = SubtensorModule::get_weights_window(netuid);
+        assert_eq!(
+            AdminUtils::sudo_set_weights_window(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(1)),
+                netuid,
+                to_be_set
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+        assert_eq!(
+            AdminUtils::sudo_set_weights_window(
+                <<Test as Config>::RuntimeOrigin>::root(),
+                netuid + 1,
+                to_be_set
+            ),
+            Err(Error::<Test>::SubnetDoesNotExist.into())
+        );
+        assert_eq!(SubtensorModule::get_weights_window(netuid), init_value);
+        assert_ok!(AdminUtils::sudo_set_weights_window(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            netuid,
+            to_be_set
+        ));
+        assert_eq!(SubtensorModule::get_weights_window(netuid), to_be_set);
+    });
+}
+
+#[test]
+fn test_sudo_set_epoch_history_depth() {
+    new_test_ext().execute_with(|| {
+        let netuid: u16 = 1;
+        let to_be_set: u16 = 8;
+        add_network(netuid, 10);
+        let init_value: u16 = SubtensorModule::get_epoch_history_depth(netuid);
+        assert_eq!(
+            AdminUtils::sudo_set_epoch_history_depth(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(1)),
+                netuid,
+                to_be_set
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+        assert_eq!(
+            AdminUtils::sudo_set_epoch_history_depth(
+                <<Test as Config>::RuntimeOrigin>::root(),
+                netuid + 1,
+                to_be_set
+            ),
+            Err(Error::<Test>::SubnetDoesNotExist.into())
+        );
+        assert_eq!(
+            AdminUtils::sudo_set_epoch_history_depth(
+                <<Test as Config>::RuntimeOrigin>::root(),
+                netuid,
+                pallet_subtensor::MAX_EPOCH_HISTORY_DEPTH + 1
+            ),
+            Err(Error::<Test>::EpochHistoryDepthTooHigh.into())
+        );
+        assert_eq!(SubtensorModule::get_epoch_history_depth(netuid), init_value);
+        assert_ok!(AdminUtils::sudo_set_epoch_history_depth(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            netuid,
+            to_be_set
+        ));
+        assert_eq!(SubtensorModule::get_epoch_history_depth(netuid), to_be_set);
+    });
+}
+
+#[test]
+fn test_sudo_set_consensus_mechanism() {
+    new_test_ext().execute_with(|| {
+        let netuid: u16 = 1;
+        let to_be_set = pallet_subtensor::ConsensusMechanism::StakeWeightedMean;
+        add_network(netuid, 10);
+        let init_value = SubtensorModule::get_consensus_mechanism(netuid);
+        assert_eq!(
+            AdminUtils::sudo_set_consensus_mechanism(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(1)),
+                netuid,
+                to_be_set
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+        assert_eq!(
+            AdminUtils::sudo_set_consensus_mechanism(
+                <<Test as Config>::RuntimeOrigin>::root(),
+                netuid + 1,
+                to_be_set
+            ),
+            Err(Error::<Test>::SubnetDoesNotExist.into())
+        );
+        assert_eq!(SubtensorModule::get_consensus_mechanism(netuid), init_value);
+        assert_ok!(AdminUtils::sudo_set_consensus_mechanism(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            netuid,
+            to_be_set
+        ));
+        assert_eq!(SubtensorModule::get_consensus_mechanism(netuid), to_be_set);
+    });
+}
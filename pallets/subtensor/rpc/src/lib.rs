@@ -12,8 +12,8 @@ use std::sync::Arc;
 use sp_api::ProvideRuntimeApi;
 
 pub use subtensor_custom_rpc_runtime_api::{
-    ColdkeySwapRuntimeApi, DelegateInfoRuntimeApi, NeuronInfoRuntimeApi, SubnetInfoRuntimeApi,
-    SubnetRegistrationRuntimeApi, SubtensorRuntimeApi,
+    ColdkeySwapRuntimeApi, DelegateInfoRuntimeApi, NeuronInfoRuntimeApi, StakeInfoRuntimeApi,
+    SubnetInfoRuntimeApi, SubnetRegistrationRuntimeApi, SubtensorRuntimeApi,
 };
 
 #[rpc(client, server)]
@@ -41,6 +41,16 @@ pub trait SubtensorCustomApi<BlockHash> {
     fn get_neurons(&self, netuid: u16, at: Option<BlockHash>) -> RpcResult<Vec<u8>>;
     #[method(name = "neuronInfo_getNeuron")]
     fn get_neuron(&self, netuid: u16, uid: u16, at: Option<BlockHash>) -> RpcResult<Vec<u8>>;
+    #[method(name = "neuronInfo_getValidatorAgreement")]
+    fn get_validator_agreement(&self, netuid: u16, at: Option<BlockHash>) -> RpcResult<Vec<u8>>;
+    #[method(name = "neuronInfo_getEpochHistory")]
+    fn get_epoch_history(
+        &self,
+        netuid: u16,
+        uid: u16,
+        count: u16,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<u8>>;
 
     #[method(name = "subnetInfo_getSubnetInfo")]
     fn get_subnet_info(&self, netuid: u16, at: Option<BlockHash>) -> RpcResult<Vec<u8>>;
@@ -48,6 +58,21 @@ pub trait SubtensorCustomApi<BlockHash> {
     fn get_subnets_info(&self, at: Option<BlockHash>) -> RpcResult<Vec<u8>>;
     #[method(name = "subnetInfo_getSubnetHyperparams")]
     fn get_subnet_hyperparams(&self, netuid: u16, at: Option<BlockHash>) -> RpcResult<Vec<u8>>;
+    #[method(name = "subnetInfo_getPendingWeightCommits")]
+    fn get_pending_weight_commits(&self, netuid: u16, at: Option<BlockHash>) -> RpcResult<Vec<u8>>;
+
+    #[method(name = "stakeInfo_getUnbondingForColdkey")]
+    fn get_unbonding_for_coldkey(
+        &self,
+        coldkey_account_vec: Vec<u8>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<u8>>;
+    #[method(name = "stakeInfo_getRewards")]
+    fn get_rewards(
+        &self,
+        coldkey_account_vec: Vec<u8>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<u8>>;
 
     #[method(name = "subnetInfo_getLockCost")]
     fn get_network_lock_cost(&self, at: Option<BlockHash>) -> RpcResult<u64>;
@@ -77,6 +102,25 @@ pub trait SubtensorCustomApi<BlockHash> {
         incentive_opt: Option<bool>,
         at: Option<BlockHash>,
     ) -> RpcResult<Vec<u8>>;
+    #[method(name = "subtensor_checkWeights")]
+    fn check_weights(
+        &self,
+        netuid: u16,
+        hotkey_account_vec: Vec<u8>,
+        uids: Vec<u16>,
+        values: Vec<u16>,
+        version_key: u64,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<u8>>;
+    #[method(name = "subtensor_simulateEpoch")]
+    fn simulate_epoch(
+        &self,
+        netuid: u16,
+        overrides: Vec<u8>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<u8>>;
+    #[method(name = "subtensor_getEpochTrace")]
+    fn get_epoch_trace(&self, netuid: u16, at: Option<BlockHash>) -> RpcResult<Vec<u8>>;
 }
 
 pub struct SubtensorCustom<C, P> {
@@ -124,6 +168,7 @@ where
     C::Api: DelegateInfoRuntimeApi<Block>,
     C::Api: NeuronInfoRuntimeApi<Block>,
     C::Api: SubnetInfoRuntimeApi<Block>,
+    C::Api: StakeInfoRuntimeApi<Block>,
     C::Api: SubnetRegistrationRuntimeApi<Block>,
     C::Api: ColdkeySwapRuntimeApi<Block>,
     C::Api: SubtensorRuntimeApi<Block>,
@@ -211,6 +256,34 @@ where
             .map_err(|e| Error::RuntimeError(format!("Unable to get neuron info: {:?}", e)).into())
     }
 
+    fn get_validator_agreement(
+        &self,
+        netuid: u16,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<u8>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.get_validator_agreement(at, netuid).map_err(|e| {
+            Error::RuntimeError(format!("Unable to get validator agreement: {:?}", e)).into()
+        })
+    }
+
+    fn get_epoch_history(
+        &self,
+        netuid: u16,
+        uid: u16,
+        count: u16,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<u8>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.get_epoch_history(at, netuid, uid, count).map_err(|e| {
+            Error::RuntimeError(format!("Unable to get epoch history: {:?}", e)).into()
+        })
+    }
+
     fn get_subnet_info(
         &self,
         netuid: u16,
@@ -235,6 +308,19 @@ where
             .map_err(|e| Error::RuntimeError(format!("Unable to get subnet info: {:?}", e)).into())
     }
 
+    fn get_pending_weight_commits(
+        &self,
+        netuid: u16,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<u8>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.get_pending_weight_commits(at, netuid).map_err(|e| {
+            Error::RuntimeError(format!("Unable to get pending weight commits: {:?}", e)).into()
+        })
+    }
+
     fn get_subnets_info(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<u8>> {
         let api = self.client.runtime_api();
         let at = at.unwrap_or_else(|| self.client.info().best_hash);
@@ -243,6 +329,32 @@ where
             .map_err(|e| Error::RuntimeError(format!("Unable to get subnets info: {:?}", e)).into())
     }
 
+    fn get_unbonding_for_coldkey(
+        &self,
+        coldkey_account_vec: Vec<u8>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<u8>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.get_unbonding_for_coldkey(at, coldkey_account_vec)
+            .map_err(|e| {
+                Error::RuntimeError(format!("Unable to get unbonding info: {:?}", e)).into()
+            })
+    }
+
+    fn get_rewards(
+        &self,
+        coldkey_account_vec: Vec<u8>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<u8>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.get_rewards_for_coldkey(at, coldkey_account_vec)
+            .map_err(|e| Error::RuntimeError(format!("Unable to get rewards: {:?}", e)).into())
+    }
+
     fn get_network_lock_cost(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<u64> {
         let api = self.client.runtime_api();
         let at = at.unwrap_or_else(|| self.client.info().best_hash);
@@ -311,4 +423,45 @@ where
         api.get_epoch(at, netuid, incentive_opt)
             .map_err(|e| Error::RuntimeError(format!("Unable to get epch: {:?}", e)).into())
     }
+
+    fn check_weights(
+        &self,
+        netuid: u16,
+        hotkey_account_vec: Vec<u8>,
+        uids: Vec<u16>,
+        values: Vec<u16>,
+        version_key: u64,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<u8>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.check_weights(at, netuid, hotkey_account_vec, uids, values, version_key)
+            .map_err(|e| Error::RuntimeError(format!("Unable to check weights: {:?}", e)).into())
+    }
+
+    fn simulate_epoch(
+        &self,
+        netuid: u16,
+        overrides: Vec<u8>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<u8>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.simulate_epoch(at, netuid, overrides)
+            .map_err(|e| Error::RuntimeError(format!("Unable to simulate epoch: {:?}", e)).into())
+    }
+
+    fn get_epoch_trace(
+        &self,
+        netuid: u16,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<u8>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.get_epoch_trace(at, netuid)
+            .map_err(|e| Error::RuntimeError(format!("Unable to get epoch trace: {:?}", e)).into())
+    }
 }
@@ -16,17 +16,22 @@ sp_api::decl_runtime_apis! {
         fn get_neuron(netuid: u16, uid: u16) -> Vec<u8>;
         fn get_neurons_lite(netuid: u16) -> Vec<u8>;
         fn get_neuron_lite(netuid: u16, uid: u16) -> Vec<u8>;
+        fn get_validator_agreement(netuid: u16) -> Vec<u8>;
+        fn get_epoch_history(netuid: u16, uid: u16, count: u16) -> Vec<u8>;
     }
 
     pub trait SubnetInfoRuntimeApi {
         fn get_subnet_info(netuid: u16) -> Vec<u8>;
         fn get_subnets_info() -> Vec<u8>;
         fn get_subnet_hyperparams(netuid: u16) -> Vec<u8>;
+        fn get_pending_weight_commits(netuid: u16) -> Vec<u8>;
     }
 
     pub trait StakeInfoRuntimeApi {
         fn get_stake_info_for_coldkey( coldkey_account_vec: Vec<u8> ) -> Vec<u8>;
         fn get_stake_info_for_coldkeys( coldkey_account_vecs: Vec<Vec<u8>> ) -> Vec<u8>;
+        fn get_unbonding_for_coldkey( coldkey_account_vec: Vec<u8> ) -> Vec<u8>;
+        fn get_rewards_for_coldkey( coldkey_account_vec: Vec<u8> ) -> Vec<u8>;
     }
 
     pub trait SubnetRegistrationRuntimeApi {
@@ -41,5 +46,8 @@ sp_api::decl_runtime_apis! {
 
     pub trait SubtensorRuntimeApi {
         fn get_epoch( netuid: u16, incentive_opt: Option<bool> ) -> Vec<u8>;
+        fn check_weights( netuid: u16, hotkey_account_vec: Vec<u8>, uids: Vec<u16>, values: Vec<u16>, version_key: u64 ) -> Vec<u8>;
+        fn simulate_epoch( netuid: u16, overrides: Vec<u8> ) -> Vec<u8>;
+        fn get_epoch_trace( netuid: u16 ) -> Vec<u8>;
     }
 }
@@ -4,8 +4,10 @@
 
 use crate::Pallet as Subtensor;
 use crate::*;
+use codec::Encode;
 use frame_benchmarking::{account, benchmarks, whitelisted_caller};
 use frame_support::assert_ok;
+use frame_support::weights::Weight;
 use frame_system::RawOrigin;
 pub use pallet::*;
 use sp_core::H256;
@@ -75,6 +77,83 @@ benchmarks! {
 
   }: set_weights(RawOrigin::Signed( signer.clone() ), netuid, dests, weights, version_key)
 
+  benchmark_set_weights_delta {
+    // The changes update part of an existing full row.
+    let c in 1 .. 4096;
+    let netuid: u16 = 1;
+    let version_key: u64 = 1;
+    let tempo: u16 = 1;
+
+    Subtensor::<T>::init_new_network(netuid, tempo);
+    Subtensor::<T>::set_max_allowed_uids( netuid, 4096 );
+
+    Subtensor::<T>::set_network_registration_allowed( netuid, true );
+    Subtensor::<T>::set_max_registrations_per_block( netuid, 4096 );
+    Subtensor::<T>::set_target_registrations_per_interval( netuid, 4096 );
+
+    let mut seed : u32 = 1;
+    let mut row: Vec<(u16, u16)> = vec![];
+    let signer : T::AccountId = account("Alice", 0, seed);
+
+    for id in 0..4096_u16 {
+      let hotkey: T::AccountId = account("Alice", 0, seed);
+      let coldkey: T::AccountId = account("Test", 0, seed);
+      seed += 1;
+
+      Subtensor::<T>::set_burn(netuid, 1);
+      let amount_to_be_staked = 1000000u32.into();
+      Subtensor::<T>::add_balance_to_coldkey_account(&coldkey.clone(), amount_to_be_staked);
+
+      Subtensor::<T>::do_burned_registration(RawOrigin::Signed(coldkey.clone()).into(), netuid, hotkey.clone())?;
+
+      let uid = Subtensor::<T>::get_uid_for_net_and_hotkey(netuid, &hotkey.clone()).unwrap();
+      Subtensor::<T>::set_validator_permit_for_uid(netuid, uid, true);
+      row.push((id, u16::MAX));
+    }
+
+    Weights::<T>::insert(netuid, 0, row);
+    let changes: Vec<(u16, u16)> = (0..c as u16).map(|id| (id, id)).collect();
+
+  }: set_weights_delta(RawOrigin::Signed( signer.clone() ), netuid, changes, version_key)
+
+  benchmark_batch_set_weights {
+    // Every entry sets a full row on its own subnet.
+    let n in 1 .. MAX_WEIGHTS_BATCH_SIZE;
+    let version_key: u64 = 1;
+    let tempo: u16 = 1;
+    let signer : T::AccountId = account("Alice", 0, 1);
+    let mut weights: Vec<(u16, Vec<u16>, Vec<u16>, u64)> = vec![];
+
+    for netuid in 1..=(n as u16) {
+      Subtensor::<T>::init_new_network(netuid, tempo);
+      Subtensor::<T>::set_max_allowed_uids( netuid, 4096 );
+      Subtensor::<T>::set_network_registration_allowed( netuid, true );
+      Subtensor::<T>::set_max_registrations_per_block( netuid, 4096 );
+      Subtensor::<T>::set_target_registrations_per_interval( netuid, 4096 );
+      Subtensor::<T>::set_burn(netuid, 1);
+
+      let mut seed : u32 = 1;
+      let mut dests: Vec<u16> = vec![];
+      let mut values: Vec<u16> = vec![];
+      for id in 0..4096_u16 {
+        let hotkey: T::AccountId = account("Alice", 0, seed);
+        let coldkey: T::AccountId = account("Test", 0, seed);
+        seed += 1;
+
+        let amount_to_be_staked = 1000000u32.into();
+        Subtensor::<T>::add_balance_to_coldkey_account(&coldkey.clone(), amount_to_be_staked);
+        Subtensor::<T>::do_burned_registration(RawOrigin::Signed(coldkey.clone()).into(), netuid, hotkey.clone())?;
+
+        let uid = Subtensor::<T>::get_uid_for_net_and_hotkey(netuid, &hotkey.clone()).unwrap();
+        Subtensor::<T>::set_validator_permit_for_uid(netuid, uid, true);
+        dests.push(id);
+        values.push(id);
+      }
+      weights.push((netuid, dests, values, version_key));
+    }
+
+  }: batch_set_weights(RawOrigin::Signed( signer.clone() ), weights)
+
 
   benchmark_become_delegate {
     // This is a whitelisted caller who can make transaction without weights.
@@ -170,6 +249,195 @@ benchmarks! {
     let amount_unstaked: u64 = u64_staked_amt - 1;
   }: remove_stake(RawOrigin::Signed( coldkey.clone() ), hotkey.clone(), amount_unstaked)
 
+  benchmark_move_stake {
+    let netuid: u16 = 1;
+    let tempo: u16 = 1;
+    let seed : u32 = 1;
+
+    Subtensor::<T>::set_target_stakes_per_interval(100);
+
+    Subtensor::<T>::init_new_network(netuid, tempo);
+    Subtensor::<T>::set_burn(netuid, 1);
+    Subtensor::<T>::set_network_registration_allowed( netuid, true );
+    Subtensor::<T>::set_max_allowed_uids( netuid, 4096 );
+
+    let coldkey: T::AccountId = account("Test", 0, seed);
+    let origin_hotkey: T::AccountId = account("Alice", 0, seed);
+    let destination_hotkey: T::AccountId = account("Bob", 0, seed);
+
+    let wallet_bal = 1000000u32.into();
+    Subtensor::<T>::add_balance_to_coldkey_account(&coldkey.clone(), wallet_bal);
+
+    assert_ok!(Subtensor::<T>::do_burned_registration(RawOrigin::Signed(coldkey.clone()).into(), netuid, origin_hotkey.clone()));
+    assert_ok!(Subtensor::<T>::do_burned_registration(RawOrigin::Signed(coldkey.clone()).into(), netuid, destination_hotkey.clone()));
+
+    let u64_staked_amt = 100_000_000_000;
+    Subtensor::<T>::add_balance_to_coldkey_account(&coldkey.clone(), u64_staked_amt);
+    assert_ok!( Subtensor::<T>::add_stake(RawOrigin::Signed( coldkey.clone() ).into() , origin_hotkey.clone(), u64_staked_amt));
+
+    let amount_moved: u64 = u64_staked_amt - 1;
+  }: move_stake(RawOrigin::Signed( coldkey.clone() ), origin_hotkey.clone(), destination_hotkey.clone(), amount_moved)
+
+  benchmark_transfer_stake {
+    let netuid: u16 = 1;
+    let tempo: u16 = 1;
+    let seed : u32 = 1;
+
+    Subtensor::<T>::set_target_stakes_per_interval(100);
+
+    Subtensor::<T>::init_new_network(netuid, tempo);
+    Subtensor::<T>::set_burn(netuid, 1);
+    Subtensor::<T>::set_network_registration_allowed( netuid, true );
+    Subtensor::<T>::set_max_allowed_uids( netuid, 4096 );
+
+    let coldkey: T::AccountId = account("Test", 0, seed);
+    let destination_coldkey: T::AccountId = account("Test", 1, seed);
+    let hotkey: T::AccountId = account("Alice", 0, seed);
+
+    let wallet_bal = 1000000u32.into();
+    Subtensor::<T>::add_balance_to_coldkey_account(&coldkey.clone(), wallet_bal);
+
+    assert_ok!(Subtensor::<T>::do_burned_registration(RawOrigin::Signed(coldkey.clone()).into(), netuid, hotkey.clone()));
+    assert_ok!(Subtensor::<T>::do_become_delegate(RawOrigin::Signed(coldkey.clone()).into(), hotkey.clone(), Subtensor::<T>::get_default_take()));
+
+    let u64_staked_amt = 100_000_000_000;
+    Subtensor::<T>::add_balance_to_coldkey_account(&coldkey.clone(), u64_staked_amt);
+    assert_ok!( Subtensor::<T>::add_stake(RawOrigin::Signed( coldkey.clone() ).into() , hotkey.clone(), u64_staked_amt));
+
+    let amount_transferred: u64 = u64_staked_amt - 1;
+  }: transfer_stake(RawOrigin::Signed( coldkey.clone() ), destination_coldkey.clone(), hotkey.clone(), amount_transferred)
+
+  benchmark_set_nomination_allowlist_enabled {
+    let netuid: u16 = 1;
+    let tempo: u16 = 1;
+    let seed : u32 = 1;
+
+    Subtensor::<T>::init_new_network(netuid, tempo);
+    Subtensor::<T>::set_burn(netuid, 1);
+    Subtensor::<T>::set_network_registration_allowed( netuid, true );
+    Subtensor::<T>::set_max_allowed_uids( netuid, 4096 );
+
+    let coldkey: T::AccountId = account("Test", 0, seed);
+    let hotkey: T::AccountId = account("Alice", 0, seed);
+
+    let wallet_bal = 1000000u32.into();
+    Subtensor::<T>::add_balance_to_coldkey_account(&coldkey.clone(), wallet_bal);
+
+    assert_ok!(Subtensor::<T>::do_burned_registration(RawOrigin::Signed(coldkey.clone()).into(), netuid, hotkey.clone()));
+    assert_ok!(Subtensor::<T>::do_become_delegate(RawOrigin::Signed(coldkey.clone()).into(), hotkey.clone(), Subtensor::<T>::get_default_take()));
+  }: set_nomination_allowlist_enabled(RawOrigin::Signed( coldkey.clone() ), hotkey.clone(), true)
+
+  benchmark_set_nomination_allowed {
+    let netuid: u16 = 1;
+    let tempo: u16 = 1;
+    let seed : u32 = 1;
+
+    Subtensor::<T>::init_new_network(netuid, tempo);
+    Subtensor::<T>::set_burn(netuid, 1);
+    Subtensor::<T>::set_network_registration_allowed( netuid, true );
+    Subtensor::<T>::set_max_allowed_uids( netuid, 4096 );
+
+    let coldkey: T::AccountId = account("Test", 0, seed);
+    let nominator: T::AccountId = account("Test", 1, seed);
+    let hotkey: T::AccountId = account("Alice", 0, seed);
+
+    let wallet_bal = 1000000u32.into();
+    Subtensor::<T>::add_balance_to_coldkey_account(&coldkey.clone(), wallet_bal);
+
+    assert_ok!(Subtensor::<T>::do_burned_registration(RawOrigin::Signed(coldkey.clone()).into(), netuid, hotkey.clone()));
+    assert_ok!(Subtensor::<T>::do_become_delegate(RawOrigin::Signed(coldkey.clone()).into(), hotkey.clone(), Subtensor::<T>::get_default_take()));
+  }: set_nomination_allowed(RawOrigin::Signed( coldkey.clone() ), hotkey.clone(), nominator.clone(), true)
+
+  benchmark_set_nomination_limits {
+    let netuid: u16 = 1;
+    let tempo: u16 = 1;
+    let seed : u32 = 1;
+
+    Subtensor::<T>::init_new_network(netuid, tempo);
+    Subtensor::<T>::set_burn(netuid, 1);
+    Subtensor::<T>::set_network_registration_allowed( netuid, true );
+    Subtensor::<T>::set_max_allowed_uids( netuid, 4096 );
+
+    let coldkey: T::AccountId = account("Test", 0, seed);
+    let hotkey: T::AccountId = account("Alice", 0, seed);
+
+    let wallet_bal = 1000000u32.into();
+    Subtensor::<T>::add_balance_to_coldkey_account(&coldkey.clone(), wallet_bal);
+
+    assert_ok!(Subtensor::<T>::do_burned_registration(RawOrigin::Signed(coldkey.clone()).into(), netuid, hotkey.clone()));
+    assert_ok!(Subtensor::<T>::do_become_delegate(RawOrigin::Signed(coldkey.clone()).into(), hotkey.clone(), Subtensor::<T>::get_default_take()));
+  }: set_nomination_limits(RawOrigin::Signed( coldkey.clone() ), hotkey.clone(), 10, 1_000_000_000)
+
+  benchmark_add_stake_multiple {
+    // Every leg nominates a delegate with an allowlist and limits, under the amount limiter.
+    let n in 1 .. MAX_STAKE_BATCH_SIZE;
+    let netuid: u16 = 1;
+    let tempo: u16 = 1;
+    let seed : u32 = 1;
+
+    Subtensor::<T>::set_target_stakes_per_interval(100);
+    Subtensor::<T>::set_stake_rate_limit_mode(StakeRateLimitMode::Amount);
+    Subtensor::<T>::set_max_stake_amount_per_window(u64::MAX);
+
+    Subtensor::<T>::init_new_network(netuid, tempo);
+    Subtensor::<T>::set_burn(netuid, 1);
+    Subtensor::<T>::set_network_registration_allowed( netuid, true );
+    Subtensor::<T>::set_max_allowed_uids( netuid, 4096 );
+    Subtensor::<T>::set_max_registrations_per_block( netuid, 4096 );
+    Subtensor::<T>::set_target_registrations_per_interval( netuid, 4096 );
+
+    let coldkey: T::AccountId = account("Test", 0, seed);
+    Subtensor::<T>::add_balance_to_coldkey_account(&coldkey.clone(), 1_000_000_000_000u64);
+
+    let mut stakes: Vec<(T::AccountId, u64)> = vec![];
+    for i in 0..n {
+      let owner: T::AccountId = account("Owner", i, seed);
+      let hotkey: T::AccountId = account("Alice", i, seed);
+      Subtensor::<T>::add_balance_to_coldkey_account(&owner.clone(), 1_000_000u64);
+      assert_ok!(Subtensor::<T>::do_burned_registration(RawOrigin::Signed(owner.clone()).into(), netuid, hotkey.clone()));
+      assert_ok!(Subtensor::<T>::do_become_delegate(RawOrigin::Signed(owner.clone()).into(), hotkey.clone(), Subtensor::<T>::get_default_take()));
+      assert_ok!(Subtensor::<T>::do_set_nomination_allowed(RawOrigin::Signed(owner.clone()).into(), hotkey.clone(), coldkey.clone(), true));
+      assert_ok!(Subtensor::<T>::do_set_nomination_allowlist_enabled(RawOrigin::Signed(owner.clone()).into(), hotkey.clone(), true));
+      assert_ok!(Subtensor::<T>::do_set_nomination_limits(RawOrigin::Signed(owner.clone()).into(), hotkey.clone(), 10, 1_000_000_000_000));
+      stakes.push((hotkey, 1_000_000));
+    }
+  }: add_stake_multiple(RawOrigin::Signed( coldkey.clone() ), stakes)
+
+  benchmark_remove_stake_multiple {
+    // Every leg unstakes a nomination under the amount limiter into the unbonding queue.
+    let n in 1 .. MAX_STAKE_BATCH_SIZE;
+    let netuid: u16 = 1;
+    let tempo: u16 = 1;
+    let seed : u32 = 1;
+
+    Subtensor::<T>::set_target_stakes_per_interval(100);
+    Subtensor::<T>::set_stake_rate_limit_mode(StakeRateLimitMode::Amount);
+    Subtensor::<T>::set_max_stake_amount_per_window(u64::MAX);
+    Subtensor::<T>::set_unbonding_period(100);
+
+    Subtensor::<T>::init_new_network(netuid, tempo);
+    Subtensor::<T>::set_burn(netuid, 1);
+    Subtensor::<T>::set_network_registration_allowed( netuid, true );
+    Subtensor::<T>::set_max_allowed_uids( netuid, 4096 );
+    Subtensor::<T>::set_max_registrations_per_block( netuid, 4096 );
+    Subtensor::<T>::set_target_registrations_per_interval( netuid, 4096 );
+
+    let coldkey: T::AccountId = account("Test", 0, seed);
+    Subtensor::<T>::add_balance_to_coldkey_account(&coldkey.clone(), 1_000_000_000_000u64);
+
+    let u64_staked_amt = 1_000_000_000;
+    let mut unstakes: Vec<(T::AccountId, u64)> = vec![];
+    for i in 0..n {
+      let owner: T::AccountId = account("Owner", i, seed);
+      let hotkey: T::AccountId = account("Alice", i, seed);
+      Subtensor::<T>::add_balance_to_coldkey_account(&owner.clone(), 1_000_000u64);
+      assert_ok!(Subtensor::<T>::do_burned_registration(RawOrigin::Signed(owner.clone()).into(), netuid, hotkey.clone()));
+      assert_ok!(Subtensor::<T>::do_become_delegate(RawOrigin::Signed(owner.clone()).into(), hotkey.clone(), Subtensor::<T>::get_default_take()));
+      assert_ok!( Subtensor::<T>::add_stake(RawOrigin::Signed( coldkey.clone() ).into() , hotkey.clone(), u64_staked_amt));
+      unstakes.push((hotkey, u64_staked_amt - 1));
+    }
+  }: remove_stake_multiple(RawOrigin::Signed( coldkey.clone() ), unstakes)
+
   benchmark_serve_axon{
     let caller: T::AccountId = whitelisted_caller::<AccountIdOf<T>>();
     let caller_origin = <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(caller.clone()));
@@ -455,4 +723,88 @@ reveal_weights {
     ));
 
   }: schedule_coldkey_swap(RawOrigin::Signed(old_coldkey.clone()), new_coldkey.clone(), vec![], block_number, nonce)
+
+  benchmark_get_stake_info_for_coldkey {
+    // Positions of the queried coldkey.
+    let n in 1 .. 64;
+    // Stake entries of other coldkeys, which must not affect the cost.
+    let m in 1 .. 512;
+    let seed: u32 = 1;
+
+    let coldkey: T::AccountId = account("Test", 0, seed);
+    for i in 0 .. n {
+      let hotkey: T::AccountId = account("Hotkey", i, seed);
+      Subtensor::<T>::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey, 1_000);
+    }
+    for i in 0 .. m {
+      let other_coldkey: T::AccountId = account("Other", i, seed);
+      let hotkey: T::AccountId = account("Hotkey", i % 64, seed);
+      Subtensor::<T>::increase_stake_on_coldkey_hotkey_account(&other_coldkey, &hotkey, 1_000);
+    }
+  }: {
+    assert_eq!(Subtensor::<T>::get_stake_info_for_coldkey(coldkey.encode()).len(), n as usize);
+  }
+
+  benchmark_get_delegated {
+    // Delegates the queried coldkey nominates to.
+    let n in 1 .. 64;
+    // Delegates the queried coldkey does not nominate to.
+    let m in 1 .. 512;
+    let seed: u32 = 1;
+
+    let coldkey: T::AccountId = account("Test", 0, seed);
+    for i in 0 .. n {
+      let hotkey: T::AccountId = account("Delegate", i, seed);
+      Subtensor::<T>::delegate_hotkey(&hotkey, Subtensor::<T>::get_default_take());
+      Subtensor::<T>::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey, 1_000);
+    }
+    for i in 0 .. m {
+      let other_coldkey: T::AccountId = account("Other", i, seed);
+      let hotkey: T::AccountId = account("OtherDelegate", i, seed);
+      Subtensor::<T>::delegate_hotkey(&hotkey, Subtensor::<T>::get_default_take());
+      Subtensor::<T>::increase_stake_on_coldkey_hotkey_account(&other_coldkey, &hotkey, 1_000);
+    }
+  }: {
+    assert_eq!(Subtensor::<T>::get_delegated(coldkey.encode()).len(), n as usize);
+  }
+
+  benchmark_block_step {
+    // Subnets with no epoch due or staged, the fixed part of the block step.
+    let seed: u32 = 1;
+    for netuid in 1 .. 33_u16 {
+      Subtensor::<T>::init_new_network(netuid, u16::MAX);
+      Subtensor::<T>::set_network_registration_allowed(netuid, true);
+      Subtensor::<T>::append_neuron(netuid, &account("Hotkey", netuid as u32, seed), 0);
+    }
+  }: {
+    assert_ok!(Subtensor::<T>::block_step());
+  }
+
+  benchmark_staged_epoch {
+    // Validators weighting all 4096 uids, each adds 4096 weights and bonds entries.
+    let k in 1 .. 64;
+    let netuid: u16 = 1;
+    let seed: u32 = 1;
+    let n: u16 = 4096;
+
+    Subtensor::<T>::init_new_network(netuid, 360);
+    Subtensor::<T>::set_max_allowed_uids(netuid, n);
+    Subtensor::<T>::set_max_allowed_validators(netuid, 64);
+    for uid in 0 .. n {
+      let hotkey: T::AccountId = account("Hotkey", uid as u32, seed);
+      Subtensor::<T>::append_neuron(netuid, &hotkey, 0);
+    }
+    let row: Vec<(u16, u16)> = (0 .. n).map(|uid| (uid, u16::MAX)).collect();
+    for uid in 0 .. k as u16 {
+      let hotkey: T::AccountId = account("Hotkey", uid as u32, seed);
+      Subtensor::<T>::increase_stake_on_coldkey_hotkey_account(&hotkey, &hotkey, 1_000);
+      Subtensor::<T>::set_validator_permit_for_uid(netuid, uid, true);
+      Weights::<T>::insert(netuid, uid, row.clone());
+      Bonds::<T>::insert(netuid, uid, row.clone());
+    }
+    let epoch_block: u64 = Subtensor::<T>::get_current_block_as_u64();
+  }: {
+    Subtensor::<T>::advance_staged_epoch(netuid, epoch_block, Weight::MAX);
+    assert_eq!(StagedEpoch::<T>::get(netuid).unwrap().phase, StagedEpochPhase::Done);
+  }
 }
@@ -1,14 +1,16 @@
 use super::*;
 use frame_support::storage::IterableStorageDoubleMap;
 use frame_support::storage::IterableStorageMap;
+use frame_support::weights::Weight;
 use sp_runtime::Saturating;
 use substrate_fixed::types::I110F18;
 use substrate_fixed::types::I64F64;
 use substrate_fixed::types::I96F32;
 
 impl<T: Config> Pallet<T> {
-    /// Executes the necessary operations for each block.
-    pub fn block_step() -> Result<(), &'static str> {
+    /// Executes the necessary operations for each block. Returns the weight used by the epochs
+    /// staged or completed in the block.
+    pub fn block_step() -> Result<Weight, &'static str> {
         let block_number: u64 = Self::get_current_block_as_u64();
         log::debug!("block_step for block: {:?} ", block_number);
         // --- 1. Adjust difficulties.
@@ -23,9 +25,9 @@ impl<T: Config> Pallet<T> {
         // --- 3. Drains emission tuples ( hotkey, amount ).
         Self::drain_emission(block_number);
         // --- 4. Generates emission tuples from epoch functions.
-        Self::generate_emission(block_number);
+        let epoch_weight: Weight = Self::generate_emission(block_number);
         // Return ok.
-        Ok(())
+        Ok(epoch_weight)
     }
 
     #[allow(clippy::arithmetic_side_effects)]
@@ -112,9 +114,16 @@ impl<T: Config> Pallet<T> {
 
     /// Iterates through networks queues more emission onto their pending storage.
     /// If a network has no blocks left until tempo, we run the epoch function and generate
-    /// more token emission tuples for later draining onto accounts.
+    /// more token emission tuples for later draining onto accounts. Epochs due within their
+    /// lead are staged ahead of tempo, nearest first, within the staged epoch block budget. An
+    /// epoch not done at tempo carries over to the next blocks within the same budget and loads
+    /// its emission once done. Returns the weight used by the epochs.
     ///
-    pub fn generate_emission(block_number: u64) {
+    pub fn generate_emission(block_number: u64) -> Weight {
+        let mut weight: Weight = Weight::zero();
+        let mut staging: Vec<(u64, u16)> = Vec::new();
+        let mut completing: Vec<(u64, u16)> = Vec::new();
+
         // --- 1. Iterate across each network and add pending emission into stash.
         for (netuid, tempo) in <Tempo<T> as IterableStorageMap<u16, u16>>::iter() {
             // Skip the root network or subnets with registrations turned off
@@ -163,57 +172,83 @@ impl<T: Config> Pallet<T> {
                 new_queued_emission
             );
 
-            // --- 6. Check to see if this network has reached tempo.
-            if Self::blocks_until_next_epoch(netuid, tempo, block_number) != 0 {
-                // --- 3.1 No epoch, increase blocks since last step and continue,
-                Self::set_blocks_since_last_step(
-                    netuid,
-                    Self::get_blocks_since_last_step(netuid).saturating_add(1),
-                );
-                continue;
+            // --- 6. Check to see if this network has reached tempo. An epoch staged for an
+            // earlier block is overdue and completes before another one is staged, the tempos
+            // it runs over are skipped and their emission left for the next epoch.
+            let blocks_until_epoch: u64 =
+                Self::blocks_until_next_epoch(netuid, tempo, block_number);
+            let overdue: Option<u64> = StagedEpoch::<T>::get(netuid)
+                .map(|cursor| cursor.epoch_block)
+                .filter(|epoch_block| *epoch_block < block_number);
+            weight.saturating_accrue(T::DbWeight::get().reads(1));
+            if let Some(epoch_block) = overdue {
+                completing.push((epoch_block, netuid));
+            } else if blocks_until_epoch == 0 {
+                completing.push((block_number, netuid));
+            } else if blocks_until_epoch <= Self::get_staged_epoch_lead_blocks(tempo) {
+                // --- 6.1 Stage the upcoming epoch once it is within its lead.
+                staging.push((blocks_until_epoch, netuid));
             }
 
-            // --- 7. Run the epoch mechanism and return emission tuples for hotkeys in the network.
-            let emission_tuples_this_block: Vec<(T::AccountId, u64, u64)> =
-                match Self::epoch(netuid, None) {
-                    EpochReturnType::Emission(emission_tuples) => emission_tuples,
-                    _ => vec![],
-                };
-
-            // --- 8 This network is at tempo and we are running its epoch.
-            // First drain the queued emission.
-            let emission_to_drain: u64 = PendingEmission::<T>::get(netuid);
-            PendingEmission::<T>::insert(netuid, 0);
-
-            log::debug!(
-                "netuid_i: {:?} emission_to_drain: {:?} ",
+            // --- 7. Increase blocks since last step, reset once the epoch completes.
+            Self::set_blocks_since_last_step(
                 netuid,
-                emission_to_drain
+                Self::get_blocks_since_last_step(netuid).saturating_add(1),
             );
+        }
 
-            // --- 9. Check that the emission does not exceed the allowed total.
-            let emission_sum: u128 = emission_tuples_this_block
-                .iter()
-                .map(|(_account_id, ve, se)| (*ve as u128).saturating_add(*se as u128))
-                .sum();
-            if emission_sum > emission_to_drain as u128 {
-                continue;
-            } // Saftey check.
-
-            // --- 10. Sink the emission tuples onto the already loaded.
-            let mut concat_emission_tuples: Vec<(T::AccountId, u64, u64)> =
-                emission_tuples_this_block.clone();
-            if let Some(mut current_emission_tuples) = Self::get_loaded_emission_tuples(netuid) {
-                // 10.a We already have loaded emission tuples, so we concat the new ones.
-                concat_emission_tuples.append(&mut current_emission_tuples);
+        // --- 8. Advance the epochs at or past tempo, longest due first, and load their emission
+        // tuples once done. Those left unfinished carry over to the next blocks.
+        completing.sort();
+        let budget: Weight = Self::get_staged_epoch_block_budget();
+        let mut staged_weight: Weight = Weight::zero();
+        for (epoch_block, netuid) in completing {
+            if staged_weight.ref_time() < budget.ref_time() {
+                staged_weight.saturating_accrue(Self::advance_staged_epoch(
+                    netuid,
+                    epoch_block,
+                    budget.saturating_sub(staged_weight),
+                ));
             }
-            LoadedEmission::<T>::insert(netuid, concat_emission_tuples);
+            weight.saturating_accrue(Self::complete_staged_epoch(netuid, block_number));
+        }
 
-            // --- 11 Set counters.
-            Self::set_blocks_since_last_step(netuid, 0);
-            Self::set_last_mechanism_step_block(netuid, block_number);
+        // --- 9. Advance the staged epochs, nearest to tempo first, while the budget lasts.
+        staging.sort();
+        for (blocks_until_epoch, netuid) in staging {
+            if staged_weight.ref_time() >= budget.ref_time() {
+                break;
+            }
+            staged_weight.saturating_accrue(Self::advance_staged_epoch(
+                netuid,
+                block_number.saturating_add(blocks_until_epoch),
+                budget.saturating_sub(staged_weight),
+            ));
         }
+        weight.saturating_add(staged_weight)
     }
+
+    /// Returns the weight the staged epochs of all subnets may use per block,
+    /// `STAGED_EPOCH_BLOCK_WEIGHT_PERCENT` of the maximum block weight.
+    ///
+    pub fn get_staged_epoch_block_budget() -> Weight {
+        let max_block: Weight = <T as frame_system::Config>::BlockWeights::get().max_block;
+        Weight::from_parts(
+            max_block
+                .ref_time()
+                .saturating_div(100)
+                .saturating_mul(STAGED_EPOCH_BLOCK_WEIGHT_PERCENT),
+            0,
+        )
+    }
+
+    /// Returns the number of blocks before tempo in which the epoch of a subnet with `tempo` is
+    /// staged, at most half its tempo.
+    ///
+    pub fn get_staged_epoch_lead_blocks(tempo: u16) -> u64 {
+        STAGED_EPOCH_LEAD_BLOCKS.min((tempo as u64).saturating_div(2))
+    }
+
     /// Distributes token inflation through the hotkey based on emission. The call ensures that the inflation
     /// is distributed onto the accounts in proportion of the stake delegated minus the take. This function
     /// is called after an epoch to distribute the newly minted stake according to delegation.
@@ -226,9 +261,12 @@ impl<T: Config> Pallet<T> {
         // --- 1. Check if the hotkey is a delegate. If not, we simply pass the stake through to the
         // coldkey - hotkey account as normal.
         if !Self::hotkey_is_delegate(hotkey) {
-            Self::increase_stake_on_hotkey_account(
+            let emission: u64 = server_emission.saturating_add(validator_emission);
+            Self::increase_stake_on_hotkey_account(hotkey, emission);
+            Self::record_nominator_reward(
                 hotkey,
-                server_emission.saturating_add(validator_emission),
+                &Self::get_owning_coldkey_for_hotkey(hotkey),
+                emission,
             );
             return;
         }
@@ -249,16 +287,14 @@ impl<T: Config> Pallet<T> {
             )
         {
             // --- 4. The emission proportion is remaining_emission * ( stake / total_stake ).
+            // It is compounded or paid out according to the reward destination of the nomination.
             let stake_proportion: u64 = Self::calculate_stake_proportional_emission(
                 stake_i,
                 total_hotkey_stake,
                 validator_emission_minus_take,
             );
-            Self::increase_stake_on_coldkey_hotkey_account(
-                &owning_coldkey_i,
-                hotkey,
-                stake_proportion,
-            );
+            Self::pay_nominator_reward(&owning_coldkey_i, hotkey, stake_proportion);
+            Self::record_nominator_reward(hotkey, &owning_coldkey_i, stake_proportion);
             log::debug!(
                 "owning_coldkey_i: {:?} hotkey: {:?} emission: +{:?} ",
                 owning_coldkey_i,
@@ -279,6 +315,93 @@ impl<T: Config> Pallet<T> {
         // The server emission is distributed in-full to the delegate owner.
         // We do this after 4. for the same reason as above.
         Self::increase_stake_on_hotkey_account(hotkey, server_emission);
+        Self::record_nominator_reward(
+            hotkey,
+            &Self::get_owning_coldkey_for_hotkey(hotkey),
+            delegate_take
+                .saturating_add(remaining_validator_emission)
+                .saturating_add(server_emission),
+        );
+    }
+
+    /// Adds the emission earned by the coldkey on the hotkey to its rewards ledger.
+    ///
+    /// Rewards recorded on the same block are counted towards the same epoch.
+    pub fn record_nominator_reward(hotkey: &T::AccountId, coldkey: &T::AccountId, amount: u64) {
+        if amount == 0 {
+            return;
+        }
+        let current_block: u64 = Self::get_current_block_as_u64();
+        let is_new_ledger: bool = NominatorRewards::<T>::mutate(
+            hotkey,
+            coldkey,
+            |(total_rewards, last_epoch_rewards, last_reward_block)| {
+                let is_new_ledger: bool = *total_rewards == 0;
+                *total_rewards = total_rewards.saturating_add(amount);
+                *last_epoch_rewards = if *last_reward_block == current_block {
+                    last_epoch_rewards.saturating_add(amount)
+                } else {
+                    amount
+                };
+                *last_reward_block = current_block;
+                is_new_ledger
+            },
+        );
+        if is_new_ledger {
+            Self::add_nominator_reward_hotkey(coldkey, hotkey);
+        }
+    }
+
+    /// Moves the rewards ledger of ( from_hotkey, from_coldkey ) onto ( to_hotkey, to_coldkey ).
+    ///
+    /// An existing ledger on the destination is merged: totals are summed and the last epoch
+    /// rewards are taken from the most recent ledger, or summed if both were rewarded on the
+    /// same block.
+    pub fn merge_nominator_rewards(
+        from_hotkey: &T::AccountId,
+        from_coldkey: &T::AccountId,
+        to_hotkey: &T::AccountId,
+        to_coldkey: &T::AccountId,
+        weight: &mut Weight,
+    ) {
+        weight.saturating_accrue(T::DbWeight::get().reads(1));
+        if from_hotkey == to_hotkey && from_coldkey == to_coldkey {
+            return;
+        }
+        let Ok((total_rewards, last_epoch_rewards, last_reward_block)) =
+            NominatorRewards::<T>::try_get(from_hotkey, from_coldkey)
+        else {
+            return;
+        };
+        NominatorRewards::<T>::remove(from_hotkey, from_coldkey);
+        NominatorRewardHotkeys::<T>::mutate(from_coldkey, |hotkeys| {
+            hotkeys.retain(|h| h != from_hotkey)
+        });
+        NominatorRewards::<T>::mutate(
+            to_hotkey,
+            to_coldkey,
+            |(to_total_rewards, to_last_epoch_rewards, to_last_reward_block)| {
+                *to_total_rewards = to_total_rewards.saturating_add(total_rewards);
+                if last_reward_block == *to_last_reward_block {
+                    *to_last_epoch_rewards =
+                        to_last_epoch_rewards.saturating_add(last_epoch_rewards);
+                } else if last_reward_block > *to_last_reward_block {
+                    *to_last_epoch_rewards = last_epoch_rewards;
+                    *to_last_reward_block = last_reward_block;
+                }
+            },
+        );
+        Self::add_nominator_reward_hotkey(to_coldkey, to_hotkey);
+        weight.saturating_accrue(T::DbWeight::get().reads_writes(3, 4));
+    }
+
+    /// Adds the hotkey to the hotkeys the coldkey has a rewards ledger on.
+    fn add_nominator_reward_hotkey(coldkey: &T::AccountId, hotkey: &T::AccountId) {
+        NominatorRewardHotkeys::<T>::mutate(coldkey, |hotkeys| {
+            if !hotkeys.contains(hotkey) {
+                hotkeys.push(hotkey.clone());
+            }
+        });
     }
 
     /// Increases the stake on the cold - hot pairing by increment while also incrementing other counters.
@@ -294,11 +417,10 @@ impl<T: Config> Pallet<T> {
             hotkey,
             TotalHotkeyStake::<T>::get(hotkey).saturating_add(increment),
         );
-        Stake::<T>::insert(
-            hotkey,
-            coldkey,
-            Stake::<T>::get(hotkey, coldkey).saturating_add(increment),
-        );
+        let current_stake: u64 = Stake::<T>::get(hotkey, coldkey);
+        let new_stake: u64 = current_stake.saturating_add(increment);
+        Stake::<T>::insert(hotkey, coldkey, new_stake);
+        Self::update_delegate_nominator_count(hotkey, current_stake, new_stake);
         TotalStake::<T>::put(TotalStake::<T>::get().saturating_add(increment));
     }
 
@@ -314,11 +436,10 @@ impl<T: Config> Pallet<T> {
             hotkey,
             TotalHotkeyStake::<T>::get(hotkey).saturating_sub(decrement),
         );
-        Stake::<T>::insert(
-            hotkey,
-            coldkey,
-            Stake::<T>::get(hotkey, coldkey).saturating_sub(decrement),
-        );
+        let current_stake: u64 = Stake::<T>::get(hotkey, coldkey);
+        let new_stake: u64 = current_stake.saturating_sub(decrement);
+        Stake::<T>::insert(hotkey, coldkey, new_stake);
+        Self::update_delegate_nominator_count(hotkey, current_stake, new_stake);
         TotalStake::<T>::put(TotalStake::<T>::get().saturating_sub(decrement));
     }
 
@@ -7,7 +7,7 @@ extern crate alloc;
 use codec::Compact;
 use sp_core::hexdisplay::AsBytesRef;
 
-#[freeze_struct("5752e4c650a83e0d")]
+#[freeze_struct("ce93fa79598ecda3")]
 #[derive(Decode, Encode, PartialEq, Eq, Clone, Debug)]
 pub struct DelegateInfo<T: Config> {
     delegate_ss58: T::AccountId,
@@ -18,6 +18,11 @@ pub struct DelegateInfo<T: Config> {
     validator_permits: Vec<Compact<u16>>, // Vec of netuid this delegate has validator permit on
     return_per_1000: Compact<u64>, // Delegators current daily return per 1000 TAO staked minus take fee
     total_daily_return: Compact<u64>, // Delegators current daily return
+    pending_take: Option<(Compact<u16>, Compact<u64>)>, // Announced take increase and the block it applies at
+    nomination_allowlist_enabled: bool, // Only coldkeys on the allowlist may nominate
+    nomination_allowlist: Vec<T::AccountId>, // Coldkeys allowed to nominate
+    max_nominators: Compact<u32>,       // Maximum number of nominators, zero is uncapped
+    max_nominated_stake: Compact<u64>,  // Maximum total stake of the nominators, zero is uncapped
 }
 
 impl<T: Config> Pallet<T> {
@@ -59,6 +64,17 @@ impl<T: Config> Pallet<T> {
 
         let owner = Self::get_owning_coldkey_for_hotkey(&delegate.clone());
         let take: Compact<u16> = <Delegates<T>>::get(delegate.clone()).into();
+        let pending_take: Option<(Compact<u16>, Compact<u64>)> =
+            PendingTakeIncreases::<T>::get(&delegate)
+                .map(|(take, effective_block)| (take.into(), effective_block.into()));
+
+        let nomination_allowlist: Vec<T::AccountId> =
+            NominationAllowlist::<T>::iter_prefix(&delegate)
+                .filter(|(_, allowed)| *allowed)
+                .map(|(nominator, _)| nominator)
+                .take(MAX_NOMINATION_ALLOWLIST_LEN as usize)
+                .collect();
+        let (max_nominators, max_nominated_stake) = NominationLimits::<T>::get(&delegate);
 
         let total_stake: U64F64 = Self::get_total_stake_for_hotkey(&delegate.clone()).into();
 
@@ -79,6 +95,11 @@ impl<T: Config> Pallet<T> {
             validator_permits,
             return_per_1000: U64F64::to_num::<u64>(return_per_1000).into(),
             total_daily_return: U64F64::to_num::<u64>(emissions_per_day).into(),
+            pending_take,
+            nomination_allowlist_enabled: NominationAllowlistEnabled::<T>::get(&delegate),
+            nomination_allowlist,
+            max_nominators: max_nominators.into(),
+            max_nominated_stake: max_nominated_stake.into(),
         }
     }
 
@@ -118,7 +139,10 @@ impl<T: Config> Pallet<T> {
         };
 
         let mut delegates: Vec<(DelegateInfo<T>, Compact<u64>)> = Vec::new();
-        for delegate in <Delegates<T> as IterableStorageMap<T::AccountId, u16>>::iter_keys() {
+        for delegate in Self::get_indexed_hotkeys_for_coldkey(&delegatee) {
+            if !Self::hotkey_is_delegate(&delegate) {
+                continue; // Not a delegate
+            }
             let staked_to_this_delegatee =
                 Self::get_stake_for_coldkey_and_hotkey(&delegatee.clone(), &delegate.clone());
             if staked_to_this_delegatee == 0 {
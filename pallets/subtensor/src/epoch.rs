@@ -1,4 +1,6 @@
 use super::*;
+use crate::epoch_info::EpochTrace;
+use crate::epoch_mechanism::{EpochInputs, EpochOutputs};
 use crate::math::*;
 use frame_support::IterableStorageDoubleMap;
 use sp_std::vec;
@@ -7,8 +9,20 @@ use substrate_fixed::types::{I32F32, I64F64, I96F32};
 impl<T: Config> Pallet<T> {
     /// Calculates reward consensus and returns the emissions for uids/hotkeys in a given `netuid`.
     /// (Dense version used only for testing purposes.)
+    ///
+    /// The dense terms are those of Yuma consensus, subnets selecting another consensus
+    /// mechanism have their epoch computed by that mechanism.
     #[allow(clippy::indexing_slicing)]
     pub fn epoch_dense(netuid: u16, rao_emission: u64) -> Vec<(T::AccountId, u64, u64)> {
+        let mechanism: ConsensusMechanism = Self::get_consensus_mechanism(netuid);
+        if mechanism != ConsensusMechanism::Yuma {
+            let mut inputs: EpochInputs<T::AccountId> = Self::get_epoch_inputs(netuid);
+            inputs.rao_emission = rao_emission;
+            let outputs: EpochOutputs = mechanism.compute::<T>(&inputs, None);
+            Self::write_epoch_outputs(mechanism, &inputs, &outputs);
+            return Self::get_epoch_emission_tuples(&inputs, &outputs);
+        }
+
         // Get subnetwork size.
         let n: u16 = Self::get_subnetwork_n(netuid);
         log::trace!("n:\n{:?}\n", n);
@@ -347,8 +361,34 @@ impl<T: Config> Pallet<T> {
     ///  * 'debug' ( bool ):
     ///     - Print debugging outputs.
     ///
-    #[allow(clippy::indexing_slicing)]
     pub fn epoch(netuid: u16, incentive_opt: Option<bool>) -> EpochReturnType<T> {
+        Self::epoch_with_trace(netuid, incentive_opt, None)
+    }
+
+    /// Runs the epoch as `epoch` does, additionally recording its intermediate terms into
+    /// `trace` when one is given. The terms are computed by the subnet's consensus mechanism.
+    pub fn epoch_with_trace(
+        netuid: u16,
+        incentive_opt: Option<bool>,
+        trace: Option<&mut EpochTrace>,
+    ) -> EpochReturnType<T> {
+        let inputs: EpochInputs<T::AccountId> = Self::get_epoch_inputs(netuid);
+        let mechanism: ConsensusMechanism = Self::get_consensus_mechanism(netuid);
+        let outputs: EpochOutputs = mechanism.compute::<T>(&inputs, trace);
+        Self::write_epoch_outputs(mechanism, &inputs, &outputs);
+
+        // Return Incentive or Emission according to incentive_opt
+        if incentive_opt.unwrap_or(false) {
+            EpochReturnType::<T>::Incentive(outputs.incentive)
+        } else {
+            // Emission tuples ( hotkeys, server_emission, validator_emission )
+            EpochReturnType::<T>::Emission(Self::get_epoch_emission_tuples(&inputs, &outputs))
+        }
+    }
+
+    /// Reads the neuron state an epoch on `netuid` runs on.
+    #[allow(clippy::indexing_slicing)]
+    pub fn get_epoch_inputs(netuid: u16) -> EpochInputs<T::AccountId> {
         // Set rao_emission by calling the getter funtion
         let rao_emission: u64 = PendingEmission::<T>::get(netuid);
 
@@ -356,22 +396,77 @@ impl<T: Config> Pallet<T> {
         let n: u16 = Self::get_subnetwork_n(netuid);
         log::trace!("Number of Neurons in Network: {:?}", n);
 
-        // ======================
-        // == Active & updated ==
-        // ======================
-
         // Get current block.
         let current_block: u64 = Self::get_current_block_as_u64();
         log::trace!("current_block: {:?}", current_block);
 
-        // Get activity cutoff.
-        let activity_cutoff: u64 = Self::get_activity_cutoff(netuid) as u64;
-        log::trace!("activity_cutoff: {:?}", activity_cutoff);
-
         // Last update vector.
         let last_update: Vec<u64> = Self::get_last_update(netuid);
         log::trace!("Last update: {:?}", &last_update);
 
+        // Block at registration vector (block when each neuron was most recently registered).
+        let block_at_registration: Vec<u64> = Self::get_block_at_registration(netuid);
+        log::trace!("Block at registration: {:?}", &block_at_registration);
+
+        let hotkeys: Vec<(u16, T::AccountId)> =
+            <Keys<T> as IterableStorageDoubleMap<u16, u16, T::AccountId>>::iter_prefix(netuid)
+                .collect();
+        log::trace!("hotkeys: {:?}", &hotkeys);
+
+        // Access network stake.
+        let mut stake: Vec<u64> = vec![0; n as usize];
+        for (uid_i, hotkey) in &hotkeys {
+            stake[*uid_i as usize] = Self::get_total_stake_for_hotkey(hotkey);
+        }
+        log::trace!("Stake : {:?}", &stake);
+
+        // Get current validator permits.
+        let validator_permits: Vec<bool> = Self::get_validator_permit(netuid);
+        log::trace!("validator_permits: {:?}", validator_permits);
+
+        // Access network weights and bonds row unnormalized.
+        let weights: Vec<Vec<(u16, I32F32)>> = Self::get_weights_sparse(netuid);
+        log::trace!("Weights: {:?}", &weights);
+        let bonds: Vec<Vec<(u16, I32F32)>> = Self::get_bonds_sparse(netuid);
+        log::trace!("B: {:?}", &bonds);
+
+        EpochInputs {
+            netuid,
+            n,
+            current_block,
+            rao_emission,
+            last_update,
+            block_at_registration,
+            hotkeys,
+            stake,
+            validator_permits,
+            weights,
+            bonds,
+        }
+    }
+
+    /// Calculates Yuma consensus terms from the epoch inputs: weights are clipped at the
+    /// stake-weighted median of each column and validators earn dividends through bonds.
+    #[allow(clippy::indexing_slicing)]
+    pub fn yuma_consensus(
+        inputs: &EpochInputs<T::AccountId>,
+        mut trace: Option<&mut EpochTrace>,
+    ) -> EpochOutputs {
+        let netuid: u16 = inputs.netuid;
+        let n: u16 = inputs.n;
+        let current_block: u64 = inputs.current_block;
+        let last_update: &[u64] = &inputs.last_update;
+        let block_at_registration: &[u64] = &inputs.block_at_registration;
+        let validator_permits: &[bool] = &inputs.validator_permits;
+
+        // ======================
+        // == Active & updated ==
+        // ======================
+
+        // Get activity cutoff.
+        let activity_cutoff: u64 = Self::get_activity_cutoff(netuid) as u64;
+        log::trace!("activity_cutoff: {:?}", activity_cutoff);
+
         // Inactive mask.
         let inactive: Vec<bool> = last_update
             .iter()
@@ -382,25 +477,16 @@ impl<T: Config> Pallet<T> {
         // Logical negation of inactive.
         let active: Vec<bool> = inactive.iter().map(|&b| !b).collect();
 
-        // Block at registration vector (block when each neuron was most recently registered).
-        let block_at_registration: Vec<u64> = Self::get_block_at_registration(netuid);
-        log::trace!("Block at registration: {:?}", &block_at_registration);
-
         // ===========
         // == Stake ==
         // ===========
 
-        let hotkeys: Vec<(u16, T::AccountId)> =
-            <Keys<T> as IterableStorageDoubleMap<u16, u16, T::AccountId>>::iter_prefix(netuid)
-                .collect();
-        log::trace!("hotkeys: {:?}", &hotkeys);
-
         // Access network stake as normalized vector.
-        let mut stake_64: Vec<I64F64> = vec![I64F64::from_num(0.0); n as usize];
-        for (uid_i, hotkey) in &hotkeys {
-            stake_64[*uid_i as usize] = I64F64::from_num(Self::get_total_stake_for_hotkey(hotkey));
-        }
-        log::trace!("Stake : {:?}", &stake_64);
+        let mut stake_64: Vec<I64F64> = inputs
+            .stake
+            .iter()
+            .map(|stake| I64F64::from_num(*stake))
+            .collect();
         inplace_normalize_64(&mut stake_64);
         let stake: Vec<I32F32> = vec_fixed64_to_fixed32(stake_64);
         // range: I32F32(0, 1)
@@ -410,10 +496,6 @@ impl<T: Config> Pallet<T> {
         // == Validator permits ==
         // =======================
 
-        // Get current validator permits.
-        let validator_permits: Vec<bool> = Self::get_validator_permit(netuid);
-        log::trace!("validator_permits: {:?}", validator_permits);
-
         // Logical negation of validator_permits.
         let validator_forbids: Vec<bool> = validator_permits.iter().map(|&b| !b).collect();
 
@@ -445,14 +527,15 @@ impl<T: Config> Pallet<T> {
         // == Weights ==
         // =============
 
-        // Access network weights row unnormalized.
-        let mut weights: Vec<Vec<(u16, I32F32)>> = Self::get_weights_sparse(netuid);
-        log::trace!("Weights: {:?}", &weights);
-
         // Mask weights that are not from permitted validators.
-        weights = mask_rows_sparse(&validator_forbids, &weights);
+        let mut weights: Vec<Vec<(u16, I32F32)>> =
+            mask_rows_sparse(&validator_forbids, &inputs.weights);
         log::trace!("Weights (permit): {:?}", &weights);
 
+        // Find validators with the exact same weights as another validator.
+        let identical_weights: Vec<bool> = duplicate_rows_sparse(&weights);
+        log::trace!("Identical weights: {:?}", &identical_weights);
+
         // Remove self-weight by masking diagonal.
         weights = mask_diag_sparse(&weights);
         log::trace!("Weights (permit+diag): {:?}", &weights);
@@ -460,8 +543,8 @@ impl<T: Config> Pallet<T> {
         // Remove weights referring to deregistered neurons.
         weights = vec_mask_sparse_matrix(
             &weights,
-            &last_update,
-            &block_at_registration,
+            last_update,
+            block_at_registration,
             &|updated, registered| updated <= registered,
         );
         log::trace!("Weights (permit+diag+outdate): {:?}", &weights);
@@ -483,12 +566,32 @@ impl<T: Config> Pallet<T> {
         let consensus: Vec<I32F32> = weighted_median_col_sparse(&active_stake, &weights, n, kappa);
         log::trace!("Consensus: {:?}", &consensus);
 
+        // Agreement of validators with consensus, before clipping.
+        let consensus_similarity: Vec<I32F32> = row_cosine_similarity_sparse(&weights, &consensus);
+        log::trace!("Consensus similarity: {:?}", &consensus_similarity);
+        let unclipped_weight: Vec<I32F32> = row_sum_sparse(&weights);
+
+        // Kept only when tracing.
+        let unclipped_weights: Vec<Vec<(u16, I32F32)>> = if trace.is_some() {
+            weights.clone()
+        } else {
+            vec![]
+        };
         weights = col_clip_sparse(&weights, &consensus);
         log::trace!("Weights: {:?}", &weights);
 
         let validator_trust: Vec<I32F32> = row_sum_sparse(&weights);
         log::trace!("Validator Trust: {:?}", &validator_trust);
 
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.active_stake.clone_from(&active_stake);
+            trace.weights = trace.record_sparse(&unclipped_weights);
+            trace.preranks.clone_from(&preranks);
+            trace.consensus.clone_from(&consensus);
+            trace.clipped_weights = trace.record_sparse(&weights);
+            trace.validator_trust.clone_from(&validator_trust);
+        }
+
         // =============================
         // == Ranks, Trust, Incentive ==
         // =============================
@@ -505,19 +608,20 @@ impl<T: Config> Pallet<T> {
         let incentive: Vec<I32F32> = ranks.clone();
         log::trace!("Incentive (=Rank): {:?}", &incentive);
 
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.ranks.clone_from(&ranks);
+            trace.trust.clone_from(&trust);
+        }
+
         // =========================
         // == Bonds and Dividends ==
         // =========================
 
-        // Access network bonds.
-        let mut bonds: Vec<Vec<(u16, I32F32)>> = Self::get_bonds_sparse(netuid);
-        log::trace!("B: {:?}", &bonds);
-
         // Remove bonds referring to deregistered neurons.
-        bonds = vec_mask_sparse_matrix(
-            &bonds,
-            &last_update,
-            &block_at_registration,
+        let mut bonds: Vec<Vec<(u16, I32F32)>> = vec_mask_sparse_matrix(
+            &inputs.bonds,
+            last_update,
+            block_at_registration,
             &|updated, registered| updated <= registered,
         );
         log::trace!("B (outdatedmask): {:?}", &bonds);
@@ -534,6 +638,11 @@ impl<T: Config> Pallet<T> {
         inplace_col_normalize_sparse(&mut bonds_delta, n); // sum_i b_ij = 1
         log::trace!("ΔB (norm): {:?}", &bonds_delta);
 
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.bonds = trace.record_sparse(&bonds);
+            trace.bonds_delta = trace.record_sparse(&bonds_delta);
+        }
+
         // Compute the Exponential Moving Average (EMA) of bonds.
         let mut ema_bonds =
             Self::compute_ema_bonds_sparse(netuid, consensus.clone(), bonds_delta, bonds);
@@ -547,20 +656,67 @@ impl<T: Config> Pallet<T> {
         inplace_normalize(&mut dividends);
         log::trace!("Dividends: {:?}", &dividends);
 
+        if let Some(trace) = trace {
+            trace.ema_bonds = trace.record_sparse(&ema_bonds);
+            trace.dividends.clone_from(&dividends);
+        }
+
         // =================================
         // == Emission and Pruning scores ==
         // =================================
 
+        let (server_emission, validator_emission, combined_emission, pruning_scores) =
+            Self::compute_epoch_emission(
+                inputs.rao_emission,
+                &stake,
+                &active_stake,
+                &incentive,
+                &dividends,
+            );
+
+        // Column max-upscale EMA bonds for storage: max_i w_ij = 1.
+        inplace_col_max_upscale_sparse(&mut ema_bonds, n);
+
+        EpochOutputs {
+            active,
+            ranks,
+            trust,
+            consensus,
+            incentive,
+            dividends,
+            validator_trust,
+            pruning_scores,
+            server_emission,
+            validator_emission,
+            combined_emission,
+            validator_permits: new_validator_permits,
+            bonds: ema_bonds,
+            consensus_similarity,
+            unclipped_weight,
+            identical_weights,
+        }
+    }
+
+    /// Splits `rao_emission` between servers, by incentive, and validators, by dividends. When
+    /// neither earns anything the emission follows stake. Returns the server, validator and
+    /// combined emission, and the normalized combined emission used as pruning scores.
+    pub fn compute_epoch_emission(
+        rao_emission: u64,
+        stake: &[I32F32],
+        active_stake: &[I32F32],
+        incentive: &[I32F32],
+        dividends: &[I32F32],
+    ) -> (Vec<u64>, Vec<u64>, Vec<u64>, Vec<I32F32>) {
         // Compute normalized emission scores. range: I32F32(0, 1)
         let combined_emission: Vec<I32F32> = incentive
             .iter()
-            .zip(dividends.clone())
-            .map(|(ii, di)| ii.saturating_add(di))
+            .zip(dividends)
+            .map(|(ii, di)| ii.saturating_add(*di))
             .collect();
         let emission_sum: I32F32 = combined_emission.iter().sum();
 
-        let mut normalized_server_emission: Vec<I32F32> = incentive.clone(); // Servers get incentive.
-        let mut normalized_validator_emission: Vec<I32F32> = dividends.clone(); // Validators get dividends.
+        let mut normalized_server_emission: Vec<I32F32> = incentive.to_vec(); // Servers get incentive.
+        let mut normalized_validator_emission: Vec<I32F32> = dividends.to_vec(); // Validators get dividends.
         let mut normalized_combined_emission: Vec<I32F32> = combined_emission.clone();
         // Normalize on the sum of incentive + dividends.
         inplace_normalize_using_sum(&mut normalized_server_emission, emission_sum);
@@ -570,13 +726,13 @@ impl<T: Config> Pallet<T> {
         // If emission is zero, replace emission with normalized stake.
         if emission_sum == I32F32::from(0) {
             // no weights set | outdated weights | self_weights
-            if is_zero(&active_stake) {
+            if is_zero(active_stake) {
                 // no active stake
-                normalized_validator_emission.clone_from(&stake); // do not mask inactive, assumes stake is normalized
-                normalized_combined_emission.clone_from(&stake);
+                normalized_validator_emission = stake.to_vec(); // do not mask inactive, assumes stake is normalized
+                normalized_combined_emission = stake.to_vec();
             } else {
-                normalized_validator_emission.clone_from(&active_stake); // emission proportional to inactive-masked normalized stake
-                normalized_combined_emission.clone_from(&active_stake);
+                normalized_validator_emission = active_stake.to_vec(); // emission proportional to inactive-masked normalized stake
+                normalized_combined_emission = active_stake.to_vec();
             }
         }
 
@@ -628,91 +784,243 @@ impl<T: Config> Pallet<T> {
         log::trace!("Combined Emission: {:?}", &combined_emission);
 
         // Set pruning scores using combined emission scores.
-        let pruning_scores: Vec<I32F32> = normalized_combined_emission.clone();
+        let pruning_scores: Vec<I32F32> = normalized_combined_emission;
         log::trace!("Pruning Scores: {:?}", &pruning_scores);
 
-        // ===================
-        // == Value storage ==
-        // ===================
-        let cloned_emission: Vec<u64> = combined_emission.clone();
-        let cloned_ranks: Vec<u16> = ranks
+        (
+            server_emission,
+            validator_emission,
+            combined_emission,
+            pruning_scores,
+        )
+    }
+
+    /// Writes the terms computed by an epoch to storage. Uids appended since the inputs were
+    /// gathered keep their current terms, as do uids replaced since then, whose bonds are
+    /// dropped instead. Mechanisms which do not clip weights report none of it as clipped.
+    pub fn write_epoch_outputs(
+        mechanism: ConsensusMechanism,
+        inputs: &EpochInputs<T::AccountId>,
+        outputs: &EpochOutputs,
+    ) {
+        let netuid: u16 = inputs.netuid;
+        let replaced: Vec<bool> = Self::get_replaced_uids(netuid, &inputs.block_at_registration);
+        let is_replaced = |uid: usize| -> bool { replaced.get(uid).copied().unwrap_or(false) };
+        Self::write_epoch_terms(netuid, outputs, &replaced);
+
+        // Replaced uids start over without agreement metrics.
+        let agreement_permits: Vec<bool> = inputs
+            .validator_permits
+            .iter()
+            .enumerate()
+            .map(|(uid, permit)| *permit && !is_replaced(uid))
+            .collect();
+        Self::update_validator_agreement(
+            netuid,
+            &agreement_permits,
+            &outputs.consensus_similarity,
+            &outputs.unclipped_weight,
+            if mechanism.clips_weights() {
+                &outputs.validator_trust
+            } else {
+                &outputs.unclipped_weight
+            },
+            &outputs.identical_weights,
+        );
+        Self::record_epoch_summary(netuid, inputs.current_block, inputs.rao_emission);
+
+        outputs
+            .validator_permits
+            .iter()
+            .zip(&inputs.validator_permits)
+            .zip(&outputs.bonds)
+            .enumerate()
+            .filter(|(i, _)| !is_replaced(*i))
+            .for_each(|(i, ((new_permit, validator_permit), ema_bond))| {
+                // Set bonds only if uid retains validator permit, otherwise clear bonds.
+                if *new_permit {
+                    let new_bonds_row: Vec<(u16, u16)> = ema_bond
+                        .iter()
+                        .filter(|(j, _)| !is_replaced(*j as usize))
+                        .map(|(j, value)| (*j, fixed_proportion_to_u16(*value)))
+                        .collect();
+                    Bonds::<T>::insert(netuid, i as u16, new_bonds_row);
+                } else if *validator_permit {
+                    // Only overwrite the intersection.
+                    let new_empty_bonds_row: Vec<(u16, u16)> = vec![];
+                    Bonds::<T>::insert(netuid, i as u16, new_empty_bonds_row);
+                }
+            });
+    }
+
+    /// Returns whether each uid gathered with `block_at_registration` was replaced since.
+    pub fn get_replaced_uids(netuid: u16, block_at_registration: &[u64]) -> Vec<bool> {
+        Self::get_block_at_registration(netuid)
+            .iter()
+            .zip(block_at_registration)
+            .map(|(registered, gathered)| registered != gathered)
+            .collect()
+    }
+
+    /// Writes the per-uid terms of an epoch, skipping `replaced` uids.
+    pub fn write_epoch_terms(netuid: u16, outputs: &EpochOutputs, replaced: &[bool]) {
+        let cloned_ranks: Vec<u16> = outputs
+            .ranks
             .iter()
             .map(|xi| fixed_proportion_to_u16(*xi))
             .collect::<Vec<u16>>();
-        let cloned_trust: Vec<u16> = trust
+        let cloned_trust: Vec<u16> = outputs
+            .trust
             .iter()
             .map(|xi| fixed_proportion_to_u16(*xi))
             .collect::<Vec<u16>>();
-        let cloned_consensus: Vec<u16> = consensus
+        let cloned_consensus: Vec<u16> = outputs
+            .consensus
             .iter()
             .map(|xi| fixed_proportion_to_u16(*xi))
             .collect::<Vec<u16>>();
-        let cloned_incentive: Vec<u16> = incentive
+        let cloned_incentive: Vec<u16> = outputs
+            .incentive
             .iter()
             .map(|xi| fixed_proportion_to_u16(*xi))
             .collect::<Vec<u16>>();
-        let cloned_dividends: Vec<u16> = dividends
+        let cloned_dividends: Vec<u16> = outputs
+            .dividends
             .iter()
             .map(|xi| fixed_proportion_to_u16(*xi))
             .collect::<Vec<u16>>();
-        let cloned_pruning_scores: Vec<u16> = vec_max_upscale_to_u16(&pruning_scores);
-        let cloned_validator_trust: Vec<u16> = validator_trust
+        let cloned_pruning_scores: Vec<u16> = vec_max_upscale_to_u16(&outputs.pruning_scores);
+        let cloned_validator_trust: Vec<u16> = outputs
+            .validator_trust
             .iter()
             .map(|xi| fixed_proportion_to_u16(*xi))
             .collect::<Vec<u16>>();
-        Active::<T>::insert(netuid, active.clone());
-        Emission::<T>::insert(netuid, cloned_emission);
-        Rank::<T>::insert(netuid, cloned_ranks);
-        Trust::<T>::insert(netuid, cloned_trust);
-        Consensus::<T>::insert(netuid, cloned_consensus);
-        Incentive::<T>::insert(netuid, cloned_incentive);
-        Dividends::<T>::insert(netuid, cloned_dividends);
-        PruningScores::<T>::insert(netuid, cloned_pruning_scores);
-        ValidatorTrust::<T>::insert(netuid, cloned_validator_trust);
-        ValidatorPermit::<T>::insert(netuid, new_validator_permits.clone());
+        Active::<T>::mutate(netuid, |v| {
+            Self::merge_epoch_terms(v, &outputs.active, replaced)
+        });
+        Emission::<T>::mutate(netuid, |v| {
+            Self::merge_epoch_terms(v, &outputs.combined_emission, replaced)
+        });
+        Rank::<T>::mutate(netuid, |v| {
+            Self::merge_epoch_terms(v, &cloned_ranks, replaced)
+        });
+        Trust::<T>::mutate(netuid, |v| {
+            Self::merge_epoch_terms(v, &cloned_trust, replaced)
+        });
+        Consensus::<T>::mutate(netuid, |v| {
+            Self::merge_epoch_terms(v, &cloned_consensus, replaced)
+        });
+        Incentive::<T>::mutate(netuid, |v| {
+            Self::merge_epoch_terms(v, &cloned_incentive, replaced)
+        });
+        Dividends::<T>::mutate(netuid, |v| {
+            Self::merge_epoch_terms(v, &cloned_dividends, replaced)
+        });
+        PruningScores::<T>::mutate(netuid, |v| {
+            Self::merge_epoch_terms(v, &cloned_pruning_scores, replaced)
+        });
+        ValidatorTrust::<T>::mutate(netuid, |v| {
+            Self::merge_epoch_terms(v, &cloned_validator_trust, replaced)
+        });
+        ValidatorPermit::<T>::mutate(netuid, |v| {
+            Self::merge_epoch_terms(v, &outputs.validator_permits, replaced)
+        });
+    }
 
-        // Column max-upscale EMA bonds for storage: max_i w_ij = 1.
-        inplace_col_max_upscale_sparse(&mut ema_bonds, n);
-        new_validator_permits
-            .iter()
-            .zip(validator_permits)
-            .zip(ema_bonds)
+    /// Overwrites `current` with the epoch `terms`, skipping replaced uids. Entries past the end
+    /// of `terms` belong to uids appended after the epoch gathered its inputs and are kept.
+    fn merge_epoch_terms<V: Clone + Default>(current: &mut Vec<V>, terms: &[V], replaced: &[bool]) {
+        if current.len() < terms.len() {
+            current.resize(terms.len(), V::default());
+        }
+        current
+            .iter_mut()
+            .zip(terms)
             .enumerate()
-            .for_each(|(i, ((new_permit, validator_permit), ema_bond))| {
-                // Set bonds only if uid retains validator permit, otherwise clear bonds.
-                if *new_permit {
-                    let new_bonds_row: Vec<(u16, u16)> = ema_bond
-                        .iter()
-                        .map(|(j, value)| (*j, fixed_proportion_to_u16(*value)))
-                        .collect();
-                    Bonds::<T>::insert(netuid, i as u16, new_bonds_row);
-                } else if validator_permit {
-                    // Only overwrite the intersection.
-                    let new_empty_bonds_row: Vec<(u16, u16)> = vec![];
-                    Bonds::<T>::insert(netuid, i as u16, new_empty_bonds_row);
+            .for_each(|(uid, (value, term))| {
+                if !replaced.get(uid).copied().unwrap_or(false) {
+                    *value = term.clone();
                 }
             });
+    }
 
-        // Return Incentive or Emission according to incentive_opt
-        if incentive_opt.unwrap_or(false) {
-            EpochReturnType::<T>::Incentive(incentive)
-        } else {
-            // Emission tuples ( hotkeys, server_emission, validator_emission )
-            let emission = hotkeys
-                .into_iter()
-                .map(|(uid_i, hotkey)| {
-                    (
-                        hotkey,
-                        server_emission[uid_i as usize],
-                        validator_emission[uid_i as usize],
-                    )
-                })
-                .collect();
+    /// Returns the emission tuples ( hotkey, server_emission, validator_emission ) of an epoch.
+    #[allow(clippy::indexing_slicing)]
+    pub fn get_epoch_emission_tuples(
+        inputs: &EpochInputs<T::AccountId>,
+        outputs: &EpochOutputs,
+    ) -> Vec<(T::AccountId, u64, u64)> {
+        inputs
+            .hotkeys
+            .iter()
+            .map(|(uid_i, hotkey)| {
+                (
+                    hotkey.clone(),
+                    outputs.server_emission[*uid_i as usize],
+                    outputs.validator_emission[*uid_i as usize],
+                )
+            })
+            .collect()
+    }
 
-            EpochReturnType::<T>::Emission(emission)
+    /// Records the agreement of each permitted validator with consensus: the cosine similarity
+    /// of its weights to consensus, the share of its weight clipped at consensus and the number
+    /// of consecutive epochs its weights were identical to those of another validator, which
+    /// drops back to zero on the first epoch they differ. Uids without a validator permit have
+    /// their metrics removed.
+    #[allow(clippy::indexing_slicing)]
+    pub fn update_validator_agreement(
+        netuid: u16,
+        validator_permits: &[bool],
+        consensus_similarity: &[I32F32],
+        unclipped_weight: &[I32F32],
+        clipped_weight: &[I32F32],
+        identical_weights: &[bool],
+    ) {
+        for (uid, permit) in validator_permits.iter().enumerate() {
+            Self::update_uid_validator_agreement(
+                netuid,
+                uid as u16,
+                *permit,
+                consensus_similarity[uid],
+                unclipped_weight[uid],
+                clipped_weight[uid],
+                identical_weights[uid],
+            );
         }
     }
 
+    /// Records the agreement of a single uid with consensus, see `update_validator_agreement`.
+    pub fn update_uid_validator_agreement(
+        netuid: u16,
+        uid: u16,
+        permit: bool,
+        consensus_similarity: I32F32,
+        unclipped_weight: I32F32,
+        clipped_weight: I32F32,
+        identical_weights: bool,
+    ) {
+        if !permit {
+            if ValidatorAgreement::<T>::contains_key(netuid, uid) {
+                ValidatorAgreement::<T>::remove(netuid, uid);
+            }
+            return;
+        }
+        let clipped_share: I32F32 = unclipped_weight
+            .saturating_sub(clipped_weight)
+            .checked_div(unclipped_weight)
+            .unwrap_or(I32F32::from_num(0));
+        ValidatorAgreement::<T>::mutate(netuid, uid, |(similarity, share, identical)| {
+            *similarity = fixed_proportion_to_u16(consensus_similarity);
+            *share = fixed_proportion_to_u16(clipped_share);
+            *identical = if identical_weights {
+                identical.saturating_add(1)
+            } else {
+                0
+            };
+        });
+    }
+
     pub fn get_float_rho(netuid: u16) -> I32F32 {
         I32F32::from_num(Self::get_rho(netuid))
     }
@@ -1009,14 +1317,8 @@ impl<T: Config> Pallet<T> {
         bonds: &[Vec<(u16, I32F32)>],
         netuid: u16,
     ) -> Vec<Vec<(u16, I32F32)>> {
-        // Retrieve the bonds moving average for the given network ID and scale it down.
-        let bonds_moving_average: I64F64 = I64F64::from_num(Self::get_bonds_moving_average(netuid))
-            .saturating_div(I64F64::from_num(1_000_000));
-
         // Calculate the alpha value for the EMA calculation.
-        // Alpha is derived by subtracting the scaled bonds moving average from 1.
-        let alpha: I32F32 =
-            I32F32::from_num(1).saturating_sub(I32F32::from_num(bonds_moving_average));
+        let alpha: I32F32 = Self::get_bonds_moving_average_alpha(netuid);
 
         // Compute the Exponential Moving Average (EMA) of bonds using the calculated alpha value.
         let ema_bonds = mat_ema_sparse(bonds_delta, bonds, alpha);
@@ -1061,6 +1363,65 @@ impl<T: Config> Pallet<T> {
         ema_bonds
     }
 
+    /// Returns the EMA alpha of the bonds moving average: one minus the scaled bonds moving average.
+    pub fn get_bonds_moving_average_alpha(netuid: u16) -> I32F32 {
+        // Retrieve the bonds moving average for the given network ID and scale it down.
+        let bonds_moving_average: I64F64 = I64F64::from_num(Self::get_bonds_moving_average(netuid))
+            .saturating_div(I64F64::from_num(1_000_000));
+
+        // Alpha is derived by subtracting the scaled bonds moving average from 1.
+        I32F32::from_num(1).saturating_sub(I32F32::from_num(bonds_moving_average))
+    }
+
+    /// Returns the per-column liquid alpha values computed from consensus, or `None` when liquid
+    /// alpha is off or consensus does not allow it and the bonds moving average applies.
+    ///
+    /// # Args:
+    /// * `netuid` - The network ID.
+    /// * `consensus` - A vector of consensus values.
+    pub fn get_liquid_alpha(netuid: u16, consensus: &[I32F32]) -> Option<Vec<I32F32>> {
+        // Check if Liquid Alpha is enabled, consensus is not empty, and contains non-zero values.
+        // This way we avoid the quantil function panic.
+        if !LiquidAlphaOn::<T>::get(netuid)
+            || consensus.is_empty()
+            || consensus.iter().all(|&c| c == I32F32::from_num(0))
+        {
+            return None;
+        }
+
+        // Calculate the 75th percentile (high) and 25th percentile (low) of the consensus values.
+        let consensus_high = quantile(consensus, 0.75);
+        let consensus_low = quantile(consensus, 0.25);
+        // Further check if the high and low consensus values meet the required conditions.
+        let liquid: bool =
+            (consensus_high > consensus_low) || consensus_high != 0 || consensus_low < 0;
+        if !liquid {
+            return None;
+        }
+        log::trace!("Using Liquid Alpha");
+
+        // Get the high and low alpha values for the network.
+        let (alpha_low, alpha_high): (I32F32, I32F32) = Self::get_alpha_values_32(netuid);
+        log::trace!("alpha_low: {:?} alpha_high: {:?}", alpha_low, alpha_high);
+
+        // Calculate the logistic function parameters 'a' and 'b' based on alpha and consensus values.
+        let (a, b) =
+            Self::calculate_logistic_params(alpha_high, alpha_low, consensus_high, consensus_low);
+
+        // Compute the alpha values using the logistic function parameters.
+        let alpha = Self::compute_alpha_values(consensus, a, b);
+
+        // Clamp the alpha values between alpha_high and alpha_low.
+        Some(Self::clamp_alpha_values(alpha, alpha_high, alpha_low))
+    }
+
+    /// Returns the per-column EMA alpha of the bonds: the liquid alpha values when they apply,
+    /// otherwise the alpha of the bonds moving average for every column.
+    pub fn get_bonds_ema_alpha(netuid: u16, consensus: &[I32F32]) -> Vec<I32F32> {
+        Self::get_liquid_alpha(netuid, consensus)
+            .unwrap_or_else(|| vec![Self::get_bonds_moving_average_alpha(netuid); consensus.len()])
+    }
+
     /// Compute the Exponential Moving Average (EMA) of bonds based on the Liquid Alpha setting for a sparse matrix.
     ///
     /// # Args:
@@ -1077,51 +1438,9 @@ impl<T: Config> Pallet<T> {
         bonds_delta: Vec<Vec<(u16, I32F32)>>,
         bonds: Vec<Vec<(u16, I32F32)>>,
     ) -> Vec<Vec<(u16, I32F32)>> {
-        // Check if Liquid Alpha is enabled, consensus is not empty, and contains non-zero values.
-        // This way we avoid the quantil function panic.
-        if LiquidAlphaOn::<T>::get(netuid)
-            && !consensus.is_empty()
-            && consensus.iter().any(|&c| c != I32F32::from_num(0))
-        {
-            // Calculate the 75th percentile (high) and 25th percentile (low) of the consensus values.
-            let consensus_high = quantile(&consensus, 0.75);
-            let consensus_low = quantile(&consensus, 0.25);
-            // Further check if the high and low consensus values meet the required conditions.
-            if (consensus_high > consensus_low) || consensus_high != 0 || consensus_low < 0 {
-                // if (consensus_high > consensus_low) || consensus_high != 0) || consensus_low != 0 {
-                // if (consensus_high > consensus_low) || consensus_low != 0 {
-                log::trace!("Using Liquid Alpha");
-
-                // Get the high and low alpha values for the network.
-                let (alpha_low, alpha_high): (I32F32, I32F32) = Self::get_alpha_values_32(netuid);
-                log::trace!("alpha_low: {:?} alpha_high: {:?}", alpha_low, alpha_high);
-
-                // Calculate the logistic function parameters 'a' and 'b' based on alpha and consensus values.
-                let (a, b) = Self::calculate_logistic_params(
-                    alpha_high,
-                    alpha_low,
-                    consensus_high,
-                    consensus_low,
-                );
-
-                // Compute the alpha values using the logistic function parameters.
-                let alpha = Self::compute_alpha_values(&consensus, a, b);
-
-                // Clamp the alpha values between alpha_high and alpha_low.
-                let clamped_alpha = Self::clamp_alpha_values(alpha, alpha_high, alpha_low);
-
-                // Compute the Exponential Moving Average (EMA) of bonds using the clamped alpha values.
-                Self::compute_ema_bonds_with_liquid_alpha_sparse(
-                    &bonds_delta,
-                    &bonds,
-                    clamped_alpha,
-                )
-            } else {
-                log::trace!("Using Bonds Moving Average");
-
-                // Compute the EMA of bonds using a normal alpha value.
-                Self::compute_ema_bonds_normal_sparse(&bonds_delta, &bonds, netuid)
-            }
+        if let Some(clamped_alpha) = Self::get_liquid_alpha(netuid, &consensus) {
+            // Compute the Exponential Moving Average (EMA) of bonds using the clamped alpha values.
+            Self::compute_ema_bonds_with_liquid_alpha_sparse(&bonds_delta, &bonds, clamped_alpha)
         } else {
             log::trace!("Using Bonds Moving Average");
 
@@ -0,0 +1,184 @@
+use super::*;
+use frame_support::pallet_prelude::{Decode, DispatchError, Encode};
+use frame_support::storage::{with_transaction, TransactionOutcome};
+extern crate alloc;
+use codec::Compact;
+use substrate_fixed::types::I32F32;
+
+/// Hyperparameter and state overrides applied to a simulated epoch.
+/// Fields left as `None` keep the subnet's current value.
+#[freeze_struct("a6a193eb9c26e8ef")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, Default)]
+pub struct EpochOverrides {
+    pub kappa: Option<u16>,
+    pub rho: Option<u16>,
+    pub bonds_moving_average: Option<u64>,
+    pub liquid_alpha_enabled: Option<bool>,
+    pub alpha_values: Option<(u16, u16)>, // (alpha_low, alpha_high)
+    pub max_allowed_validators: Option<u16>,
+    pub weights: Option<Vec<(u16, Vec<(u16, u16)>)>>, // Vec of (uid, weights row) replacing the uid's row
+    pub stake: Option<Vec<(u16, u64)>>,               // Vec of (uid, total hotkey stake)
+}
+
+#[freeze_struct("67fa8912c80c87d7")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug)]
+pub struct EpochSimulation {
+    pub rank: Vec<Compact<u16>>,
+    pub trust: Vec<Compact<u16>>,
+    pub consensus: Vec<Compact<u16>>,
+    pub incentive: Vec<Compact<u16>>,
+    pub dividends: Vec<Compact<u16>>,
+    pub emission: Vec<Compact<u64>>,
+}
+
+/// Intermediate terms of a single epoch, in the fixed point form used by the epoch math.
+/// Each sparse matrix keeps at most `MAX_EPOCH_TRACE_MATRIX_ENTRIES` entries, row by row;
+/// `truncated` is set when any entry was dropped.
+#[freeze_struct("4ff131adb8e20006")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, Default)]
+pub struct EpochTrace {
+    pub netuid: u16,
+    pub block: u64,
+    pub truncated: bool,
+    pub active_stake: Vec<I32F32>,
+    pub weights: Vec<Vec<(u16, I32F32)>>, // Masked and row normalized, before clipping.
+    pub preranks: Vec<I32F32>,
+    pub consensus: Vec<I32F32>,
+    pub clipped_weights: Vec<Vec<(u16, I32F32)>>,
+    pub validator_trust: Vec<I32F32>,
+    pub ranks: Vec<I32F32>,
+    pub trust: Vec<I32F32>,
+    pub bonds: Vec<Vec<(u16, I32F32)>>, // Masked and column normalized, before the EMA.
+    pub bonds_delta: Vec<Vec<(u16, I32F32)>>,
+    pub ema_bonds: Vec<Vec<(u16, I32F32)>>,
+    pub dividends: Vec<I32F32>,
+}
+
+impl EpochTrace {
+    /// Copies `matrix` into the trace, keeping rows until the entry cap is reached.
+    pub fn record_sparse(&mut self, matrix: &[Vec<(u16, I32F32)>]) -> Vec<Vec<(u16, I32F32)>> {
+        let mut remaining: usize = MAX_EPOCH_TRACE_MATRIX_ENTRIES as usize;
+        matrix
+            .iter()
+            .map(|row| {
+                let kept: usize = row.len().min(remaining);
+                if kept < row.len() {
+                    self.truncated = true;
+                }
+                remaining = remaining.saturating_sub(kept);
+                row.iter().take(kept).copied().collect()
+            })
+            .collect()
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Runs the epoch for `netuid` with `overrides` applied and returns the resulting per-uid
+    /// terms. Every storage write, including the overrides themselves, is rolled back.
+    pub fn simulate_epoch(netuid: u16, overrides: EpochOverrides) -> Option<EpochSimulation> {
+        if !Self::if_subnet_exist(netuid) {
+            return None;
+        }
+
+        with_transaction(|| {
+            Self::apply_epoch_overrides(netuid, overrides);
+            Self::epoch(netuid, None);
+
+            let to_compact = |values: Vec<u16>| -> Vec<Compact<u16>> {
+                values.into_iter().map(Compact::from).collect()
+            };
+            let simulation = EpochSimulation {
+                rank: to_compact(Rank::<T>::get(netuid)),
+                trust: to_compact(Trust::<T>::get(netuid)),
+                consensus: to_compact(Consensus::<T>::get(netuid)),
+                incentive: to_compact(Incentive::<T>::get(netuid)),
+                dividends: to_compact(Dividends::<T>::get(netuid)),
+                emission: Emission::<T>::get(netuid)
+                    .into_iter()
+                    .map(Compact::from)
+                    .collect(),
+            };
+            TransactionOutcome::Rollback(Ok::<EpochSimulation, DispatchError>(simulation))
+        })
+        .ok()
+    }
+
+    pub fn get_epoch_simulation(netuid: u16, overrides_vec: Vec<u8>) -> Option<EpochSimulation> {
+        let overrides: EpochOverrides = if overrides_vec.is_empty() {
+            EpochOverrides::default()
+        } else {
+            EpochOverrides::decode(&mut overrides_vec.as_slice()).ok()?
+        };
+
+        Self::simulate_epoch(netuid, overrides)
+    }
+
+    /// Runs the epoch for `netuid` and returns the trace of its intermediate terms. Storage is
+    /// left untouched.
+    pub fn get_epoch_trace(netuid: u16) -> Option<EpochTrace> {
+        if !Self::if_subnet_exist(netuid) {
+            return None;
+        }
+
+        with_transaction(|| {
+            let mut trace = EpochTrace {
+                netuid,
+                block: Self::get_current_block_as_u64(),
+                ..Default::default()
+            };
+            Self::epoch_with_trace(netuid, None, Some(&mut trace));
+            TransactionOutcome::Rollback(Ok::<EpochTrace, DispatchError>(trace))
+        })
+        .ok()
+    }
+
+    /// Appends the summary of the epoch just written to storage to the subnet's epoch history,
+    /// overwriting the oldest summary once the history is full.
+    pub fn record_epoch_summary(netuid: u16, block: u64, emission_total: u64) {
+        let depth: u64 = Self::get_epoch_history_depth(netuid) as u64;
+        let index: u64 = EpochHistoryIndex::<T>::get(netuid);
+        let Some(slot) = index.checked_rem(depth) else {
+            return;
+        };
+
+        let summary = EpochSummary {
+            block,
+            emission_total,
+            incentive: Incentive::<T>::get(netuid),
+            dividends: Dividends::<T>::get(netuid),
+            emission: Emission::<T>::get(netuid),
+        };
+        EpochHistory::<T>::insert(netuid, slot as u16, summary);
+        EpochHistoryIndex::<T>::insert(netuid, index.saturating_add(1));
+    }
+
+    fn apply_epoch_overrides(netuid: u16, overrides: EpochOverrides) {
+        if let Some(kappa) = overrides.kappa {
+            Kappa::<T>::insert(netuid, kappa);
+        }
+        if let Some(rho) = overrides.rho {
+            Rho::<T>::insert(netuid, rho);
+        }
+        if let Some(bonds_moving_average) = overrides.bonds_moving_average {
+            BondsMovingAverage::<T>::insert(netuid, bonds_moving_average);
+        }
+        if let Some(enabled) = overrides.liquid_alpha_enabled {
+            LiquidAlphaOn::<T>::insert(netuid, enabled);
+        }
+        if let Some(alpha_values) = overrides.alpha_values {
+            AlphaValues::<T>::insert(netuid, alpha_values);
+        }
+        if let Some(max_allowed_validators) = overrides.max_allowed_validators {
+            MaxAllowedValidators::<T>::insert(netuid, max_allowed_validators);
+        }
+        // Uids outside the subnet are ignored by the epoch weight and stake readers.
+        for (uid, row) in overrides.weights.unwrap_or_default() {
+            Weights::<T>::insert(netuid, uid, row);
+        }
+        for (uid, stake) in overrides.stake.unwrap_or_default() {
+            if let Ok(hotkey) = Self::get_hotkey_for_net_and_uid(netuid, uid) {
+                TotalHotkeyStake::<T>::insert(hotkey, stake);
+            }
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use super::*;
+use crate::epoch_info::EpochTrace;
+use crate::math::*;
+use frame_support::pallet_prelude::{Decode, Encode, TypeInfo};
+use sp_std::vec;
+use substrate_fixed::types::{I32F32, I64F64};
+
+/// Neuron state read by an epoch, gathered from storage when the epoch starts.
+/// Hyperparameters are read by the consensus mechanism itself.
+#[freeze_struct("ab10f64351d3511d")]
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+pub struct EpochInputs<AccountId> {
+    pub netuid: u16,
+    pub n: u16,
+    pub current_block: u64,
+    pub rao_emission: u64,
+    pub last_update: Vec<u64>,
+    pub block_at_registration: Vec<u64>,
+    pub hotkeys: Vec<(u16, AccountId)>,
+    pub stake: Vec<u64>,                  // Total hotkey stake per uid.
+    pub validator_permits: Vec<bool>,     // Permits held during the epoch.
+    pub weights: Vec<Vec<(u16, I32F32)>>, // Unnormalized, as stored.
+    pub bonds: Vec<Vec<(u16, I32F32)>>,   // Unnormalized, as stored.
+}
+
+/// Terms computed by an epoch, written to storage once the epoch completes.
+#[freeze_struct("5f28393567353698")]
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug, Default)]
+pub struct EpochOutputs {
+    pub active: Vec<bool>,
+    pub ranks: Vec<I32F32>,
+    pub trust: Vec<I32F32>,
+    pub consensus: Vec<I32F32>,
+    pub incentive: Vec<I32F32>,
+    pub dividends: Vec<I32F32>,
+    pub validator_trust: Vec<I32F32>,
+    pub pruning_scores: Vec<I32F32>,
+    pub server_emission: Vec<u64>,
+    pub validator_emission: Vec<u64>,
+    pub combined_emission: Vec<u64>,
+    pub validator_permits: Vec<bool>, // Permits for the next epoch.
+    pub bonds: Vec<Vec<(u16, I32F32)>>, // Column max-upscaled, written for permitted validators.
+    pub consensus_similarity: Vec<I32F32>,
+    pub unclipped_weight: Vec<I32F32>,
+    pub identical_weights: Vec<bool>,
+}
+
+/// An incentive mechanism computing the terms of an epoch from its inputs.
+pub trait EpochMechanism<T: Config> {
+    /// Computes the epoch terms, recording intermediate terms into `trace` when one is given.
+    fn compute(inputs: &EpochInputs<T::AccountId>, trace: Option<&mut EpochTrace>) -> EpochOutputs;
+}
+
+/// Yuma consensus: weights are clipped at the stake-weighted median and validators earn
+/// dividends through bonds.
+pub struct YumaConsensus;
+
+impl<T: Config> EpochMechanism<T> for YumaConsensus {
+    fn compute(inputs: &EpochInputs<T::AccountId>, trace: Option<&mut EpochTrace>) -> EpochOutputs {
+        Pallet::<T>::yuma_consensus(inputs, trace)
+    }
+}
+
+/// Stake-weighted mean: incentive is the stake-weighted mean of the validators' weights,
+/// without clipping, and validators earn dividends in proportion to their stake times the
+/// incentive of the uids they weight. Bonds are not used and are cleared.
+///
+/// Consensus is the stake-weighted mean itself. Nothing is clipped at it, so trust and
+/// validator trust carry no signal and are zero.
+pub struct StakeWeightedMean;
+
+impl<T: Config> EpochMechanism<T> for StakeWeightedMean {
+    fn compute(inputs: &EpochInputs<T::AccountId>, trace: Option<&mut EpochTrace>) -> EpochOutputs {
+        Pallet::<T>::stake_weighted_mean(inputs, trace)
+    }
+}
+
+impl ConsensusMechanism {
+    /// Returns true if the mechanism clips weights at consensus, so that validator trust is
+    /// the weight left after clipping.
+    pub fn clips_weights(self) -> bool {
+        match self {
+            ConsensusMechanism::Yuma => true,
+            ConsensusMechanism::StakeWeightedMean => false,
+        }
+    }
+
+    /// Computes the epoch terms with the selected mechanism.
+    pub fn compute<T: Config>(
+        self,
+        inputs: &EpochInputs<T::AccountId>,
+        trace: Option<&mut EpochTrace>,
+    ) -> EpochOutputs {
+        match self {
+            ConsensusMechanism::Yuma => {
+                <YumaConsensus as EpochMechanism<T>>::compute(inputs, trace)
+            }
+            ConsensusMechanism::StakeWeightedMean => {
+                <StakeWeightedMean as EpochMechanism<T>>::compute(inputs, trace)
+            }
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Computes the stake-weighted mean epoch terms from `inputs`.
+    pub fn stake_weighted_mean(
+        inputs: &EpochInputs<T::AccountId>,
+        trace: Option<&mut EpochTrace>,
+    ) -> EpochOutputs {
+        let netuid: u16 = inputs.netuid;
+        let n: u16 = inputs.n;
+
+        // Active mask and normalized stake.
+        let activity_cutoff: u64 = Self::get_activity_cutoff(netuid) as u64;
+        let inactive: Vec<bool> = inputs
+            .last_update
+            .iter()
+            .map(|updated| updated.saturating_add(activity_cutoff) < inputs.current_block)
+            .collect();
+        let active: Vec<bool> = inactive.iter().map(|&b| !b).collect();
+        let mut stake_64: Vec<I64F64> = inputs
+            .stake
+            .iter()
+            .map(|stake| I64F64::from_num(*stake))
+            .collect();
+        inplace_normalize_64(&mut stake_64);
+        let stake: Vec<I32F32> = vec_fixed64_to_fixed32(stake_64);
+
+        // Validator permits and active validator stake.
+        let validator_forbids: Vec<bool> = inputs.validator_permits.iter().map(|&b| !b).collect();
+        let max_allowed_validators: u16 = Self::get_max_allowed_validators(netuid);
+        let new_validator_permits: Vec<bool> = is_topk(&stake, max_allowed_validators as usize);
+        let mut active_stake: Vec<I32F32> = stake.clone();
+        inplace_mask_vector(&inactive, &mut active_stake);
+        inplace_mask_vector(&validator_forbids, &mut active_stake);
+        inplace_normalize(&mut active_stake);
+
+        // Permitted, non-self, up to date weights, row normalized.
+        let mut weights: Vec<Vec<(u16, I32F32)>> =
+            mask_rows_sparse(&validator_forbids, &inputs.weights);
+        let identical_weights: Vec<bool> = duplicate_rows_sparse(&weights);
+        weights = mask_diag_sparse(&weights);
+        weights = vec_mask_sparse_matrix(
+            &weights,
+            &inputs.last_update,
+            &inputs.block_at_registration,
+            &|updated, registered| updated <= registered,
+        );
+        inplace_row_normalize_sparse(&mut weights);
+
+        // Stake-weighted mean of the weights: r_j = SUM(i) w_ij * s_i, without clipping.
+        let mut ranks: Vec<I32F32> = matmul_sparse(&weights, &active_stake, n);
+        let consensus: Vec<I32F32> = ranks.clone();
+        let consensus_similarity: Vec<I32F32> = row_cosine_similarity_sparse(&weights, &consensus);
+        let unclipped_weight: Vec<I32F32> = row_sum_sparse(&weights);
+        // No weight is clipped, so there is no trust to measure.
+        let trust: Vec<I32F32> = vec![I32F32::from_num(0); n as usize];
+        let validator_trust: Vec<I32F32> = vec![I32F32::from_num(0); n as usize];
+        inplace_normalize(&mut ranks);
+        let incentive: Vec<I32F32> = ranks.clone();
+
+        // Dividends: d_i = s_i * SUM(j) w_ij * inc_j.
+        let stake_weights: Vec<Vec<(u16, I32F32)>> = row_hadamard_sparse(&weights, &active_stake);
+        let mut dividends: Vec<I32F32> = matmul_transpose_sparse(&stake_weights, &incentive);
+        inplace_normalize(&mut dividends);
+
+        if let Some(trace) = trace {
+            trace.active_stake.clone_from(&active_stake);
+            trace.weights = trace.record_sparse(&weights);
+            trace.consensus.clone_from(&consensus);
+            trace.validator_trust.clone_from(&validator_trust);
+            trace.ranks.clone_from(&ranks);
+            trace.trust.clone_from(&trust);
+            trace.dividends.clone_from(&dividends);
+        }
+
+        let (server_emission, validator_emission, combined_emission, pruning_scores) =
+            Self::compute_epoch_emission(
+                inputs.rao_emission,
+                &stake,
+                &active_stake,
+                &incentive,
+                &dividends,
+            );
+
+        EpochOutputs {
+            active,
+            ranks,
+            trust,
+            consensus,
+            incentive,
+            dividends,
+            unclipped_weight,
+            validator_trust,
+            pruning_scores,
+            server_emission,
+            validator_emission,
+            combined_emission,
+            validator_permits: new_validator_permits,
+            bonds: vec![vec![]; n as usize],
+            consensus_similarity,
+            identical_weights,
+        }
+    }
+}
@@ -156,5 +156,33 @@ mod errors {
         InsufficientBalanceToPerformColdkeySwap,
         /// The maximum number of coldkey destinations has been reached
         MaxColdkeyDestinationsReached,
+        /// The origin and destination hotkeys of a stake move are the same.
+        SameHotkeyStakeMove,
+        /// The origin and destination coldkeys of a stake transfer are the same.
+        SameColdkeyStakeTransfer,
+        /// The coldkey has too many pending unbonding chunks.
+        UnbondingQueueFull,
+        /// The coldkey has no matured unbonding stake to claim.
+        NoUnbondedStakeToClaim,
+        /// The staking batch is empty or has too many legs.
+        InvalidStakeBatchSize,
+        /// The coldkey is not on the nomination allowlist of the delegate.
+        NominatorNotAllowlisted,
+        /// The delegate has reached its maximum number of nominators.
+        DelegateNominatorLimitReached,
+        /// The stake would exceed the maximum nominated stake of the delegate.
+        DelegateNominatedStakeLimitReached,
+        /// The hotkey has reached the maximum number of pending weight commits on the subnet.
+        TooManyUnrevealedCommits,
+        /// The weights version key is above the maximum version key of the subnet.
+        WeightVersionKeyTooHigh,
+        /// The weights version key is banned on the subnet.
+        WeightVersionKeyBanned,
+        /// The weights are set outside the weights window of the subnet.
+        WeightsWindowClosed,
+        /// The nomination allowlist of the delegate is full.
+        NominationAllowlistFull,
+        /// The weights batch is empty or has too many entries.
+        InvalidWeightsBatchSize,
     }
 }
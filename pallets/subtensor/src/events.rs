@@ -164,5 +164,186 @@ mod events {
             /// The account ID of the coldkey
             coldkey: T::AccountId,
         },
+        /// Stake has been moved from one hotkey to another
+        StakeMoved {
+            /// The account ID of the coldkey owning the stake
+            coldkey: T::AccountId,
+            /// The hotkey the stake was moved from
+            origin_hotkey: T::AccountId,
+            /// The hotkey the stake was moved to
+            destination_hotkey: T::AccountId,
+            /// The amount of stake moved
+            amount: u64,
+        },
+        /// Removed stake has been queued for release after the unbonding period
+        StakeUnbonding {
+            /// The account ID of the coldkey the stake is released to
+            coldkey: T::AccountId,
+            /// The hotkey the stake was removed from
+            hotkey: T::AccountId,
+            /// The amount of stake unbonding
+            amount: u64,
+            /// The block from which the stake can be released
+            unlock_block: u64,
+        },
+        /// Unbonded stake has been released to the coldkey balance
+        UnbondedStakeReleased {
+            /// The account ID of the coldkey
+            coldkey: T::AccountId,
+            /// The amount released
+            amount: u64,
+        },
+        /// the global unbonding period is set.
+        UnbondingPeriodSet(u64),
+        /// the unbonding period for a network is set.
+        NetworkUnbondingPeriodSet(u16, u64),
+        /// The reward destination of a nomination has been set
+        RewardDestinationSet {
+            /// The account ID of the coldkey owning the stake
+            coldkey: T::AccountId,
+            /// The hotkey the stake is delegated to
+            hotkey: T::AccountId,
+            /// Where the rewards of the nomination are paid to
+            destination: RewardDestination<T::AccountId>,
+        },
+        /// The emission of a nomination has been paid out instead of compounded
+        NominatorRewardPaid {
+            /// The account ID of the coldkey owning the stake
+            coldkey: T::AccountId,
+            /// The hotkey the stake is delegated to
+            hotkey: T::AccountId,
+            /// The account ID of the coldkey the reward was paid to
+            payee: T::AccountId,
+            /// The amount paid out
+            amount: u64,
+        },
+        /// Stake has been transferred from one coldkey to another
+        StakeTransferred {
+            /// The account ID of the coldkey the stake was transferred from
+            origin_coldkey: T::AccountId,
+            /// The account ID of the coldkey the stake was transferred to
+            destination_coldkey: T::AccountId,
+            /// The hotkey the stake is staked on
+            hotkey: T::AccountId,
+            /// The amount of stake transferred
+            amount: u64,
+        },
+        /// A delegate has announced a take increase
+        TakeIncreaseAnnounced {
+            /// The account ID of the coldkey owning the delegate
+            coldkey: T::AccountId,
+            /// The delegate hotkey
+            hotkey: T::AccountId,
+            /// The announced take
+            take: u16,
+            /// The block the take is applied at
+            effective_block: u64,
+        },
+        /// An announced take increase has been applied to a delegate
+        TakeIncreaseApplied {
+            /// The delegate hotkey
+            hotkey: T::AccountId,
+            /// The new take
+            take: u16,
+        },
+        /// the take increase delay is set by sudo/admin transaction
+        TakeIncreaseDelaySet(u64),
+        /// A delegate has restricted or opened nominations to its allowlist
+        NominationAllowlistEnabledSet {
+            /// The delegate hotkey
+            hotkey: T::AccountId,
+            /// Whether only allowlisted coldkeys may nominate
+            enabled: bool,
+        },
+        /// A coldkey has been added to or removed from the nomination allowlist of a delegate
+        NominationAllowlistUpdated {
+            /// The delegate hotkey
+            hotkey: T::AccountId,
+            /// The account ID of the nominator coldkey
+            nominator: T::AccountId,
+            /// Whether the coldkey is on the allowlist
+            allowed: bool,
+        },
+        /// A delegate has set its nomination caps
+        NominationLimitsSet {
+            /// The delegate hotkey
+            hotkey: T::AccountId,
+            /// The maximum number of nominators, zero is uncapped
+            max_nominators: u32,
+            /// The maximum total nominated stake, zero is uncapped
+            max_nominated_stake: u64,
+        },
+        /// the stake rate limiter is selected by sudo/admin transaction
+        StakeRateLimitModeSet(StakeRateLimitMode),
+        /// the stake amount window is set by sudo/admin transaction
+        StakeAmountWindowSet(u64),
+        /// the maximum stake amount per window is set by sudo/admin transaction
+        MaxStakeAmountPerWindowSet(u64),
+        /// a batch of weights has been processed, entries are applied independently.
+        BatchWeightsCompleted {
+            /// The hotkey setting the weights
+            hotkey: T::AccountId,
+            /// The outcome of every entry of the batch, by netuid
+            results: Vec<(u16, DispatchResult)>,
+        },
+        /// a batch of weight commits has been processed, entries are committed independently.
+        BatchWeightsCommitCompleted {
+            /// The hotkey committing the weights
+            hotkey: T::AccountId,
+            /// The outcome of every entry of the batch, by netuid
+            results: Vec<(u16, DispatchResult)>,
+        },
+        /// a batch of weight reveals has been processed, entries are revealed independently.
+        BatchWeightsRevealCompleted {
+            /// The hotkey revealing the weights
+            hotkey: T::AccountId,
+            /// The outcome of every entry of the batch, by netuid
+            results: Vec<(u16, DispatchResult)>,
+        },
+        /// a weight commit has been removed after its reveal window passed.
+        WeightsCommitExpired {
+            /// The subnet the commit was made on
+            netuid: u16,
+            /// The hotkey which made the commit
+            hotkey: T::AccountId,
+            /// The expired commit hash
+            commit_hash: H256,
+            /// The block the commit was made at
+            commit_block: u64,
+        },
+        /// a hotkey has committed a hash of its weights.
+        WeightsCommitted {
+            /// The subnet the commit is made on
+            netuid: u16,
+            /// The committing hotkey
+            hotkey: T::AccountId,
+            /// The committed hash
+            commit_hash: H256,
+            /// The block the commit is made at
+            block: u64,
+        },
+        /// a hotkey has revealed the weights of a commit.
+        WeightsRevealed {
+            /// The subnet the commit was made on
+            netuid: u16,
+            /// The revealing hotkey
+            hotkey: T::AccountId,
+            /// The revealed commit hash
+            commit_hash: H256,
+        },
+        /// the maximum weights version key is set for a subnet.
+        WeightsMaxVersionKeySet(u16, u64),
+        /// the banned weights version keys are set for a subnet.
+        BannedWeightsVersionKeysSet(u16, Vec<u64>),
+        /// the weights window is set for a subnet.
+        WeightsWindowSet(u16, u64),
+        /// the epoch history depth is set for a subnet.
+        EpochHistoryDepthSet(u16, u16),
+        /// the consensus mechanism is set for a subnet.
+        ConsensusMechanismSet(u16, ConsensusMechanism),
+        /// the staged epoch of a subnet completed, with the block its inputs were gathered at.
+        StagedEpochCompleted(u16, u64),
+        /// the maximum number of pending unbonding chunks per coldkey is set.
+        MaxUnbondingChunksSet(u32),
     }
 }
@@ -44,13 +44,17 @@ pub mod math;
 mod registration;
 mod root;
 mod serving;
+mod staged_epoch;
 mod staking;
 mod swap;
 mod uids;
+mod unbonding;
 mod utils;
 mod weights;
 
 pub mod delegate_info;
+pub mod epoch_info;
+pub mod epoch_mechanism;
 pub mod neuron_info;
 pub mod schedule_coldkey_swap_info;
 pub mod stake_info;
@@ -74,8 +78,10 @@ pub mod pallet {
     use frame_system::pallet_prelude::*;
     use sp_core::H256;
     use sp_runtime::traits::TrailingZeroInput;
+    use sp_std::collections::vec_deque::VecDeque;
     use sp_std::vec;
     use sp_std::vec::Vec;
+    use substrate_fixed::types::I32F32;
 
     use subtensor_macros::freeze_struct;
 
@@ -86,11 +92,42 @@ pub mod pallet {
 
     /// Tracks version for migrations. Should be monotonic with respect to the
     /// order of migrations. (i.e. always increasing)
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(6);
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(9);
 
     /// Minimum balance required to perform a coldkey swap
     pub const MIN_BALANCE_TO_PERFORM_COLDKEY_SWAP: u64 = 100_000_000; // 0.1 TAO in RAO
 
+    /// Maximum number of (hotkey, amount) legs in a batched staking call
+    pub const MAX_STAKE_BATCH_SIZE: u32 = 64;
+
+    /// Maximum number of subnet entries in a batched weights call
+    pub const MAX_WEIGHTS_BATCH_SIZE: u32 = 8;
+
+    /// Maximum number of coldkeys on the nomination allowlist of a delegate
+    pub const MAX_NOMINATION_ALLOWLIST_LEN: u32 = 256;
+
+    /// Maximum number of banned weights version keys per subnet
+    pub const MAX_BANNED_WEIGHTS_VERSION_KEYS: u32 = 32;
+
+    /// Maximum number of entries recorded per sparse matrix in an epoch trace
+    pub const MAX_EPOCH_TRACE_MATRIX_ENTRIES: u32 = 32_768;
+
+    /// Maximum number of epoch summaries kept per subnet
+    pub const MAX_EPOCH_HISTORY_DEPTH: u16 = 64;
+
+    /// Number of blocks before tempo in which a subnet's epoch is staged
+    pub const STAGED_EPOCH_LEAD_BLOCKS: u64 = 16;
+
+    /// Number of columns per stored segment of a staged weights row
+    pub const STAGED_EPOCH_SEGMENT_COLUMNS: u16 = 256;
+
+    /// Percent of the maximum block weight the staged epochs of all subnets may use per block
+    pub const STAGED_EPOCH_BLOCK_WEIGHT_PERCENT: u64 = 10;
+
+    /// Ref time charged per weight, bond or per-uid term processed by a staged epoch, the slope
+    /// of benchmark_staged_epoch over the 4096 entries each validator adds
+    pub const STAGED_EPOCH_ENTRY_REF_TIME: u64 = 25_000;
+
     #[pallet::pallet]
     #[pallet::without_storage_info]
     #[pallet::storage_version(STORAGE_VERSION)]
@@ -217,6 +254,9 @@ pub mod pallet {
         /// Initial delegate take transaction rate limit.
         #[pallet::constant]
         type InitialTxDelegateTakeRateLimit: Get<u64>;
+        /// Initial number of blocks between announcing and applying a delegate take increase.
+        #[pallet::constant]
+        type InitialTakeIncreaseDelay: Get<u64>;
         /// Initial percentage of total stake required to join senate.
         #[pallet::constant]
         type InitialSenateRequiredStakePercentage: Get<u64>;
@@ -247,6 +287,12 @@ pub mod pallet {
         /// Initial target stakes per interval issuance.
         #[pallet::constant]
         type InitialTargetStakesPerInterval: Get<u64>;
+        /// Initial stake rate limiter, counting operations or summing the moved amount.
+        #[pallet::constant]
+        type InitialStakeRateLimitMode: Get<StakeRateLimitMode>;
+        /// Initial maximum amount of stake moved per (coldkey, hotkey) within the stake amount window.
+        #[pallet::constant]
+        type InitialMaxStakeAmountPerWindow: Get<u64>;
         /// Cost of swapping a hotkey.
         #[pallet::constant]
         type HotkeySwapCost: Get<u64>;
@@ -262,6 +308,9 @@ pub mod pallet {
         /// The base difficulty for proof of work for coldkey swaps
         #[pallet::constant]
         type InitialBaseDifficulty: Get<u64>;
+        /// The maximum number of pending weight commits per hotkey on a subnet.
+        #[pallet::constant]
+        type MaxWeightCommits: Get<u32>;
     }
 
     /// Alias for the account ID.
@@ -337,6 +386,21 @@ pub mod pallet {
     pub fn DefaultStakeInterval<T: Config>() -> u64 {
         360
     }
+    /// Default stake rate limiter.
+    #[pallet::type_value]
+    pub fn DefaultStakeRateLimitMode<T: Config>() -> StakeRateLimitMode {
+        T::InitialStakeRateLimitMode::get()
+    }
+    /// Default stake amount window.
+    #[pallet::type_value]
+    pub fn DefaultStakeAmountWindow<T: Config>() -> u64 {
+        360
+    }
+    /// Default maximum amount of stake moved per window.
+    #[pallet::type_value]
+    pub fn DefaultMaxStakeAmountPerWindow<T: Config>() -> u64 {
+        T::InitialMaxStakeAmountPerWindow::get()
+    }
 
     /// Default base difficulty for proof of work for coldkey swaps
     #[pallet::type_value]
@@ -362,6 +426,24 @@ pub mod pallet {
     pub type BaseDifficulty<T> = StorageValue<_, u64, ValueQuery, DefaultBaseDifficulty<T>>;
     #[pallet::storage] // --- ITEM (default_stake_interval)
     pub type StakeInterval<T> = StorageValue<_, u64, ValueQuery, DefaultStakeInterval<T>>;
+
+    /// How staking operations of a (coldkey, hotkey) pair are rate limited.
+    #[derive(Encode, Decode, Default, TypeInfo, Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum StakeRateLimitMode {
+        /// At most TargetStakesPerInterval operations per StakeInterval.
+        #[default]
+        Count,
+        /// At most MaxStakeAmountPerWindow RAO moved per sliding StakeAmountWindow.
+        Amount,
+    }
+    #[pallet::storage] // --- ITEM ( stake_rate_limit_mode ) | Selects the stake rate limiter.
+    pub type StakeRateLimitSelector<T> =
+        StorageValue<_, StakeRateLimitMode, ValueQuery, DefaultStakeRateLimitMode<T>>;
+    #[pallet::storage] // --- ITEM ( stake_amount_window ) | Length in blocks of the stake amount window.
+    pub type StakeAmountWindow<T> = StorageValue<_, u64, ValueQuery, DefaultStakeAmountWindow<T>>;
+    #[pallet::storage] // --- ITEM ( max_stake_amount_per_window ) | Maximum amount of stake moved per window.
+    pub type MaxStakeAmountPerWindow<T> =
+        StorageValue<_, u64, ValueQuery, DefaultMaxStakeAmountPerWindow<T>>;
     #[pallet::storage] // --- MAP ( hot ) --> stake | Returns the total amount of stake under a hotkey.
     pub type TotalHotkeyStake<T: Config> =
         StorageMap<_, Identity, T::AccountId, u64, ValueQuery, DefaultAccountTake<T>>;
@@ -380,6 +462,17 @@ pub mod pallet {
         ValueQuery,
         DefaultStakesPerInterval<T>,
     >;
+    #[pallet::storage]
+    ///  MAP (cold, hot) --> (window, amount_this_window, amount_last_window) | Returns the stake moved in the current and previous window.
+    pub type TotalHotkeyColdkeyStakeAmountThisWindow<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        T::AccountId,
+        Identity,
+        T::AccountId,
+        (u64, u64, u64),
+        ValueQuery,
+    >;
     #[pallet::storage] // --- MAP ( hot ) --> cold | Returns the controlling coldkey for a hotkey.
     pub type Owner<T: Config> =
         StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId, ValueQuery, DefaultAccount<T>>;
@@ -404,6 +497,63 @@ pub mod pallet {
         DefaultAccountTake<T>,
     >;
 
+    /// Where the emission earned by a nomination is paid to.
+    #[derive(Encode, Decode, Default, TypeInfo, Clone, PartialEq, Eq, Debug)]
+    pub enum RewardDestination<AccountId> {
+        /// The emission is staked back onto the (hotkey, coldkey) stake.
+        #[default]
+        Compound,
+        /// The emission is paid to the free balance of the staking coldkey.
+        FreeBalance,
+        /// The emission is paid to the free balance of another coldkey.
+        Account(AccountId),
+    }
+    #[pallet::storage] // --- DMAP ( cold, hot ) --> destination | Returns where the emission of the nomination is paid to.
+    pub type RewardDestinations<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Identity,
+        T::AccountId,
+        RewardDestination<T::AccountId>,
+        ValueQuery,
+    >;
+    #[pallet::storage] // --- DMAP ( hot, cold ) --> (total_rewards, last_epoch_rewards, last_reward_block) | Returns the emission earned by a coldkey on a hotkey.
+    pub type NominatorRewards<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Identity,
+        T::AccountId,
+        (u64, u64, u64),
+        ValueQuery,
+    >;
+    #[pallet::storage] // --- MAP ( cold ) --> Vec<hot> | Returns the hotkeys the coldkey has a rewards ledger on.
+    pub type NominatorRewardHotkeys<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, Vec<T::AccountId>, ValueQuery>;
+    #[pallet::storage] // --- MAP ( hot ) --> enabled | Returns true if only allowlisted coldkeys may nominate to the delegate.
+    pub type NominationAllowlistEnabled<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, bool, ValueQuery>;
+    #[pallet::storage] // --- DMAP ( hot, cold ) --> allowed | Returns true if the coldkey is on the nomination allowlist of the delegate.
+    pub type NominationAllowlist<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Identity,
+        T::AccountId,
+        bool,
+        ValueQuery,
+    >;
+    #[pallet::storage] // --- MAP ( hot ) --> (max_nominators, max_nominated_stake) | Returns the nomination caps of the delegate, zero is uncapped.
+    pub type NominationLimits<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (u32, u64), ValueQuery>;
+    #[pallet::storage] // --- MAP ( hot ) --> len | Returns the number of coldkeys on the nomination allowlist of the delegate.
+    pub type NominationAllowlistLen<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+    #[pallet::storage] // --- MAP ( hot ) --> count | Returns the number of coldkeys with a non-zero stake on the hotkey, the owner included.
+    pub type DelegateNominatorCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
     #[pallet::type_value]
     /// Default value for hotkeys.
     pub fn EmptyAccounts<T: Config>() -> Vec<T::AccountId> {
@@ -434,6 +584,33 @@ pub mod pallet {
     #[pallet::storage] // --- MAP ( u64 ) --> Vec<coldkeys_to_drain>  | Coldkeys to drain on the specific block.
     pub type ColdkeysToSwapAtBlock<T: Config> =
         StorageMap<_, Identity, u64, Vec<T::AccountId>, ValueQuery, EmptyAccounts<T>>;
+    #[pallet::type_value]
+    /// Default unbonding period, zero releases removed stake immediately.
+    pub fn DefaultUnbondingPeriod<T: Config>() -> u64 {
+        0
+    }
+    #[pallet::type_value]
+    /// Default maximum number of pending unbonding chunks per coldkey.
+    pub fn DefaultMaxUnbondingChunks<T: Config>() -> u32 {
+        32
+    }
+    #[pallet::storage] // --- ITEM ( unbonding_period ) | Global number of blocks removed stake stays locked.
+    pub type UnbondingPeriod<T> = StorageValue<_, u64, ValueQuery, DefaultUnbondingPeriod<T>>;
+    #[pallet::storage] // --- MAP ( netuid ) --> unbonding_period | Unbonding period for hotkeys registered on the network.
+    pub type NetworkUnbondingPeriod<T> =
+        StorageMap<_, Identity, u16, u64, ValueQuery, DefaultUnbondingPeriod<T>>;
+    #[pallet::storage] // --- ITEM ( max_unbonding_chunks )
+    pub type MaxUnbondingChunks<T> = StorageValue<_, u32, ValueQuery, DefaultMaxUnbondingChunks<T>>;
+    #[pallet::storage] // --- MAP ( cold ) --> Vec<(amount, unlock_block)> | Removed stake waiting to be released to the coldkey.
+    pub type UnbondingQueue<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, Vec<(u64, u64)>, ValueQuery>;
+    #[pallet::storage] // --- MAP ( u64 ) --> Vec<coldkeys> | Coldkeys with stake unlocking on the specific block.
+    pub type ColdkeysToUnbondAtBlock<T: Config> =
+        StorageMap<_, Identity, u64, Vec<T::AccountId>, ValueQuery, EmptyAccounts<T>>;
+    #[pallet::storage] // --- ITEM ( unbonding_release_cursor ) | Next block whose unlocking stake on_idle has to release.
+    pub type UnbondingReleaseCursor<T> = StorageValue<_, u64, ValueQuery>;
+    #[pallet::storage] // --- ITEM ( unbonding_last_unlock_block ) | Latest block any unbonding stake unlocks at.
+    pub type UnbondingLastUnlockBlock<T> = StorageValue<_, u64, ValueQuery>;
     /// -- ITEM (switches liquid alpha on)
     #[pallet::type_value]
     pub fn DefaultLiquidAlpha<T: Config>() -> bool {
@@ -781,6 +958,138 @@ pub mod pallet {
         pub ip_type: u8,
     }
 
+    /// How the terms of a subnet's epoch are computed.
+    #[derive(Encode, Decode, Default, TypeInfo, Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum ConsensusMechanism {
+        /// Yuma consensus, clipping weights at the stake-weighted median.
+        #[default]
+        Yuma,
+        /// Stake-weighted mean of the weights, without clipping or bonds.
+        StakeWeightedMean,
+    }
+
+    /// Phase of an epoch staged across the blocks before tempo. Each phase runs in chunks over
+    /// a range of uids, or of weights segments for consensus.
+    #[derive(Encode, Decode, TypeInfo, Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum StagedEpochPhase {
+        /// Neuron state, weights and bonds are read from storage.
+        Gather,
+        /// Weights are masked and row normalized and bonds masked, accumulating preranks and
+        /// bond column sums.
+        Weights,
+        /// Consensus is computed over the segments of the weights.
+        Consensus,
+        /// Weights are clipped at consensus, accumulating ranks and bond delta column sums.
+        Clip,
+        /// Bonds are replaced by their exponential moving average.
+        Bonds,
+        /// Dividends are computed.
+        Dividends,
+        /// Epoch terms, validator agreement and bonds are written.
+        Write,
+        /// Everything is written, the emission is loaded at tempo.
+        Done,
+    }
+
+    /// Cursor of an epoch staged across the blocks before tempo.
+    #[freeze_struct("3eeb3000e363f160")]
+    #[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+    pub struct StagedEpochCursor {
+        /// Mechanism the epoch terms are computed with.
+        pub mechanism: ConsensusMechanism,
+        /// Phase being run.
+        pub phase: StagedEpochPhase,
+        /// Next uid, or weights segment, of the phase.
+        pub next: u16,
+        /// Number of uids the epoch runs over.
+        pub n: u16,
+        /// Block the epoch is due at.
+        pub epoch_block: u64,
+        /// Block the stage started at.
+        pub start_block: u64,
+        /// Pending emission distributed by the epoch.
+        pub rao_emission: u64,
+    }
+
+    /// Neuron state gathered by a staged epoch.
+    #[freeze_struct("53481b9d8f29b5d")]
+    #[derive(Encode, Decode, Default, TypeInfo, Clone, PartialEq, Eq, Debug)]
+    pub struct StagedNeuron {
+        /// Total stake of the hotkey.
+        pub stake: u64,
+        /// Block the uid was registered at.
+        pub registered: u64,
+        /// Block the uid last set weights at.
+        pub last_update: u64,
+        /// Validator permit held during the epoch.
+        pub permit: bool,
+        /// The uid set weights within the activity cutoff.
+        pub active: bool,
+        /// The weights of the uid are identical to those of another validator.
+        pub identical: bool,
+        /// A weights row is staged for the uid.
+        pub weighted: bool,
+        /// A bonds row is staged for the uid.
+        pub bonded: bool,
+        /// The uid was replaced before the epoch terms were written.
+        pub replaced: bool,
+        /// Server emission of the uid.
+        pub server_emission: u64,
+        /// Validator emission of the uid.
+        pub validator_emission: u64,
+    }
+
+    /// Per-uid term of a staged epoch, kept as a vector over the uids.
+    #[derive(Encode, Decode, TypeInfo, Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum StagedEpochTerm {
+        /// Normalized stake.
+        Stake,
+        /// Normalized stake of the active, permitted validators.
+        ActiveStake,
+        /// Ranks before clipping.
+        Preranks,
+        /// Consensus.
+        Consensus,
+        /// Cosine similarity of the weights to consensus.
+        ConsensusSimilarity,
+        /// Weight before clipping.
+        UnclippedWeight,
+        /// Validator trust.
+        ValidatorTrust,
+        /// Ranks, accumulated while clipping and normalized after.
+        Ranks,
+        /// Trust.
+        Trust,
+        /// Per-column EMA alpha of the bonds.
+        BondsAlpha,
+        /// Column sums of the masked bonds.
+        BondsSum,
+        /// Column sums of the bonds delta.
+        BondsDeltaSum,
+        /// Column sums of the EMA bonds.
+        EmaBondsSum,
+        /// Column maxima of the normalized EMA bonds.
+        EmaBondsMax,
+        /// Dividends, accumulated and normalized once complete.
+        Dividends,
+    }
+
+    /// Data structure for the compact summary of an epoch kept in the epoch history.
+    #[freeze_struct("6320a037e5bda253")]
+    #[derive(Encode, Decode, Default, TypeInfo, Clone, PartialEq, Eq, Debug)]
+    pub struct EpochSummary {
+        /// Block the epoch ran at.
+        pub block: u64,
+        /// Pending emission distributed by the epoch.
+        pub emission_total: u64,
+        /// Per-uid incentive.
+        pub incentive: Vec<u16>,
+        /// Per-uid dividends.
+        pub dividends: Vec<u16>,
+        /// Per-uid emission.
+        pub emission: Vec<u64>,
+    }
+
     /// Default value for rate limiting
     #[pallet::type_value]
     pub fn DefaultTxRateLimit<T: Config>() -> u64 {
@@ -791,6 +1100,11 @@ pub mod pallet {
     pub fn DefaultTxDelegateTakeRateLimit<T: Config>() -> u64 {
         T::InitialTxDelegateTakeRateLimit::get()
     }
+    /// Default notice period for delegate take increases.
+    #[pallet::type_value]
+    pub fn DefaultTakeIncreaseDelay<T: Config>() -> u64 {
+        T::InitialTakeIncreaseDelay::get()
+    }
     /// Default value for last extrinsic block.
     #[pallet::type_value]
     pub fn DefaultLastTxBlock<T: Config>() -> u64 {
@@ -802,6 +1116,14 @@ pub mod pallet {
     #[pallet::storage] // --- ITEM ( tx_rate_limit )
     pub(super) type TxDelegateTakeRateLimit<T> =
         StorageValue<_, u64, ValueQuery, DefaultTxDelegateTakeRateLimit<T>>;
+    #[pallet::storage] // --- ITEM ( take_increase_delay ) | Blocks between announcing a take increase and applying it.
+    pub type TakeIncreaseDelay<T> = StorageValue<_, u64, ValueQuery, DefaultTakeIncreaseDelay<T>>;
+    #[pallet::storage] // --- MAP ( hot ) --> (take, effective_block) | Returns the announced take increase of a delegate.
+    pub type PendingTakeIncreases<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (u16, u64), OptionQuery>;
+    #[pallet::storage] // --- MAP ( u64 ) --> Vec<hot> | Delegates whose announced take increase applies on the block.
+    pub type TakeIncreasesAtBlock<T: Config> =
+        StorageMap<_, Identity, u64, Vec<T::AccountId>, ValueQuery, EmptyAccounts<T>>;
     #[pallet::storage] // --- MAP ( key ) --> last_block
     pub type LastTxBlock<T: Config> =
         StorageMap<_, Identity, T::AccountId, u64, ValueQuery, DefaultLastTxBlock<T>>;
@@ -881,6 +1203,26 @@ pub mod pallet {
     pub fn DefaultWeightsVersionKey<T: Config>() -> u64 {
         T::InitialWeightsVersionKey::get()
     }
+    /// Default consensus mechanism.
+    #[pallet::type_value]
+    pub fn DefaultConsensusMechanism<T: Config>() -> ConsensusMechanism {
+        ConsensusMechanism::Yuma
+    }
+    /// Default epoch history depth, 0 means no epoch summaries are kept.
+    #[pallet::type_value]
+    pub fn DefaultEpochHistoryDepth<T: Config>() -> u16 {
+        0
+    }
+    /// Default weights window, 0 means weights are accepted at any block.
+    #[pallet::type_value]
+    pub fn DefaultWeightsWindow<T: Config>() -> u64 {
+        0
+    }
+    /// Default weights max version key, 0 means there is no maximum.
+    #[pallet::type_value]
+    pub fn DefaultWeightsMaxVersionKey<T: Config>() -> u64 {
+        0
+    }
     /// Default minimal allowed weights.
     #[pallet::type_value]
     pub fn DefaultMinAllowedWeights<T: Config>() -> u16 {
@@ -964,6 +1306,14 @@ pub mod pallet {
     #[pallet::storage] // --- MAP ( netuid ) --> weights_version_key
     pub type WeightsVersionKey<T> =
         StorageMap<_, Identity, u16, u64, ValueQuery, DefaultWeightsVersionKey<T>>;
+    #[pallet::storage] // --- MAP ( netuid ) --> weights_window
+    pub type WeightsWindow<T> =
+        StorageMap<_, Identity, u16, u64, ValueQuery, DefaultWeightsWindow<T>>;
+    #[pallet::storage] // --- MAP ( netuid ) --> weights_max_version_key
+    pub type WeightsMaxVersionKey<T> =
+        StorageMap<_, Identity, u16, u64, ValueQuery, DefaultWeightsMaxVersionKey<T>>;
+    #[pallet::storage] // --- MAP ( netuid ) --> banned_weights_version_keys
+    pub type BannedWeightsVersionKeys<T> = StorageMap<_, Identity, u16, Vec<u64>, ValueQuery>;
     #[pallet::storage] // --- MAP ( netuid ) --> min_allowed_weights
     pub type MinAllowedWeights<T> =
         StorageMap<_, Identity, u16, u16, ValueQuery, DefaultMinAllowedWeights<T>>;
@@ -1008,16 +1358,18 @@ pub mod pallet {
     pub type AlphaValues<T> =
         StorageMap<_, Identity, u16, (u16, u16), ValueQuery, DefaultAlphaValues<T>>;
 
-    #[pallet::storage] // --- MAP (netuid, who) --> (hash, weight) | Returns the hash and weight committed by an account for a given netuid.
+    #[pallet::storage] // --- DMAP (netuid, who) --> VecDeque<(hash, commit_block)> | Returns the pending weight commits of an account for a given netuid, oldest first.
     pub type WeightCommits<T: Config> = StorageDoubleMap<
         _,
         Twox64Concat,
         u16,
         Twox64Concat,
         T::AccountId,
-        (H256, u64),
+        VecDeque<(H256, u64)>,
         OptionQuery,
     >;
+    #[pallet::storage] // --- ITEM ( raw_key ) | The WeightCommits key after which the expired commit sweep resumes.
+    pub type WeightCommitsSweepCursor<T> = StorageValue<_, Vec<u8>, OptionQuery>;
 
     /// Default value for weight commit reveal interval.
     #[pallet::type_value]
@@ -1118,6 +1470,39 @@ pub mod pallet {
     #[pallet::storage] // --- DMAP ( netuid ) --> validator_permit
     pub(super) type ValidatorPermit<T: Config> =
         StorageMap<_, Identity, u16, Vec<bool>, ValueQuery, EmptyBoolVec<T>>;
+    #[pallet::storage] // --- DMAP ( netuid, uid ) --> ( consensus_similarity, clipped_weight_share, identical_weight_epochs ) | The last count is of consecutive epochs.
+    pub type ValidatorAgreement<T: Config> =
+        StorageDoubleMap<_, Identity, u16, Identity, u16, (u16, u16, u32), ValueQuery>;
+    #[pallet::storage] // --- MAP ( netuid ) --> consensus_mechanism | Selects how the subnet's epoch terms are computed.
+    pub type SubnetConsensusMechanism<T> =
+        StorageMap<_, Identity, u16, ConsensusMechanism, ValueQuery, DefaultConsensusMechanism<T>>;
+    #[pallet::storage] // --- MAP ( netuid ) --> epoch_history_depth | Number of epoch summaries kept for the subnet.
+    pub type EpochHistoryDepth<T> =
+        StorageMap<_, Identity, u16, u16, ValueQuery, DefaultEpochHistoryDepth<T>>;
+    #[pallet::storage] // --- MAP ( netuid ) --> epochs_recorded | Number of epoch summaries recorded since the depth was set.
+    pub type EpochHistoryIndex<T> = StorageMap<_, Identity, u16, u64, ValueQuery>;
+    #[pallet::storage] // --- DMAP ( netuid, slot ) --> epoch_summary | Ring buffer of epoch summaries, written at slot epochs_recorded % depth.
+    pub type EpochHistory<T> =
+        StorageDoubleMap<_, Identity, u16, Identity, u16, EpochSummary, OptionQuery>;
+    #[pallet::storage] // --- MAP ( netuid ) --> staged_epoch_cursor | Cursor of the epoch staged ahead of the subnet's tempo.
+    pub type StagedEpoch<T> = StorageMap<_, Identity, u16, StagedEpochCursor, OptionQuery>;
+    #[pallet::storage] // --- MAP ( netuid ) --> staged_neurons | Neuron state gathered by the staged epoch, indexed by uid.
+    pub type StagedEpochNeurons<T> = StorageMap<_, Identity, u16, Vec<StagedNeuron>, ValueQuery>;
+    #[pallet::storage] // --- MAP ( netuid ) --> ( uid, hotkey ) | Hotkeys the staged epoch pays.
+    pub type StagedEpochHotkeys<T: Config> =
+        StorageMap<_, Identity, u16, Vec<(u16, T::AccountId)>, ValueQuery>;
+    #[pallet::storage] // --- MAP ( netuid ) --> ( uid, digest ) | Digests of the permitted weights rows gathered by the staged epoch.
+    pub type StagedEpochWeightDigests<T> =
+        StorageMap<_, Identity, u16, Vec<(u16, H256)>, ValueQuery>;
+    #[pallet::storage] // --- DMAP ( netuid, term ) --> values | Per-uid terms of the staged epoch.
+    pub type StagedEpochTerms<T> =
+        StorageDoubleMap<_, Identity, u16, Identity, StagedEpochTerm, Vec<I32F32>, ValueQuery>;
+    #[pallet::storage] // --- DMAP ( netuid, ( uid, segment ) ) --> weights | Staged weights rows, in segments of STAGED_EPOCH_SEGMENT_COLUMNS columns.
+    pub type StagedEpochWeights<T> =
+        StorageDoubleMap<_, Identity, u16, Identity, (u16, u16), Vec<(u16, I32F32)>, ValueQuery>;
+    #[pallet::storage] // --- DMAP ( netuid, uid ) --> bonds | Staged bonds rows.
+    pub type StagedEpochBonds<T> =
+        StorageDoubleMap<_, Identity, u16, Identity, u16, Vec<(u16, I32F32)>, ValueQuery>;
 
     #[pallet::storage] // --- DMAP ( netuid, uid ) --> weights
     pub(super) type Weights<T: Config> = StorageDoubleMap<
@@ -1280,6 +1665,7 @@ pub mod pallet {
                     TotalIssuance::<T>::put(TotalIssuance::<T>::get().saturating_add(*stake));
 
                     Stake::<T>::insert(hotkey.clone(), coldkey.clone(), stake);
+                    Pallet::<T>::update_delegate_nominator_count(hotkey, 0, *stake);
 
                     // Update StakingHotkeys map
                     let mut staking_hotkeys = StakingHotkeys::<T>::get(coldkey);
@@ -1348,8 +1734,15 @@ pub mod pallet {
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-        fn on_idle(_n: BlockNumberFor<T>, _remaining_weight: Weight) -> Weight {
-            Weight::zero()
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            // Release unbonded stake with whatever weight is left in the block.
+            let mut weight_used: Weight = Self::release_unbonded_stake_on_idle(remaining_weight);
+
+            // Then sweep the weight commits which can no longer be revealed.
+            weight_used.saturating_accrue(Self::remove_expired_weight_commits_on_idle(
+                remaining_weight.saturating_sub(weight_used),
+            ));
+            weight_used
         }
 
         fn on_initialize(_block_number: BlockNumberFor<T>) -> Weight {
@@ -1368,24 +1761,24 @@ pub mod pallet {
             };
             total_weight = total_weight.saturating_add(swap_weight);
 
-            // Perform block step
+            // Apply the delegate take increases which become effective on this block
+            let take_weight = Self::apply_pending_take_increases(Self::get_current_block_as_u64());
+            total_weight = total_weight.saturating_add(take_weight);
+
+            // Perform block step. The fixed part of the step, benchmark_block_step, is charged
+            // here and the staged epochs report their own weight on top.
+            let block_step_weight: Weight = Weight::from_parts(5_000_000_000_u64, 0)
+                .saturating_add(T::DbWeight::get().reads(112_u64))
+                .saturating_add(T::DbWeight::get().writes(110_u64));
+            total_weight = total_weight.saturating_add(block_step_weight);
             let block_step_result = Self::block_step();
             match block_step_result {
-                Ok(_) => {
+                Ok(epoch_weight) => {
                     log::debug!("Successfully ran block step.");
-                    total_weight = total_weight.saturating_add(
-                        Weight::from_parts(110_634_229_000_u64, 0)
-                            .saturating_add(T::DbWeight::get().reads(8304_u64))
-                            .saturating_add(T::DbWeight::get().writes(110_u64)),
-                    );
+                    total_weight = total_weight.saturating_add(epoch_weight);
                 }
                 Err(e) => {
                     log::error!("Error while stepping block: {:?}", e);
-                    total_weight = total_weight.saturating_add(
-                        Weight::from_parts(110_634_229_000_u64, 0)
-                            .saturating_add(T::DbWeight::get().reads(8304_u64))
-                            .saturating_add(T::DbWeight::get().writes(110_u64)),
-                    );
                 }
             }
 
@@ -1421,10 +1814,22 @@ pub mod pallet {
                 // Populate OwnedHotkeys map for coldkey swap. Doesn't update storage vesion.
                 .saturating_add(migration::migrate_populate_owned::<T>())
                 // Populate StakingHotkeys map for coldkey swap. Doesn't update storage vesion.
-                .saturating_add(migration::migrate_populate_staking_hotkeys::<T>());
+                .saturating_add(migration::migrate_populate_staking_hotkeys::<T>())
+                // Storage version v6 -> v7
+                .saturating_add(migration::migrate_to_v7_stake_amount_rate_limit::<T>())
+                // Storage version v7 -> v8
+                .saturating_add(migration::migrate_to_v8_weight_commits_queue::<T>())
+                // Storage version v8 -> v9
+                .saturating_add(migration::migrate_to_v9_delegate_nominator_count::<T>());
 
             weight
         }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+            Self::check_staking_hotkeys_index()?;
+            Ok(())
+        }
     }
 
     /// Dispatchable functions allow users to interact with the pallet and invoke state changes.
@@ -1445,6 +1850,10 @@ pub mod pallet {
         /// elements is larger or smaller than the amount of elements * u16_max, all elements
         /// will be corrected for this deviation.
         ///
+        /// Note: The epoch gathers the weights when it is staged, STAGED_EPOCH_LEAD_BLOCKS
+        /// (at most half the tempo) blocks before tempo. Weights set after that are only read
+        /// by the next epoch, with the default weights window of 0 they are accepted regardless.
+        ///
         /// # Args:
         /// * `origin`: (<T as frame_system::Config>Origin):
         ///     - The caller, a hotkey who wishes to set their weights.
@@ -1633,6 +2042,12 @@ pub mod pallet {
         ///  * 'IncorrectWeightVersionKey':
         ///      - Attempting to set weights with the incorrect network version key.
         ///
+        ///  * 'WeightVersionKeyTooHigh':
+        ///      - Attempting to set weights with a version key above the network maximum.
+        ///
+        ///  * 'WeightVersionKeyBanned':
+        ///      - Attempting to set weights with a version key banned on the network.
+        ///
         ///  * 'SettingWeightsTooFast':
         ///      - Attempting to set weights too fast.
         ///
@@ -1731,7 +2146,8 @@ pub mod pallet {
             Self::do_decrease_take(origin, hotkey, take)
         }
 
-        /// --- Allows delegates to increase its take value. This call is rate-limited.
+        /// --- Allows delegates to announce an increase of its take value. The new take is
+        /// applied once the take increase delay has passed. This call is rate-limited.
         ///
         /// # Args:
         /// * 'origin': (<T as frame_system::Config>::Origin):
@@ -1748,8 +2164,14 @@ pub mod pallet {
         ///        [0.01 * 65535] = [655.35] = 655
         ///
         /// # Event:
+        /// * TakeIncreaseAnnounced;
+        /// 	- On successfully announcing an increased take for this hotkey.
+        ///
         /// * TakeIncreased;
-        /// 	- On successfully setting a increased take for this hotkey.
+        /// 	- When the increased take is applied to this hotkey.
+        ///
+        /// * TakeIncreaseApplied;
+        /// 	- When the increased take is applied to this hotkey.
         ///
         /// # Raises:
         /// * 'NotRegistered':
@@ -1803,8 +2225,8 @@ pub mod pallet {
         ///
         #[pallet::call_index(2)]
         #[pallet::weight((Weight::from_parts(124_000_000, 0)
-		.saturating_add(T::DbWeight::get().reads(10))
-		.saturating_add(T::DbWeight::get().writes(7)), DispatchClass::Normal, Pays::No))]
+		.saturating_add(T::DbWeight::get().reads(13))
+		.saturating_add(T::DbWeight::get().writes(8)), DispatchClass::Normal, Pays::No))]
         pub fn add_stake(
             origin: OriginFor<T>,
             hotkey: T::AccountId,
@@ -1844,8 +2266,8 @@ pub mod pallet {
         #[pallet::call_index(3)]
         #[pallet::weight((Weight::from_parts(111_000_000, 0)
 		.saturating_add(Weight::from_parts(0, 43991))
-		.saturating_add(T::DbWeight::get().reads(10))
-		.saturating_add(T::DbWeight::get().writes(7)), DispatchClass::Normal, Pays::No))]
+		.saturating_add(T::DbWeight::get().reads(13))
+		.saturating_add(T::DbWeight::get().writes(8)), DispatchClass::Normal, Pays::No))]
         pub fn remove_stake(
             origin: OriginFor<T>,
             hotkey: T::AccountId,
@@ -1854,6 +2276,483 @@ pub mod pallet {
             Self::do_remove_stake(origin, hotkey, amount_unstaked)
         }
 
+        /// Moves stake from one hotkey to another for the calling coldkey, without
+        /// crediting the coldkey balance in between.
+        ///
+        /// # Args:
+        /// * 'origin': (<T as frame_system::Config>Origin):
+        /// 	- The signature of the caller's coldkey.
+        ///
+        /// * 'origin_hotkey' (T::AccountId):
+        /// 	- The hotkey the stake is moved from.
+        ///
+        /// * 'destination_hotkey' (T::AccountId):
+        /// 	- The hotkey the stake is moved to.
+        ///
+        /// * 'amount' (u64):
+        /// 	- The amount of stake to be moved.
+        ///
+        /// # Event:
+        /// * StakeMoved;
+        /// 	- On the successfully moving stake between the hotkeys.
+        ///
+        /// # Raises:
+        /// * 'HotKeyAccountNotExists':
+        /// 	- Thrown if either hotkey is not registered.
+        ///
+        /// * 'HotKeyNotDelegateAndSignerNotOwnHotKey':
+        /// 	- Thrown if either hotkey is not a delegate and not owned by the coldkey.
+        ///
+        /// * 'NotEnoughStakeToWithdraw':
+        /// 	- Thrown if there is not enough stake on the origin hotkey to move this amount.
+        ///
+        /// * 'NomStakeBelowMinimumThreshold':
+        /// 	- Thrown if the resulting nomination on the destination is below the minimum.
+        ///
+        #[pallet::call_index(73)]
+        #[pallet::weight((Weight::from_parts(142_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(21))
+		.saturating_add(T::DbWeight::get().writes(16)), DispatchClass::Normal, Pays::No))]
+        pub fn move_stake(
+            origin: OriginFor<T>,
+            origin_hotkey: T::AccountId,
+            destination_hotkey: T::AccountId,
+            amount: u64,
+        ) -> DispatchResult {
+            Self::do_move_stake(origin, origin_hotkey, destination_hotkey, amount)
+        }
+
+        /// Releases all unbonding stake of the calling coldkey whose unbonding period has passed.
+        /// Matured stake is also released automatically when blocks have spare weight.
+        ///
+        /// # Args:
+        /// * 'origin': (<T as frame_system::Config>Origin):
+        /// 	- The signature of the caller's coldkey.
+        ///
+        /// # Event:
+        /// * UnbondedStakeReleased;
+        /// 	- On the successfully releasing the matured stake to the coldkey balance.
+        ///
+        /// # Raises:
+        /// * 'NoUnbondedStakeToClaim':
+        /// 	- Thrown if the coldkey has no matured unbonding stake.
+        ///
+        #[pallet::call_index(74)]
+        #[pallet::weight((Weight::from_parts(45_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(3))
+		.saturating_add(T::DbWeight::get().writes(3)), DispatchClass::Normal, Pays::No))]
+        pub fn claim_unbonded(origin: OriginFor<T>) -> DispatchResult {
+            Self::do_claim_unbonded(origin)
+        }
+
+        /// Adds stake to many hotkeys in a single call. The call is atomic, if any leg fails
+        /// no stake is added. Every leg goes through the same checks as add_stake.
+        ///
+        /// # Args:
+        /// * 'origin': (<T as frame_system::Config>Origin):
+        /// 	- The signature of the caller's coldkey.
+        ///
+        /// * 'stakes' (Vec<(T::AccountId, u64)>):
+        /// 	- The hotkeys and the amounts to stake on each of them.
+        ///
+        /// # Event:
+        /// * StakeAdded;
+        /// 	- For every leg which adds stake to a hotkey account.
+        ///
+        /// # Raises:
+        /// * 'InvalidStakeBatchSize':
+        /// 	- The batch is empty or has more than MAX_STAKE_BATCH_SIZE legs.
+        ///
+        /// * 'NotEnoughBalanceToStake':
+        /// 	- Not enough balance on the coldkey to add onto the global account.
+        ///
+        #[pallet::call_index(75)]
+        #[pallet::weight((Weight::from_parts(124_000_000, 0).saturating_mul(stakes.len().min(MAX_STAKE_BATCH_SIZE as usize) as u64)
+		.saturating_add(T::DbWeight::get().reads(11))
+		.saturating_add(T::DbWeight::get().writes(6))
+		.saturating_add(T::DbWeight::get().reads(11).saturating_mul(stakes.len().min(MAX_STAKE_BATCH_SIZE as usize) as u64))
+		.saturating_add(T::DbWeight::get().writes(5).saturating_mul(stakes.len().min(MAX_STAKE_BATCH_SIZE as usize) as u64)), DispatchClass::Normal, Pays::No))]
+        pub fn add_stake_multiple(
+            origin: OriginFor<T>,
+            stakes: Vec<(T::AccountId, u64)>,
+        ) -> DispatchResult {
+            Self::do_add_stake_multiple(origin, stakes)
+        }
+
+        /// Removes stake from many hotkeys in a single call. The call is atomic, if any leg fails
+        /// no stake is removed. Every leg goes through the same checks as remove_stake.
+        ///
+        /// # Args:
+        /// * 'origin': (<T as frame_system::Config>Origin):
+        /// 	- The signature of the caller's coldkey.
+        ///
+        /// * 'unstakes' (Vec<(T::AccountId, u64)>):
+        /// 	- The hotkeys and the amounts to unstake from each of them.
+        ///
+        /// # Event:
+        /// * StakeRemoved;
+        /// 	- For every leg which removes stake from a hotkey account.
+        ///
+        /// # Raises:
+        /// * 'InvalidStakeBatchSize':
+        /// 	- The batch is empty or has more than MAX_STAKE_BATCH_SIZE legs.
+        ///
+        /// * 'NotEnoughStakeToWithdraw':
+        /// 	- Thrown if there is not enough stake on a hotkey to withdwraw the amount.
+        ///
+        #[pallet::call_index(76)]
+        #[pallet::weight((Weight::from_parts(111_000_000, 43991).saturating_mul(unstakes.len().min(MAX_STAKE_BATCH_SIZE as usize) as u64)
+		.saturating_add(T::DbWeight::get().reads(17))
+		.saturating_add(T::DbWeight::get().writes(9))
+		.saturating_add(T::DbWeight::get().reads(10).saturating_mul(unstakes.len().min(MAX_STAKE_BATCH_SIZE as usize) as u64))
+		.saturating_add(T::DbWeight::get().writes(6).saturating_mul(unstakes.len().min(MAX_STAKE_BATCH_SIZE as usize) as u64)), DispatchClass::Normal, Pays::No))]
+        pub fn remove_stake_multiple(
+            origin: OriginFor<T>,
+            unstakes: Vec<(T::AccountId, u64)>,
+        ) -> DispatchResult {
+            Self::do_remove_stake_multiple(origin, unstakes)
+        }
+
+        /// Sets where the emission earned by the caller's stake on a hotkey is paid to.
+        ///
+        /// # Args:
+        /// * 'origin': (<T as frame_system::Config>Origin):
+        /// 	- The signature of the caller's coldkey.
+        ///
+        /// * 'hotkey' (T::AccountId):
+        /// 	- The hotkey the caller's stake is delegated to.
+        ///
+        /// * 'destination' (RewardDestination<T::AccountId>):
+        /// 	- Compound the emission, pay it to the caller's free balance or to another coldkey.
+        ///
+        /// # Event:
+        /// * RewardDestinationSet;
+        /// 	- On successfully setting the reward destination.
+        ///
+        /// # Raises:
+        /// * 'HotKeyAccountNotExists':
+        /// 	- The hotkey account does not exist.
+        ///
+        #[pallet::call_index(77)]
+        #[pallet::weight((Weight::from_parts(20_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(3))
+		.saturating_add(T::DbWeight::get().writes(1)), DispatchClass::Normal, Pays::No))]
+        pub fn set_reward_destination(
+            origin: OriginFor<T>,
+            hotkey: T::AccountId,
+            destination: RewardDestination<T::AccountId>,
+        ) -> DispatchResult {
+            Self::do_set_reward_destination(origin, hotkey, destination)
+        }
+
+        /// Transfers stake on a hotkey from the calling coldkey to another coldkey, without
+        /// unstaking it.
+        ///
+        /// # Args:
+        /// * 'origin': (<T as frame_system::Config>Origin):
+        /// 	- The signature of the caller's coldkey.
+        ///
+        /// * 'destination_coldkey' (T::AccountId):
+        /// 	- The coldkey the stake is transferred to.
+        ///
+        /// * 'hotkey' (T::AccountId):
+        /// 	- The hotkey the stake is staked on.
+        ///
+        /// * 'amount' (u64):
+        /// 	- The amount of stake to be transferred.
+        ///
+        /// # Event:
+        /// * StakeTransferred;
+        /// 	- On the successfully transferring stake between the coldkeys.
+        ///
+        /// # Raises:
+        /// * 'HotKeyAccountNotExists':
+        /// 	- Thrown if the hotkey is not registered.
+        ///
+        /// * 'HotKeyNotDelegateAndSignerNotOwnHotKey':
+        /// 	- Thrown if the hotkey is not a delegate and not owned by the destination coldkey.
+        ///
+        /// * 'NotEnoughStakeToWithdraw':
+        /// 	- Thrown if there is not enough stake on the hotkey to transfer this amount.
+        ///
+        /// * 'NomStakeBelowMinimumThreshold':
+        /// 	- Thrown if the resulting nomination of the destination coldkey is below the minimum.
+        ///
+        #[pallet::call_index(78)]
+        #[pallet::weight((Weight::from_parts(130_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(20))
+		.saturating_add(T::DbWeight::get().writes(15)), DispatchClass::Normal, Pays::No))]
+        pub fn transfer_stake(
+            origin: OriginFor<T>,
+            destination_coldkey: T::AccountId,
+            hotkey: T::AccountId,
+            amount: u64,
+        ) -> DispatchResult {
+            Self::do_transfer_stake(origin, destination_coldkey, hotkey, amount)
+        }
+
+        /// Restricts nominations to the delegate to the coldkeys on its allowlist, or lifts
+        /// the restriction.
+        ///
+        /// # Args:
+        /// * 'origin': (<T as frame_system::Config>Origin):
+        /// 	- The signature of the caller's coldkey.
+        ///
+        /// * 'hotkey' (T::AccountId):
+        /// 	- The delegate hotkey.
+        ///
+        /// * 'enabled' (bool):
+        /// 	- Whether only allowlisted coldkeys may nominate to the delegate.
+        ///
+        /// # Event:
+        /// * NominationAllowlistEnabledSet;
+        /// 	- On successfully setting the restriction.
+        ///
+        /// # Raises:
+        /// * 'HotKeyAccountNotExists':
+        /// 	- Thrown if the hotkey is not registered.
+        ///
+        /// * 'NonAssociatedColdKey':
+        /// 	- Thrown if the caller does not own the hotkey.
+        ///
+        /// * 'HotKeyNotDelegateAndSignerNotOwnHotKey':
+        /// 	- Thrown if the hotkey is not a delegate.
+        ///
+        #[pallet::call_index(79)]
+        #[pallet::weight((Weight::from_parts(20_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(4))
+		.saturating_add(T::DbWeight::get().writes(1)), DispatchClass::Normal, Pays::No))]
+        pub fn set_nomination_allowlist_enabled(
+            origin: OriginFor<T>,
+            hotkey: T::AccountId,
+            enabled: bool,
+        ) -> DispatchResult {
+            Self::do_set_nomination_allowlist_enabled(origin, hotkey, enabled)
+        }
+
+        /// Adds a coldkey to, or removes it from, the nomination allowlist of the delegate.
+        /// Removing a coldkey does not remove its existing stake.
+        ///
+        /// # Args:
+        /// * 'origin': (<T as frame_system::Config>Origin):
+        /// 	- The signature of the caller's coldkey.
+        ///
+        /// * 'hotkey' (T::AccountId):
+        /// 	- The delegate hotkey.
+        ///
+        /// * 'nominator' (T::AccountId):
+        /// 	- The coldkey to allow or disallow.
+        ///
+        /// * 'allowed' (bool):
+        /// 	- Whether the coldkey is on the allowlist.
+        ///
+        /// # Event:
+        /// * NominationAllowlistUpdated;
+        /// 	- On successfully updating the allowlist.
+        ///
+        /// # Raises:
+        /// * 'HotKeyAccountNotExists':
+        /// 	- Thrown if the hotkey is not registered.
+        ///
+        /// * 'NonAssociatedColdKey':
+        /// 	- Thrown if the caller does not own the hotkey.
+        ///
+        /// * 'HotKeyNotDelegateAndSignerNotOwnHotKey':
+        /// 	- Thrown if the hotkey is not a delegate.
+        ///
+        /// * 'NominationAllowlistFull':
+        /// 	- Thrown if the allowlist already holds MAX_NOMINATION_ALLOWLIST_LEN coldkeys.
+        ///
+        #[pallet::call_index(80)]
+        #[pallet::weight((Weight::from_parts(20_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(6))
+		.saturating_add(T::DbWeight::get().writes(2)), DispatchClass::Normal, Pays::No))]
+        pub fn set_nomination_allowed(
+            origin: OriginFor<T>,
+            hotkey: T::AccountId,
+            nominator: T::AccountId,
+            allowed: bool,
+        ) -> DispatchResult {
+            Self::do_set_nomination_allowed(origin, hotkey, nominator, allowed)
+        }
+
+        /// Caps the number of nominators and the total stake nominated to the delegate.
+        /// Lowering a cap does not remove existing stake.
+        ///
+        /// # Args:
+        /// * 'origin': (<T as frame_system::Config>Origin):
+        /// 	- The signature of the caller's coldkey.
+        ///
+        /// * 'hotkey' (T::AccountId):
+        /// 	- The delegate hotkey.
+        ///
+        /// * 'max_nominators' (u32):
+        /// 	- The maximum number of nominators, zero is uncapped.
+        ///
+        /// * 'max_nominated_stake' (u64):
+        /// 	- The maximum total stake of the nominators, zero is uncapped.
+        ///
+        /// # Event:
+        /// * NominationLimitsSet;
+        /// 	- On successfully setting the caps.
+        ///
+        /// # Raises:
+        /// * 'HotKeyAccountNotExists':
+        /// 	- Thrown if the hotkey is not registered.
+        ///
+        /// * 'NonAssociatedColdKey':
+        /// 	- Thrown if the caller does not own the hotkey.
+        ///
+        /// * 'HotKeyNotDelegateAndSignerNotOwnHotKey':
+        /// 	- Thrown if the hotkey is not a delegate.
+        ///
+        #[pallet::call_index(81)]
+        #[pallet::weight((Weight::from_parts(20_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(4))
+		.saturating_add(T::DbWeight::get().writes(1)), DispatchClass::Normal, Pays::No))]
+        pub fn set_nomination_limits(
+            origin: OriginFor<T>,
+            hotkey: T::AccountId,
+            max_nominators: u32,
+            max_nominated_stake: u64,
+        ) -> DispatchResult {
+            Self::do_set_nomination_limits(origin, hotkey, max_nominators, max_nominated_stake)
+        }
+
+        /// --- Sets the caller weights on several subnetworks at once. Every entry goes through
+        /// the same checks as set_weights and is applied on its own, so a failing entry does not
+        /// revert the entries that succeeded.
+        ///
+        /// # Args:
+        /// * `origin`: (<T as frame_system::Config>Origin):
+        /// 	- The caller, a hotkey who wishes to set their weights.
+        ///
+        /// * `weights` (Vec<(u16, Vec<u16>, Vec<u16>, u64)>):
+        /// 	- The (netuid, dests, weights, version_key) entries, as passed to set_weights.
+        ///
+        /// # Event:
+        /// * WeightsSet;
+        /// 	- For every entry successfully set on chain.
+        ///
+        /// * BatchWeightsCompleted;
+        /// 	- With the result of every entry, by netuid.
+        ///
+        /// # Raises:
+        /// * 'InvalidWeightsBatchSize':
+        /// 	- The batch is empty or has more than MAX_WEIGHTS_BATCH_SIZE entries.
+        ///
+        /// The weight of every entry is charged for the subnet size, and refunded down to the
+        /// number of uids it sets once the batch is processed.
+        ///
+        #[pallet::call_index(82)]
+        #[pallet::weight((Weight::from_parts(22_060_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(4106))
+		.saturating_add(T::DbWeight::get().writes(2))
+		.saturating_mul(weights.len().min(MAX_WEIGHTS_BATCH_SIZE as usize) as u64), DispatchClass::Normal, Pays::No))]
+        pub fn batch_set_weights(
+            origin: OriginFor<T>,
+            weights: Vec<(u16, Vec<u16>, Vec<u16>, u64)>,
+        ) -> DispatchResultWithPostInfo {
+            Self::do_batch_set_weights(origin, weights)
+        }
+
+        /// ---- Used to commit hashes of your weight values on several subnets at once.
+        ///
+        /// # Args:
+        /// * `origin`: (`<T as frame_system::Config>::RuntimeOrigin`):
+        ///   - The signature of the committing hotkey.
+        ///
+        /// * `commits` (`Vec<(u16, H256)>`):
+        ///   - The (netuid, commit_hash) entries, as passed to commit_weights.
+        ///
+        /// # Event:
+        /// * `BatchWeightsCommitCompleted`:
+        ///   - With the result of every entry, by netuid.
+        ///
+        /// # Raises:
+        /// * `InvalidWeightsBatchSize`:
+        ///   - The batch is empty or has more than `MAX_WEIGHTS_BATCH_SIZE` entries.
+        ///
+        #[pallet::call_index(83)]
+        #[pallet::weight((Weight::from_parts(46_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(1))
+		.saturating_add(T::DbWeight::get().writes(1))
+		.saturating_mul(commits.len().min(MAX_WEIGHTS_BATCH_SIZE as usize) as u64), DispatchClass::Normal, Pays::No))]
+        pub fn batch_commit_weights(
+            origin: T::RuntimeOrigin,
+            commits: Vec<(u16, H256)>,
+        ) -> DispatchResultWithPostInfo {
+            Self::do_batch_commit_weights(origin, commits)
+        }
+
+        /// ---- Used to reveal the weights for previously committed hashes on several subnets at once.
+        ///
+        /// # Args:
+        /// * `origin`: (`<T as frame_system::Config>::RuntimeOrigin`):
+        ///   - The signature of the revealing hotkey.
+        ///
+        /// * `reveals` (`Vec<(u16, Vec<u16>, Vec<u16>, Vec<u16>, u64)>`):
+        ///   - The (netuid, uids, values, salt, version_key) entries, as passed to reveal_weights.
+        ///
+        /// # Event:
+        /// * `BatchWeightsRevealCompleted`:
+        ///   - With the result of every entry, by netuid.
+        ///
+        /// # Raises:
+        /// * `InvalidWeightsBatchSize`:
+        ///   - The batch is empty or has more than `MAX_WEIGHTS_BATCH_SIZE` entries.
+        ///
+        #[pallet::call_index(84)]
+        #[pallet::weight((Weight::from_parts(103_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(11))
+		.saturating_add(T::DbWeight::get().writes(3))
+		.saturating_mul(reveals.len().min(MAX_WEIGHTS_BATCH_SIZE as usize) as u64), DispatchClass::Normal, Pays::No))]
+        pub fn batch_reveal_weights(
+            origin: T::RuntimeOrigin,
+            reveals: Vec<(u16, Vec<u16>, Vec<u16>, Vec<u16>, u64)>,
+        ) -> DispatchResultWithPostInfo {
+            Self::do_batch_reveal_weights(origin, reveals)
+        }
+
+        /// --- Updates individual entries of the caller weights on a subnetwork, without resending
+        /// the whole row. The updated row goes through the same checks as set_weights.
+        ///
+        /// # Args:
+        /// * `origin`: (<T as frame_system::Config>Origin):
+        /// 	- The caller, a hotkey who wishes to update their weights.
+        ///
+        /// * `netuid` (u16):
+        /// 	- The network uid we are setting these weights on.
+        ///
+        /// * `changes` (Vec<(u16, u16)>):
+        /// 	- The (dest, weight) entries to upsert, a zero weight removes the dest.
+        /// 		Weights are on the scale of the stored row, where the largest is u16::MAX.
+        ///
+        /// * 'version_key' ( u64 ):
+        /// 	- The network version key to check if the validator is up to date.
+        ///
+        /// # Event:
+        /// * WeightsSet;
+        /// 	- On successfully updating the weights on chain.
+        ///
+        /// The weight is charged for a stored row of 4096 uids plus the changes, and refunded
+        /// down to the length of the stored row.
+        ///
+        #[pallet::call_index(85)]
+        #[pallet::weight((Weight::from_parts(30_000_000, 0)
+		.saturating_add(Weight::from_parts(5_400_000, 0).saturating_mul((changes.len() as u64).saturating_add(4096)))
+		.saturating_add(T::DbWeight::get().reads(12))
+		.saturating_add(T::DbWeight::get().reads(changes.len() as u64))
+		.saturating_add(T::DbWeight::get().writes(2)), DispatchClass::Normal, Pays::No))]
+        pub fn set_weights_delta(
+            origin: OriginFor<T>,
+            netuid: u16,
+            changes: Vec<(u16, u16)>,
+            version_key: u64,
+        ) -> DispatchResultWithPostInfo {
+            Self::do_set_weights_delta(origin, netuid, changes, version_key)
+        }
+
         /// Serves or updates axon /promethteus information for the neuron associated with the caller. If the caller is
         /// already registered the metadata is updated. If the caller is not registered this call throws NotRegistered.
         ///
@@ -2262,6 +3161,16 @@ pub mod pallet {
             0
         }
 
+        /// Returns the transaction priority for setting weights on several subnets, which is
+        /// the lowest priority among the subnets so a batch never outranks its freshest entry.
+        pub fn get_priority_batch_set_weights(hotkey: &T::AccountId, netuids: &[u16]) -> u64 {
+            netuids
+                .iter()
+                .map(|netuid| Self::get_priority_set_weights(hotkey, *netuid))
+                .min()
+                .unwrap_or(0)
+        }
+
         /// Is the caller allowed to set weights
         pub fn check_weights_min_stake(hotkey: &T::AccountId) -> bool {
             // Blacklist weights transactions for low stake peers.
@@ -2352,6 +3261,10 @@ where
         Pallet::<T>::get_priority_set_weights(who, netuid)
     }
 
+    pub fn get_priority_batch_set_weights(who: &T::AccountId, netuids: &[u16]) -> u64 {
+        Pallet::<T>::get_priority_batch_set_weights(who, netuids)
+    }
+
     pub fn check_weights_min_stake(who: &T::AccountId) -> bool {
         Pallet::<T>::check_weights_min_stake(who)
     }
@@ -2426,7 +3339,7 @@ where
                     Err(InvalidTransaction::Call.into())
                 }
             }
-            Some(Call::set_weights { netuid, .. }) => {
+            Some(Call::set_weights { netuid, .. } | Call::set_weights_delta { netuid, .. }) => {
                 if Self::check_weights_min_stake(who) {
                     let priority: u64 = Self::get_priority_set_weights(who, *netuid);
                     Ok(ValidTransaction {
@@ -2438,6 +3351,54 @@ where
                     Err(InvalidTransaction::Call.into())
                 }
             }
+            Some(Call::batch_set_weights { weights }) => {
+                if !weights.is_empty()
+                    && weights.len() <= MAX_WEIGHTS_BATCH_SIZE as usize
+                    && Self::check_weights_min_stake(who)
+                {
+                    let netuids: Vec<u16> = weights.iter().map(|(netuid, ..)| *netuid).collect();
+                    let priority: u64 = Self::get_priority_batch_set_weights(who, &netuids);
+                    Ok(ValidTransaction {
+                        priority,
+                        longevity: 1,
+                        ..Default::default()
+                    })
+                } else {
+                    Err(InvalidTransaction::Call.into())
+                }
+            }
+            Some(Call::batch_commit_weights { commits }) => {
+                if !commits.is_empty()
+                    && commits.len() <= MAX_WEIGHTS_BATCH_SIZE as usize
+                    && Self::check_weights_min_stake(who)
+                {
+                    let netuids: Vec<u16> = commits.iter().map(|(netuid, _)| *netuid).collect();
+                    let priority: u64 = Self::get_priority_batch_set_weights(who, &netuids);
+                    Ok(ValidTransaction {
+                        priority,
+                        longevity: 1,
+                        ..Default::default()
+                    })
+                } else {
+                    Err(InvalidTransaction::Call.into())
+                }
+            }
+            Some(Call::batch_reveal_weights { reveals }) => {
+                if !reveals.is_empty()
+                    && reveals.len() <= MAX_WEIGHTS_BATCH_SIZE as usize
+                    && Self::check_weights_min_stake(who)
+                {
+                    let netuids: Vec<u16> = reveals.iter().map(|(netuid, ..)| *netuid).collect();
+                    let priority: u64 = Self::get_priority_batch_set_weights(who, &netuids);
+                    Ok(ValidTransaction {
+                        priority,
+                        longevity: 1,
+                        ..Default::default()
+                    })
+                } else {
+                    Err(InvalidTransaction::Call.into())
+                }
+            }
             Some(Call::set_root_weights { netuid, hotkey, .. }) => {
                 if Self::check_weights_min_stake(hotkey) {
                     let priority: u64 = Self::get_priority_set_weights(hotkey, *netuid);
@@ -2511,7 +3472,11 @@ where
                 let transaction_fee = 0;
                 Ok((CallType::RemoveStake, transaction_fee, who.clone()))
             }
-            Some(Call::set_weights { .. }) => {
+            Some(Call::set_weights { .. } | Call::set_weights_delta { .. }) => {
+                let transaction_fee = 0;
+                Ok((CallType::SetWeights, transaction_fee, who.clone()))
+            }
+            Some(Call::batch_set_weights { .. }) => {
                 let transaction_fee = 0;
                 Ok((CallType::SetWeights, transaction_fee, who.clone()))
             }
@@ -2523,6 +3488,14 @@ where
                 let transaction_fee = 0;
                 Ok((CallType::SetWeights, transaction_fee, who.clone()))
             }
+            Some(Call::batch_commit_weights { .. }) => {
+                let transaction_fee = 0;
+                Ok((CallType::SetWeights, transaction_fee, who.clone()))
+            }
+            Some(Call::batch_reveal_weights { .. }) => {
+                let transaction_fee = 0;
+                Ok((CallType::SetWeights, transaction_fee, who.clone()))
+            }
             Some(Call::register { .. }) => {
                 let transaction_fee = 0;
                 Ok((CallType::Register, transaction_fee, who.clone()))
@@ -7,7 +7,7 @@ use sp_runtime::traits::{CheckedAdd, Saturating};
 use sp_std::cmp::Ordering;
 
 use sp_std::vec;
-use substrate_fixed::transcendental::{exp, ln};
+use substrate_fixed::transcendental::{exp, ln, sqrt};
 use substrate_fixed::types::{I32F32, I64F64};
 
 // TODO: figure out what cfg gate this needs to not be a warning in rustc
@@ -357,6 +357,53 @@ pub fn row_sum_sparse(sparse_matrix: &[Vec<(u16, I32F32)>]) -> Vec<I32F32> {
         .collect()
 }
 
+// Cosine similarity of each row of a sparse matrix to a vector, 0 for an empty row or zero vector.
+#[allow(dead_code, clippy::indexing_slicing)]
+pub fn row_cosine_similarity_sparse(
+    sparse_matrix: &[Vec<(u16, I32F32)>],
+    vector: &[I32F32],
+) -> Vec<I32F32> {
+    let zero: I64F64 = I64F64::from_num(0);
+    let vector_norm_sq: I64F64 = vector.iter().fold(zero, |acc, v| {
+        let v = I64F64::from_num(*v);
+        acc.saturating_add(v.saturating_mul(v))
+    });
+    sparse_matrix
+        .iter()
+        .map(|row| {
+            let mut dot: I64F64 = zero;
+            let mut row_norm_sq: I64F64 = zero;
+            for (j, value) in row.iter() {
+                let value = I64F64::from_num(*value);
+                dot =
+                    dot.saturating_add(value.saturating_mul(I64F64::from_num(vector[*j as usize])));
+                row_norm_sq = row_norm_sq.saturating_add(value.saturating_mul(value));
+            }
+            let norm: I64F64 =
+                sqrt::<I64F64, I64F64>(row_norm_sq.saturating_mul(vector_norm_sq)).unwrap_or(zero);
+            let similarity: I64F64 = dot.checked_div(norm).unwrap_or(zero);
+            I32F32::from_num(similarity.min(I64F64::from_num(1)))
+        })
+        .collect()
+}
+
+// Marks the non-empty rows of a sparse matrix that are identical to at least one other row.
+#[allow(dead_code, clippy::indexing_slicing)]
+pub fn duplicate_rows_sparse(sparse_matrix: &[Vec<(u16, I32F32)>]) -> Vec<bool> {
+    let mut duplicate: Vec<bool> = vec![false; sparse_matrix.len()];
+    let mut rows: Vec<usize> = (0..sparse_matrix.len())
+        .filter(|i| !sparse_matrix[*i].is_empty())
+        .collect();
+    rows.sort_by(|a, b| sparse_matrix[*a].cmp(&sparse_matrix[*b]));
+    for pair in rows.windows(2) {
+        if sparse_matrix[pair[0]] == sparse_matrix[pair[1]] {
+            duplicate[pair[0]] = true;
+            duplicate[pair[1]] = true;
+        }
+    }
+    duplicate
+}
+
 // Sum across each column (dim=1) of a matrix.
 #[allow(dead_code)]
 pub fn col_sum(x: &[Vec<I32F32>]) -> Vec<I32F32> {
@@ -1222,6 +1269,37 @@ pub fn mat_ema_alpha_vec_sparse(
     result
 }
 
+/// Calculates the exponential moving average (EMA) of a single sparse row over `columns` columns
+/// using dynamic alpha values, `alpha_j * new_j + one_minus_alpha_j * old_j`.
+#[allow(dead_code)]
+pub fn row_ema_alpha_vec_sparse(
+    new: &[(u16, I32F32)],
+    old: &[(u16, I32F32)],
+    alpha: &[I32F32],
+    columns: u16,
+) -> Vec<(u16, I32F32)> {
+    let zero: I32F32 = I32F32::from_num(0.0);
+    let mut row: Vec<I32F32> = vec![zero; columns as usize];
+    for (j, value) in new.iter() {
+        let alpha_val: I32F32 = alpha.get(*j as usize).copied().unwrap_or(zero);
+        if let Some(row_val) = row.get_mut(*j as usize) {
+            *row_val = alpha_val.saturating_mul(*value);
+        }
+    }
+    for (j, value) in old.iter() {
+        let alpha_val: I32F32 = alpha.get(*j as usize).copied().unwrap_or(zero);
+        let one_minus_alpha: I32F32 = I32F32::from_num(1.0).saturating_sub(alpha_val);
+        if let Some(row_val) = row.get_mut(*j as usize) {
+            *row_val = row_val.saturating_add(one_minus_alpha.saturating_mul(*value));
+        }
+    }
+    row.into_iter()
+        .enumerate()
+        .filter(|(_, value)| *value > zero)
+        .map(|(j, value)| (j as u16, value))
+        .collect()
+}
+
 /// Return matrix exponential moving average: `alpha_j * a_ij + one_minus_alpha_j * b_ij`.
 /// `alpha_` is the EMA coefficient passed as a vector per column.
 #[allow(dead_code)]
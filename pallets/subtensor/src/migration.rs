@@ -7,7 +7,9 @@ use frame_support::{
     weights::Weight,
 };
 use log::info;
+use sp_core::H256;
 use sp_runtime::Saturating;
+use sp_std::collections::vec_deque::VecDeque;
 use sp_std::vec::Vec;
 
 // TODO (camfairchild): TEST MIGRATION
@@ -603,3 +605,115 @@ pub fn migrate_populate_staking_hotkeys<T: Config>() -> Weight {
         Weight::zero()
     }
 }
+
+/// Storage version v6 -> v7: introduces the amount based stake rate limiter.
+///
+/// The count based limiter stays selected and the stake amount window starts out with the
+/// length of the current stake interval, so both limiters measure the same period.
+pub fn migrate_to_v7_stake_amount_rate_limit<T: Config>() -> Weight {
+    let new_storage_version = 7;
+    let migration_name = "Initialize amount based stake rate limiting";
+    let mut weight = T::DbWeight::get().reads(1);
+
+    let onchain_version = Pallet::<T>::on_chain_storage_version();
+    if onchain_version < new_storage_version {
+        info!(target: LOG_TARGET_1, ">>> Starting Migration: {}", migration_name);
+
+        StakeRateLimitSelector::<T>::put(StakeRateLimitMode::Count);
+        StakeAmountWindow::<T>::put(StakeInterval::<T>::get());
+        weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 2));
+
+        StorageVersion::new(new_storage_version).put::<Pallet<T>>();
+        weight.saturating_accrue(T::DbWeight::get().writes(1));
+
+        info!(target: LOG_TARGET_1, "Migration {} finished.", migration_name);
+        weight
+    } else {
+        info!(target: LOG_TARGET_1, "Migration {} already done!", migration_name);
+        weight
+    }
+}
+
+/// Storage version v7 -> v8: weight commits become a queue of pending commits.
+///
+/// Every existing (hash, commit_block) commit is kept as the single entry of its queue.
+pub fn migrate_to_v8_weight_commits_queue<T: Config>() -> Weight {
+    let new_storage_version = 8;
+    let migration_name = "Migrate weight commits to a queue";
+    let mut weight = T::DbWeight::get().reads(1);
+
+    let onchain_version = Pallet::<T>::on_chain_storage_version();
+    if onchain_version < new_storage_version {
+        info!(target: LOG_TARGET_1, ">>> Starting Migration: {}", migration_name);
+
+        let mut translated: u64 = 0;
+        WeightCommits::<T>::translate::<(H256, u64), _>(|_netuid, _hotkey, commit| {
+            translated = translated.saturating_add(1);
+            let mut commits = VecDeque::new();
+            commits.push_back(commit);
+            Some(commits)
+        });
+        weight.saturating_accrue(T::DbWeight::get().reads_writes(translated, translated));
+
+        StorageVersion::new(new_storage_version).put::<Pallet<T>>();
+        weight.saturating_accrue(T::DbWeight::get().writes(1));
+
+        info!(
+            target: LOG_TARGET_1,
+            "Migration {} finished. Translated {} commits.", migration_name, translated
+        );
+        weight
+    } else {
+        info!(target: LOG_TARGET_1, "Migration {} already done!", migration_name);
+        weight
+    }
+}
+
+/// Storage version v8 -> v9: counts the coldkeys staking to each hotkey and the coldkeys on
+/// each nomination allowlist, so nominations are checked without iterating the stakes.
+pub fn migrate_to_v9_delegate_nominator_count<T: Config>() -> Weight {
+    let new_storage_version = 9;
+    let migration_name = "Populate delegate nominator counts";
+    let mut weight = T::DbWeight::get().reads(1);
+
+    let onchain_version = Pallet::<T>::on_chain_storage_version();
+    if onchain_version < new_storage_version {
+        info!(target: LOG_TARGET_1, ">>> Starting Migration: {}", migration_name);
+
+        let _ = DelegateNominatorCount::<T>::clear(u32::MAX, None);
+        let mut stakes: u64 = 0;
+        for (hotkey, _coldkey, stake) in Stake::<T>::iter() {
+            stakes = stakes.saturating_add(1);
+            if stake > 0 {
+                DelegateNominatorCount::<T>::mutate(&hotkey, |count| {
+                    *count = count.saturating_add(1)
+                });
+                weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+            }
+        }
+        weight.saturating_accrue(T::DbWeight::get().reads(stakes));
+
+        let _ = NominationAllowlistLen::<T>::clear(u32::MAX, None);
+        let mut listed: u64 = 0;
+        for (hotkey, _nominator, allowed) in NominationAllowlist::<T>::iter() {
+            listed = listed.saturating_add(1);
+            if allowed {
+                NominationAllowlistLen::<T>::mutate(&hotkey, |len| *len = len.saturating_add(1));
+                weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+            }
+        }
+        weight.saturating_accrue(T::DbWeight::get().reads(listed));
+
+        StorageVersion::new(new_storage_version).put::<Pallet<T>>();
+        weight.saturating_accrue(T::DbWeight::get().writes(1));
+
+        info!(
+            target: LOG_TARGET_1,
+            "Migration {} finished. Counted {} stakes.", migration_name, stakes
+        );
+        weight
+    } else {
+        info!(target: LOG_TARGET_1, "Migration {} already done!", migration_name);
+        weight
+    }
+}
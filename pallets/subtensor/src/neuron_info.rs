@@ -3,6 +3,7 @@ use frame_support::pallet_prelude::{Decode, Encode};
 use frame_support::storage::IterableStorageDoubleMap;
 extern crate alloc;
 use codec::Compact;
+use sp_core::hexdisplay::AsBytesRef;
 
 #[freeze_struct("45e69321f5c74b4b")]
 #[derive(Decode, Encode, PartialEq, Eq, Clone, Debug)]
@@ -53,6 +54,33 @@ pub struct NeuronInfoLite<T: Config> {
     pruning_score: Compact<u16>,
 }
 
+#[freeze_struct("e94b6974982cc096")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug)]
+pub struct ValidatorAgreementInfo<T: Config> {
+    hotkey: T::AccountId,
+    uid: Compact<u16>,
+    consensus_similarity: Compact<u16>,
+    clipped_weight_share: Compact<u16>,
+    identical_weight_epochs: Compact<u32>,
+}
+
+#[freeze_struct("755fc63b6f573084")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug)]
+pub struct EpochHistoryInfo {
+    block: Compact<u64>,
+    emission_total: Compact<u64>,
+    incentive: Compact<u16>,
+    dividends: Compact<u16>,
+    emission: Compact<u64>,
+}
+
+#[freeze_struct("7283aa14a96b525e")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug)]
+pub struct WeightsCheckInfo {
+    failures: Vec<DispatchError>,
+    weights: Vec<(Compact<u16>, Compact<u16>)>, // Vec of (uid, weight)
+}
+
 impl<T: Config> Pallet<T> {
     pub fn get_neurons(netuid: u16) -> Vec<NeuronInfo<T>> {
         if !Self::if_subnet_exist(netuid) {
@@ -239,4 +267,89 @@ impl<T: Config> Pallet<T> {
 
         Self::get_neuron_lite_subnet_exists(netuid, uid)
     }
+
+    /// Returns the agreement metrics of the validators on the subnet, as of the last epoch.
+    pub fn get_validator_agreement(netuid: u16) -> Vec<ValidatorAgreementInfo<T>> {
+        if !Self::if_subnet_exist(netuid) {
+            return Vec::new();
+        }
+
+        let mut agreement: Vec<(u16, (u16, u16, u32))> =
+            ValidatorAgreement::<T>::iter_prefix(netuid).collect();
+        agreement.sort_by_key(|(uid, _)| *uid);
+        agreement
+            .into_iter()
+            .filter_map(|(uid, (similarity, clipped_share, identical_epochs))| {
+                let hotkey = Self::get_hotkey_for_net_and_uid(netuid, uid).ok()?;
+                Some(ValidatorAgreementInfo {
+                    hotkey,
+                    uid: uid.into(),
+                    consensus_similarity: similarity.into(),
+                    clipped_weight_share: clipped_share.into(),
+                    identical_weight_epochs: identical_epochs.into(),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the uid's terms from up to `count` of the most recent epoch summaries, newest first.
+    pub fn get_epoch_history(netuid: u16, uid: u16, count: u16) -> Vec<EpochHistoryInfo> {
+        let depth: u64 = Self::get_epoch_history_depth(netuid) as u64;
+        let index: u64 = EpochHistoryIndex::<T>::get(netuid);
+        let recorded: u64 = index.min(depth).min(count as u64);
+
+        (1..=recorded)
+            .filter_map(|back| {
+                let slot: u64 = index.saturating_sub(back).checked_rem(depth)?;
+                let summary = EpochHistory::<T>::get(netuid, slot as u16)?;
+                Some(EpochHistoryInfo {
+                    block: summary.block.into(),
+                    emission_total: summary.emission_total.into(),
+                    incentive: summary
+                        .incentive
+                        .get(uid as usize)
+                        .copied()
+                        .unwrap_or(0)
+                        .into(),
+                    dividends: summary
+                        .dividends
+                        .get(uid as usize)
+                        .copied()
+                        .unwrap_or(0)
+                        .into(),
+                    emission: summary
+                        .emission
+                        .get(uid as usize)
+                        .copied()
+                        .unwrap_or(0)
+                        .into(),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the failing set_weights checks for the hotkey and the weights that would be stored.
+    pub fn get_weights_check(
+        netuid: u16,
+        hotkey_account_vec: Vec<u8>,
+        uids: Vec<u16>,
+        values: Vec<u16>,
+        version_key: u64,
+    ) -> Option<WeightsCheckInfo> {
+        if hotkey_account_vec.len() != 32 {
+            return None;
+        }
+
+        let hotkey: T::AccountId =
+            T::AccountId::decode(&mut hotkey_account_vec.as_bytes_ref()).ok()?;
+        let (failures, weights) = Self::check_weights(netuid, &hotkey, &uids, &values, version_key);
+
+        Some(WeightsCheckInfo {
+            failures,
+            weights: weights
+                .into_iter()
+                .map(|(uid, weight)| (uid.into(), weight.into()))
+                .collect(),
+        })
+    }
 }
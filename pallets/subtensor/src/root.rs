@@ -686,10 +686,7 @@ impl<T: Config> Pallet<T> {
         );
 
         // Ensure version_key is up-to-date.
-        ensure!(
-            Self::check_version_key(netuid, version_key),
-            Error::<T>::IncorrectWeightVersionKey
-        );
+        Self::check_version_key(netuid, version_key)?;
 
         // Get the neuron uid of associated hotkey on network netuid.
         let neuron_uid = Self::get_uid_for_net_and_hotkey(netuid, &hotkey)?;
@@ -1045,6 +1042,10 @@ impl<T: Config> Pallet<T> {
         let _ = Uids::<T>::clear_prefix(netuid, u32::MAX, None);
         let _ = Keys::<T>::clear_prefix(netuid, u32::MAX, None);
         let _ = Bonds::<T>::clear_prefix(netuid, u32::MAX, None);
+        let _ = ValidatorAgreement::<T>::clear_prefix(netuid, u32::MAX, None);
+        let _ = EpochHistory::<T>::clear_prefix(netuid, u32::MAX, None);
+        EpochHistoryIndex::<T>::remove(netuid);
+        let _ = Self::clear_staged_epoch(netuid);
 
         // --- 8. Removes the weights for this subnet (do not remove).
         let _ = Weights::<T>::clear_prefix(netuid, u32::MAX, None);
@@ -1093,6 +1094,12 @@ impl<T: Config> Pallet<T> {
         RegistrationsThisInterval::<T>::remove(netuid);
         POWRegistrationsThisInterval::<T>::remove(netuid);
         BurnRegistrationsThisInterval::<T>::remove(netuid);
+        NetworkUnbondingPeriod::<T>::remove(netuid);
+        EpochHistoryDepth::<T>::remove(netuid);
+        SubnetConsensusMechanism::<T>::remove(netuid);
+        WeightsMaxVersionKey::<T>::remove(netuid);
+        BannedWeightsVersionKeys::<T>::remove(netuid);
+        WeightsWindow::<T>::remove(netuid);
 
         // --- 12. Add the balance back to the owner.
         Self::add_balance_to_coldkey_account(&owner_coldkey, reserved_amount);
@@ -0,0 +1,1151 @@
+use super::*;
+use crate::epoch_mechanism::EpochOutputs;
+use crate::math::*;
+use frame_support::weights::Weight;
+use sp_core::{Get, H256};
+use sp_runtime::traits::{BlakeTwo256, Hash};
+use sp_std::vec;
+use substrate_fixed::types::{I32F32, I64F64};
+
+impl<T: Config> Pallet<T> {
+    /// Advances the epoch of `netuid` due at `epoch_block` by as many chunks as fit in `budget`.
+    /// A stage left over from another epoch block, as after a tempo change, is dropped first.
+    /// Returns the weight used.
+    ///
+    pub fn advance_staged_epoch(netuid: u16, epoch_block: u64, budget: Weight) -> Weight {
+        let (cursor, weight) = Self::run_staged_epoch(netuid, epoch_block, budget);
+        StagedEpoch::<T>::insert(netuid, cursor);
+        weight.saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Completes the staged epoch of `netuid` once its cursor is done, at or after tempo. The
+    /// emission gathered with the stage is drained from the pending emission and loaded as the
+    /// tuples ( hotkey, server_emission, validator_emission ) of the hotkeys the epoch was
+    /// gathered with. Uids replaced since then are not paid, their emission is left pending for
+    /// the next epoch. A cursor not yet done is left for the next blocks. Returns the weight used.
+    ///
+    pub fn complete_staged_epoch(netuid: u16, block_number: u64) -> Weight {
+        let mut weight: Weight = T::DbWeight::get().reads(1);
+        if let Some(cursor) =
+            StagedEpoch::<T>::get(netuid).filter(|cursor| cursor.phase == StagedEpochPhase::Done)
+        {
+            let neurons: Vec<StagedNeuron> = StagedEpochNeurons::<T>::get(netuid);
+            let hotkeys: Vec<(u16, T::AccountId)> = StagedEpochHotkeys::<T>::get(netuid);
+            // The staged neurons and hotkeys, and the registration of each hotkey.
+            weight.saturating_accrue(
+                T::DbWeight::get()
+                    .reads((hotkeys.len() as u64).saturating_add(2))
+                    .saturating_add(Self::staged_entries_weight(
+                        neurons.len().saturating_add(hotkeys.len()),
+                    )),
+            );
+            let mut unpaid: u64 = 0;
+            let emission_tuples: Vec<(T::AccountId, u64, u64)> = hotkeys
+                .into_iter()
+                .filter_map(|(uid, hotkey)| {
+                    let neuron: StagedNeuron =
+                        neurons.get(uid as usize).cloned().unwrap_or_default();
+                    if neuron.replaced
+                        || Self::get_neuron_block_at_registration(netuid, uid) != neuron.registered
+                    {
+                        unpaid = unpaid
+                            .saturating_add(neuron.server_emission)
+                            .saturating_add(neuron.validator_emission);
+                        return None;
+                    }
+                    Some((hotkey, neuron.server_emission, neuron.validator_emission))
+                })
+                .collect();
+            weight.saturating_accrue(Self::clear_staged_epoch(netuid));
+            Self::deposit_event(Event::StagedEpochCompleted(netuid, cursor.start_block));
+            weight.saturating_accrue(Self::load_staged_epoch_emission(
+                netuid,
+                block_number,
+                emission_tuples,
+                cursor.rao_emission.saturating_sub(unpaid),
+            ));
+        }
+        weight
+    }
+
+    /// Drains `emission_to_drain` from the pending emission of `netuid` and sinks the emission
+    /// tuples of its epoch onto those already loaded, then resets the step counters at
+    /// `block_number`. Emission queued since the epoch was staged is left for the next epoch.
+    /// Returns the weight used.
+    fn load_staged_epoch_emission(
+        netuid: u16,
+        block_number: u64,
+        emission_tuples: Vec<(T::AccountId, u64, u64)>,
+        emission_to_drain: u64,
+    ) -> Weight {
+        PendingEmission::<T>::mutate(netuid, |queued| {
+            *queued = queued.saturating_sub(emission_to_drain)
+        });
+
+        log::debug!(
+            "netuid_i: {:?} emission_to_drain: {:?} ",
+            netuid,
+            emission_to_drain
+        );
+
+        // Check that the emission does not exceed the allowed total.
+        let emission_sum: u128 = emission_tuples
+            .iter()
+            .map(|(_account_id, ve, se)| (*ve as u128).saturating_add(*se as u128))
+            .sum();
+        if emission_sum > emission_to_drain as u128 {
+            return T::DbWeight::get().reads_writes(1, 1);
+        } // Saftey check.
+
+        // Sink the emission tuples onto the already loaded.
+        let mut concat_emission_tuples: Vec<(T::AccountId, u64, u64)> = emission_tuples;
+        if let Some(mut current_emission_tuples) = Self::get_loaded_emission_tuples(netuid) {
+            // We already have loaded emission tuples, so we concat the new ones.
+            concat_emission_tuples.append(&mut current_emission_tuples);
+        }
+        let entries: usize = concat_emission_tuples.len();
+        LoadedEmission::<T>::insert(netuid, concat_emission_tuples);
+
+        // Set counters.
+        Self::set_blocks_since_last_step(netuid, 0);
+        Self::set_last_mechanism_step_block(netuid, block_number);
+        T::DbWeight::get()
+            .reads_writes(2, 4)
+            .saturating_add(Self::staged_entries_weight(entries))
+    }
+
+    /// Removes the staged epoch of `netuid`. Returns the weight used.
+    ///
+    pub fn clear_staged_epoch(netuid: u16) -> Weight {
+        StagedEpoch::<T>::remove(netuid);
+        StagedEpochNeurons::<T>::remove(netuid);
+        StagedEpochHotkeys::<T>::remove(netuid);
+        StagedEpochWeightDigests::<T>::remove(netuid);
+        let terms = StagedEpochTerms::<T>::clear_prefix(netuid, u32::MAX, None);
+        let weights = StagedEpochWeights::<T>::clear_prefix(netuid, u32::MAX, None);
+        let bonds = StagedEpochBonds::<T>::clear_prefix(netuid, u32::MAX, None);
+        T::DbWeight::get().writes(
+            4_u64
+                .saturating_add(terms.unique.into())
+                .saturating_add(weights.unique.into())
+                .saturating_add(bonds.unique.into()),
+        )
+    }
+
+    /// Runs chunks of the epoch of `netuid` due at `epoch_block` while `budget` lasts, starting
+    /// the stage if needed. Returns the cursor and the weight used.
+    fn run_staged_epoch(
+        netuid: u16,
+        epoch_block: u64,
+        budget: Weight,
+    ) -> (StagedEpochCursor, Weight) {
+        let mut weight: Weight = T::DbWeight::get().reads(1);
+        let mut cursor: StagedEpochCursor = match StagedEpoch::<T>::get(netuid) {
+            Some(cursor) if cursor.epoch_block == epoch_block => cursor,
+            stale => {
+                if stale.is_some() {
+                    weight.saturating_accrue(Self::clear_staged_epoch(netuid));
+                }
+                weight.saturating_accrue(T::DbWeight::get().reads(3));
+                StagedEpochCursor {
+                    mechanism: Self::get_consensus_mechanism(netuid),
+                    phase: StagedEpochPhase::Gather,
+                    next: 0,
+                    n: Self::get_subnetwork_n(netuid),
+                    epoch_block,
+                    start_block: Self::get_current_block_as_u64(),
+                    rao_emission: PendingEmission::<T>::get(netuid),
+                }
+            }
+        };
+        while cursor.phase != StagedEpochPhase::Done && weight.ref_time() < budget.ref_time() {
+            let chunk_budget: Weight = budget.saturating_sub(weight);
+            let chunk_weight: Weight = match cursor.phase {
+                StagedEpochPhase::Gather => {
+                    Self::gather_staged_epoch(netuid, &mut cursor, chunk_budget)
+                }
+                StagedEpochPhase::Weights => {
+                    Self::mask_staged_epoch_weights(netuid, &mut cursor, chunk_budget)
+                }
+                StagedEpochPhase::Consensus => {
+                    Self::compute_staged_epoch_consensus(netuid, &mut cursor, chunk_budget)
+                }
+                StagedEpochPhase::Clip => {
+                    Self::clip_staged_epoch_weights(netuid, &mut cursor, chunk_budget)
+                }
+                StagedEpochPhase::Bonds => {
+                    Self::compute_staged_epoch_bonds(netuid, &mut cursor, chunk_budget)
+                }
+                StagedEpochPhase::Dividends => {
+                    Self::compute_staged_epoch_dividends(netuid, &mut cursor, chunk_budget)
+                }
+                StagedEpochPhase::Write => {
+                    Self::write_staged_epoch(netuid, &mut cursor, chunk_budget)
+                }
+                StagedEpochPhase::Done => Weight::zero(),
+            };
+            weight.saturating_accrue(chunk_weight);
+        }
+        (cursor, weight)
+    }
+
+    /// Gathers the neuron state, weights and bonds of a range of uids. Once every uid is
+    /// gathered, normalizes stake and finds identical weights.
+    fn gather_staged_epoch(netuid: u16, cursor: &mut StagedEpochCursor, budget: Weight) -> Weight {
+        let n: u16 = cursor.n;
+        let segments: u16 = Self::get_staged_epoch_segments(n);
+        let last_update: Vec<u64> = Self::get_last_update(netuid);
+        let validator_permits: Vec<bool> = Self::get_validator_permit(netuid);
+        let mut neurons: Vec<StagedNeuron> = StagedEpochNeurons::<T>::get(netuid);
+        let mut hotkeys: Vec<(u16, T::AccountId)> = StagedEpochHotkeys::<T>::get(netuid);
+        let mut digests: Vec<(u16, H256)> = StagedEpochWeightDigests::<T>::get(netuid);
+        let mut weight: Weight =
+            T::DbWeight::get()
+                .reads(5)
+                .saturating_add(Self::staged_entries_weight(
+                    last_update
+                        .len()
+                        .saturating_add(validator_permits.len())
+                        .saturating_add(neurons.len())
+                        .saturating_add(hotkeys.len())
+                        .saturating_add(digests.len()),
+                ));
+
+        let start: u16 = cursor.next;
+        while cursor.next < n && (cursor.next == start || weight.ref_time() < budget.ref_time()) {
+            let uid: u16 = cursor.next;
+            let mut neuron = StagedNeuron {
+                last_update: last_update.get(uid as usize).copied().unwrap_or(0),
+                permit: validator_permits
+                    .get(uid as usize)
+                    .copied()
+                    .unwrap_or(false),
+                ..Default::default()
+            };
+            weight.saturating_accrue(T::DbWeight::get().reads(1));
+            if let Ok(hotkey) = Keys::<T>::try_get(netuid, uid) {
+                neuron.stake = Self::get_total_stake_for_hotkey(&hotkey);
+                neuron.registered = Self::get_neuron_block_at_registration(netuid, uid);
+                hotkeys.push((uid, hotkey));
+                weight.saturating_accrue(T::DbWeight::get().reads(2));
+            }
+
+            // Weights of uids without a validator permit are masked.
+            if neuron.permit {
+                let row: Vec<(u16, I32F32)> =
+                    Self::get_staged_row(Weights::<T>::get(netuid, uid), n);
+                weight.saturating_accrue(
+                    T::DbWeight::get()
+                        .reads(1)
+                        .saturating_add(Self::staged_entries_weight(row.len())),
+                );
+                if !row.is_empty() {
+                    digests.push((uid, BlakeTwo256::hash_of(&row)));
+                    neuron.weighted = true;
+                    weight.saturating_accrue(Self::put_staged_weights_row(
+                        netuid, uid, row, segments,
+                    ));
+                }
+            }
+            if cursor.mechanism == ConsensusMechanism::Yuma {
+                let row: Vec<(u16, I32F32)> = Self::get_staged_row(Bonds::<T>::get(netuid, uid), n);
+                weight.saturating_accrue(
+                    T::DbWeight::get()
+                        .reads(1)
+                        .saturating_add(Self::staged_entries_weight(row.len())),
+                );
+                if !row.is_empty() {
+                    neuron.bonded = true;
+                    StagedEpochBonds::<T>::insert(netuid, uid, row);
+                    weight.saturating_accrue(T::DbWeight::get().writes(1));
+                }
+            }
+            neurons.push(neuron);
+            cursor.next = uid.saturating_add(1);
+        }
+
+        if cursor.next >= n {
+            // Find validators with the exact same weights as another validator.
+            digests.sort_by_key(|(_, digest)| *digest);
+            for pair in digests.windows(2) {
+                if let [(uid_a, digest_a), (uid_b, digest_b)] = pair {
+                    if digest_a == digest_b {
+                        for uid in [uid_a, uid_b] {
+                            if let Some(neuron) = neurons.get_mut(*uid as usize) {
+                                neuron.identical = true;
+                            }
+                        }
+                    }
+                }
+            }
+            digests.clear();
+
+            // Normalized stake and active stake of the permitted validators.
+            let mut stake_64: Vec<I64F64> = neurons
+                .iter()
+                .map(|neuron| I64F64::from_num(neuron.stake))
+                .collect();
+            inplace_normalize_64(&mut stake_64);
+            let stake: Vec<I32F32> = vec_fixed64_to_fixed32(stake_64);
+            let activity_cutoff: u64 = Self::get_activity_cutoff(netuid) as u64;
+            for neuron in neurons.iter_mut() {
+                neuron.active =
+                    neuron.last_update.saturating_add(activity_cutoff) >= cursor.start_block;
+            }
+            let mut active_stake: Vec<I32F32> = stake
+                .iter()
+                .zip(&neurons)
+                .map(|(stake, neuron)| {
+                    if neuron.active && neuron.permit {
+                        *stake
+                    } else {
+                        I32F32::from_num(0)
+                    }
+                })
+                .collect();
+            inplace_normalize(&mut active_stake);
+            weight.saturating_accrue(T::DbWeight::get().reads(1));
+            weight.saturating_accrue(Self::set_staged_term(netuid, StagedEpochTerm::Stake, stake));
+            weight.saturating_accrue(Self::set_staged_term(
+                netuid,
+                StagedEpochTerm::ActiveStake,
+                active_stake,
+            ));
+
+            cursor.phase = StagedEpochPhase::Weights;
+            cursor.next = 0;
+        }
+
+        weight.saturating_accrue(
+            T::DbWeight::get()
+                .writes(3)
+                .saturating_add(Self::staged_entries_weight(
+                    neurons
+                        .len()
+                        .saturating_add(hotkeys.len())
+                        .saturating_add(digests.len()),
+                )),
+        );
+        StagedEpochNeurons::<T>::insert(netuid, neurons);
+        StagedEpochHotkeys::<T>::insert(netuid, hotkeys);
+        StagedEpochWeightDigests::<T>::insert(netuid, digests);
+        weight
+    }
+
+    /// Masks self-weights and outdated weights and bonds of a range of uids, row normalizes the
+    /// weights and accumulates preranks and the column sums of the bonds.
+    fn mask_staged_epoch_weights(
+        netuid: u16,
+        cursor: &mut StagedEpochCursor,
+        budget: Weight,
+    ) -> Weight {
+        let n: u16 = cursor.n;
+        let segments: u16 = Self::get_staged_epoch_segments(n);
+        let mut weight: Weight = Weight::zero();
+        let neurons: Vec<StagedNeuron> = Self::get_staged_neurons(netuid, &mut weight);
+        let active_stake: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::ActiveStake, n, &mut weight);
+        let mut preranks: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::Preranks, n, &mut weight);
+        let mut unclipped_weight: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::UnclippedWeight, n, &mut weight);
+        let mut bonds_sum: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::BondsSum, n, &mut weight);
+
+        let start: u16 = cursor.next;
+        while cursor.next < n && (cursor.next == start || weight.ref_time() < budget.ref_time()) {
+            let uid: u16 = cursor.next;
+            cursor.next = uid.saturating_add(1);
+            let Some(neuron) = neurons.get(uid as usize) else {
+                continue;
+            };
+            // Remove weights and bonds referring to neurons registered since the uid last set weights.
+            let updated: u64 = neuron.last_update;
+            let outdated = |uid_j: u16| -> bool {
+                neurons
+                    .get(uid_j as usize)
+                    .map(|neuron_j| updated <= neuron_j.registered)
+                    .unwrap_or(true)
+            };
+
+            if neuron.weighted {
+                let mut row: Vec<(u16, I32F32)> =
+                    Self::get_staged_weights_row(netuid, uid, segments, &mut weight);
+                row.retain(|(uid_j, _)| *uid_j != uid && !outdated(*uid_j));
+                inplace_row_normalize_sparse(sp_std::slice::from_mut(&mut row));
+
+                // Compute preranks: r_j = SUM(i) w_ij * s_i
+                let stake_i: I32F32 = active_stake.get(uid as usize).copied().unwrap_or_default();
+                for (uid_j, value) in row.iter() {
+                    if let Some(rank) = preranks.get_mut(*uid_j as usize) {
+                        *rank = rank.saturating_add(stake_i.saturating_mul(*value));
+                    }
+                }
+                if let Some(unclipped) = unclipped_weight.get_mut(uid as usize) {
+                    *unclipped = row.iter().map(|(_, value)| value).sum();
+                }
+                weight.saturating_accrue(Self::put_staged_weights_row(netuid, uid, row, segments));
+            }
+
+            if neuron.bonded {
+                let mut row: Vec<(u16, I32F32)> = StagedEpochBonds::<T>::get(netuid, uid);
+                row.retain(|(uid_j, _)| !outdated(*uid_j));
+                for (uid_j, value) in row.iter() {
+                    if let Some(sum) = bonds_sum.get_mut(*uid_j as usize) {
+                        *sum = sum.saturating_add(*value);
+                    }
+                }
+                weight.saturating_accrue(
+                    T::DbWeight::get()
+                        .reads_writes(1, 1)
+                        .saturating_add(Self::staged_entries_weight(row.len())),
+                );
+                Self::put_staged_bonds_row(netuid, uid, row);
+            }
+        }
+
+        if cursor.next >= n {
+            if cursor.mechanism == ConsensusMechanism::Yuma {
+                cursor.phase = StagedEpochPhase::Consensus;
+            } else {
+                // The stake-weighted mean is consensus itself.
+                weight.saturating_accrue(Self::set_staged_term(
+                    netuid,
+                    StagedEpochTerm::Consensus,
+                    preranks.clone(),
+                ));
+                cursor.phase = StagedEpochPhase::Clip;
+            }
+            cursor.next = 0;
+        }
+
+        weight.saturating_accrue(Self::set_staged_term(
+            netuid,
+            StagedEpochTerm::Preranks,
+            preranks,
+        ));
+        weight.saturating_accrue(Self::set_staged_term(
+            netuid,
+            StagedEpochTerm::UnclippedWeight,
+            unclipped_weight,
+        ));
+        weight.saturating_accrue(Self::set_staged_term(
+            netuid,
+            StagedEpochTerm::BondsSum,
+            bonds_sum,
+        ));
+        weight
+    }
+
+    /// Computes consensus, the stake-weighted median of each column, over a range of weights
+    /// segments.
+    fn compute_staged_epoch_consensus(
+        netuid: u16,
+        cursor: &mut StagedEpochCursor,
+        budget: Weight,
+    ) -> Weight {
+        let n: u16 = cursor.n;
+        let segments: u16 = Self::get_staged_epoch_segments(n);
+        let mut weight: Weight = T::DbWeight::get().reads(1);
+        let neurons: Vec<StagedNeuron> = Self::get_staged_neurons(netuid, &mut weight);
+        let active_stake: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::ActiveStake, n, &mut weight);
+        let mut consensus: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::Consensus, n, &mut weight);
+        let kappa: I32F32 = Self::get_float_kappa(netuid); // consensus majority ratio, e.g. 51%.
+
+        // Only validators with active stake take part in consensus.
+        let (voters, voter_stake): (Vec<u16>, Vec<I32F32>) = active_stake
+            .iter()
+            .enumerate()
+            .filter(|(_, stake)| **stake > I32F32::from_num(0))
+            .map(|(uid, stake)| (uid as u16, *stake))
+            .unzip();
+
+        let start: u16 = cursor.next;
+        while cursor.next < segments
+            && (cursor.next == start || weight.ref_time() < budget.ref_time())
+        {
+            let segment: u16 = cursor.next;
+            cursor.next = segment.saturating_add(1);
+            let offset: u16 = segment.saturating_mul(STAGED_EPOCH_SEGMENT_COLUMNS);
+            let columns: u16 = n.saturating_sub(offset).min(STAGED_EPOCH_SEGMENT_COLUMNS);
+
+            let mut entries: usize = 0;
+            let rows: Vec<Vec<(u16, I32F32)>> = voters
+                .iter()
+                .map(|uid| {
+                    if !neurons
+                        .get(*uid as usize)
+                        .is_some_and(|neuron| neuron.weighted)
+                    {
+                        return vec![];
+                    }
+                    weight.saturating_accrue(T::DbWeight::get().reads(1));
+                    let row: Vec<(u16, I32F32)> =
+                        StagedEpochWeights::<T>::get(netuid, (*uid, segment));
+                    entries = entries.saturating_add(row.len());
+                    row.into_iter()
+                        .map(|(uid_j, value)| (uid_j.saturating_sub(offset), value))
+                        .collect()
+                })
+                .collect();
+            let median: Vec<I32F32> =
+                weighted_median_col_sparse(&voter_stake, &rows, columns, kappa);
+            weight.saturating_accrue(Self::staged_entries_weight(
+                entries.saturating_add((columns as usize).saturating_mul(rows.len())),
+            ));
+            for (column, value) in median.into_iter().enumerate() {
+                if let Some(consensus_j) =
+                    consensus.get_mut((offset as usize).saturating_add(column))
+                {
+                    *consensus_j = value;
+                }
+            }
+        }
+
+        if cursor.next >= segments {
+            cursor.phase = StagedEpochPhase::Clip;
+            cursor.next = 0;
+        }
+
+        weight.saturating_accrue(Self::set_staged_term(
+            netuid,
+            StagedEpochTerm::Consensus,
+            consensus,
+        ));
+        weight
+    }
+
+    /// Measures the similarity of the weights of a range of uids to consensus. Under Yuma the
+    /// weights are clipped at consensus, accumulating ranks and the column sums of the bonds
+    /// delta. Once every uid is done, computes trust, ranks and the EMA alpha of the bonds.
+    fn clip_staged_epoch_weights(
+        netuid: u16,
+        cursor: &mut StagedEpochCursor,
+        budget: Weight,
+    ) -> Weight {
+        let n: u16 = cursor.n;
+        let segments: u16 = Self::get_staged_epoch_segments(n);
+        let yuma: bool = cursor.mechanism == ConsensusMechanism::Yuma;
+        let mut weight: Weight = Weight::zero();
+        let neurons: Vec<StagedNeuron> = Self::get_staged_neurons(netuid, &mut weight);
+        let consensus: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::Consensus, n, &mut weight);
+        let active_stake: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::ActiveStake, n, &mut weight);
+        let mut consensus_similarity: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::ConsensusSimilarity, n, &mut weight);
+        let mut validator_trust: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::ValidatorTrust, n, &mut weight);
+        let mut ranks: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::Ranks, n, &mut weight);
+        let mut bonds_delta_sum: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::BondsDeltaSum, n, &mut weight);
+
+        let start: u16 = cursor.next;
+        while cursor.next < n && (cursor.next == start || weight.ref_time() < budget.ref_time()) {
+            let uid: u16 = cursor.next;
+            cursor.next = uid.saturating_add(1);
+            if !neurons
+                .get(uid as usize)
+                .is_some_and(|neuron| neuron.weighted)
+            {
+                continue;
+            }
+            let row: Vec<(u16, I32F32)> =
+                Self::get_staged_weights_row(netuid, uid, segments, &mut weight);
+
+            // Agreement of the validator with consensus, before clipping.
+            if let Some(similarity) = consensus_similarity.get_mut(uid as usize) {
+                *similarity =
+                    row_cosine_similarity_sparse(sp_std::slice::from_ref(&row), &consensus)
+                        .first()
+                        .copied()
+                        .unwrap_or_default();
+            }
+            weight.saturating_accrue(Self::staged_entries_weight(n as usize));
+            if !yuma {
+                continue;
+            }
+
+            // Clip weights at majority consensus.
+            let row: Vec<(u16, I32F32)> =
+                col_clip_sparse(sp_std::slice::from_ref(&row), &consensus)
+                    .pop()
+                    .unwrap_or_default();
+            if let Some(trust) = validator_trust.get_mut(uid as usize) {
+                *trust = row.iter().map(|(_, value)| value).sum();
+            }
+
+            // Compute ranks: r_j = SUM(i) w_ij * s_i, and the column sums of ΔB = W◦S.
+            let stake_i: I32F32 = active_stake.get(uid as usize).copied().unwrap_or_default();
+            for (uid_j, value) in row.iter() {
+                if let Some(rank) = ranks.get_mut(*uid_j as usize) {
+                    *rank = rank.saturating_add(stake_i.saturating_mul(*value));
+                }
+                if let Some(sum) = bonds_delta_sum.get_mut(*uid_j as usize) {
+                    *sum = sum.saturating_add(value.saturating_mul(stake_i));
+                }
+            }
+            weight.saturating_accrue(Self::put_staged_weights_row(netuid, uid, row, segments));
+        }
+
+        if cursor.next >= n {
+            let preranks: Vec<I32F32> =
+                Self::get_staged_term(netuid, StagedEpochTerm::Preranks, n, &mut weight);
+            if yuma {
+                // Compute server trust: ratio of rank after vs. rank before.
+                let trust: Vec<I32F32> = vecdiv(&ranks, &preranks);
+                weight.saturating_accrue(Self::set_staged_term(
+                    netuid,
+                    StagedEpochTerm::Trust,
+                    trust,
+                ));
+                let alpha: Vec<I32F32> = Self::get_bonds_ema_alpha(netuid, &consensus);
+                weight.saturating_accrue(T::DbWeight::get().reads(4));
+                weight.saturating_accrue(Self::set_staged_term(
+                    netuid,
+                    StagedEpochTerm::BondsAlpha,
+                    alpha,
+                ));
+                cursor.phase = StagedEpochPhase::Bonds;
+            } else {
+                // Nothing is clipped, ranks are the stake-weighted mean.
+                ranks = preranks;
+                cursor.phase = StagedEpochPhase::Dividends;
+            }
+            inplace_normalize(&mut ranks);
+            cursor.next = 0;
+        }
+
+        weight.saturating_accrue(Self::set_staged_term(
+            netuid,
+            StagedEpochTerm::ConsensusSimilarity,
+            consensus_similarity,
+        ));
+        if yuma {
+            weight.saturating_accrue(Self::set_staged_term(
+                netuid,
+                StagedEpochTerm::ValidatorTrust,
+                validator_trust,
+            ));
+            weight.saturating_accrue(Self::set_staged_term(
+                netuid,
+                StagedEpochTerm::BondsDeltaSum,
+                bonds_delta_sum,
+            ));
+        }
+        if yuma || cursor.phase == StagedEpochPhase::Dividends {
+            weight.saturating_accrue(Self::set_staged_term(netuid, StagedEpochTerm::Ranks, ranks));
+        }
+        weight
+    }
+
+    /// Replaces the bonds of a range of uids with the exponential moving average of their
+    /// column normalized bonds delta and bonds, accumulating the column sums of the EMA bonds.
+    fn compute_staged_epoch_bonds(
+        netuid: u16,
+        cursor: &mut StagedEpochCursor,
+        budget: Weight,
+    ) -> Weight {
+        let n: u16 = cursor.n;
+        let segments: u16 = Self::get_staged_epoch_segments(n);
+        let mut weight: Weight = Weight::zero();
+        let neurons: Vec<StagedNeuron> = Self::get_staged_neurons(netuid, &mut weight);
+        let active_stake: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::ActiveStake, n, &mut weight);
+        let bonds_sum: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::BondsSum, n, &mut weight);
+        let bonds_delta_sum: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::BondsDeltaSum, n, &mut weight);
+        let alpha: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::BondsAlpha, n, &mut weight);
+        let mut ema_bonds_sum: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::EmaBondsSum, n, &mut weight);
+
+        let start: u16 = cursor.next;
+        while cursor.next < n && (cursor.next == start || weight.ref_time() < budget.ref_time()) {
+            let uid: u16 = cursor.next;
+            cursor.next = uid.saturating_add(1);
+            let Some(neuron) = neurons.get(uid as usize) else {
+                continue;
+            };
+            if !neuron.weighted && !neuron.bonded {
+                continue;
+            }
+
+            // Bonds delta ΔB = W◦S, column normalized: sum_i b_ij = 1.
+            let stake_i: I32F32 = active_stake.get(uid as usize).copied().unwrap_or_default();
+            let mut bonds_delta: Vec<(u16, I32F32)> = if neuron.weighted {
+                Self::take_staged_weights_row(netuid, uid, segments, &mut weight)
+            } else {
+                vec![]
+            };
+            for (uid_j, value) in bonds_delta.iter_mut() {
+                *value = value.saturating_mul(stake_i);
+                Self::normalize_staged_value(value, bonds_delta_sum.get(*uid_j as usize));
+            }
+
+            // Remaining bonds, column normalized: sum_i b_ij = 1.
+            let mut bonds: Vec<(u16, I32F32)> = StagedEpochBonds::<T>::get(netuid, uid);
+            for (uid_j, value) in bonds.iter_mut() {
+                Self::normalize_staged_value(value, bonds_sum.get(*uid_j as usize));
+            }
+
+            let ema_bonds: Vec<(u16, I32F32)> =
+                row_ema_alpha_vec_sparse(&bonds_delta, &bonds, &alpha, n);
+            for (uid_j, value) in ema_bonds.iter() {
+                if let Some(sum) = ema_bonds_sum.get_mut(*uid_j as usize) {
+                    *sum = sum.saturating_add(*value);
+                }
+            }
+            weight.saturating_accrue(
+                T::DbWeight::get()
+                    .reads_writes(1, 1)
+                    .saturating_add(Self::staged_entries_weight(
+                        (n as usize)
+                            .saturating_add(bonds.len())
+                            .saturating_add(ema_bonds.len()),
+                    )),
+            );
+            Self::put_staged_bonds_row(netuid, uid, ema_bonds);
+        }
+
+        if cursor.next >= n {
+            cursor.phase = StagedEpochPhase::Dividends;
+            cursor.next = 0;
+        }
+
+        weight.saturating_accrue(Self::set_staged_term(
+            netuid,
+            StagedEpochTerm::EmaBondsSum,
+            ema_bonds_sum,
+        ));
+        weight
+    }
+
+    /// Computes the dividends of a range of uids: through their column normalized EMA bonds
+    /// under Yuma, through their stake-weighted weights under the stake-weighted mean.
+    fn compute_staged_epoch_dividends(
+        netuid: u16,
+        cursor: &mut StagedEpochCursor,
+        budget: Weight,
+    ) -> Weight {
+        let n: u16 = cursor.n;
+        let segments: u16 = Self::get_staged_epoch_segments(n);
+        let yuma: bool = cursor.mechanism == ConsensusMechanism::Yuma;
+        let mut weight: Weight = Weight::zero();
+        let neurons: Vec<StagedNeuron> = Self::get_staged_neurons(netuid, &mut weight);
+        let active_stake: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::ActiveStake, n, &mut weight);
+        let incentive: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::Ranks, n, &mut weight);
+        let ema_bonds_sum: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::EmaBondsSum, n, &mut weight);
+        let mut ema_bonds_max: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::EmaBondsMax, n, &mut weight);
+        let mut dividends: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::Dividends, n, &mut weight);
+
+        let start: u16 = cursor.next;
+        while cursor.next < n && (cursor.next == start || weight.ref_time() < budget.ref_time()) {
+            let uid: u16 = cursor.next;
+            cursor.next = uid.saturating_add(1);
+            let Some(neuron) = neurons.get(uid as usize) else {
+                continue;
+            };
+
+            // Compute dividends: d_i = SUM(j) b_ij * inc_j.
+            let mut dividend: I32F32 = I32F32::from_num(0);
+            if yuma {
+                if !neuron.weighted && !neuron.bonded {
+                    continue;
+                }
+                let mut ema_bonds: Vec<(u16, I32F32)> = StagedEpochBonds::<T>::get(netuid, uid);
+                for (uid_j, value) in ema_bonds.iter_mut() {
+                    Self::normalize_staged_value(value, ema_bonds_sum.get(*uid_j as usize));
+                    let incentive_j: I32F32 =
+                        incentive.get(*uid_j as usize).copied().unwrap_or_default();
+                    dividend = dividend.saturating_add(incentive_j.saturating_mul(*value));
+                    if let Some(max) = ema_bonds_max.get_mut(*uid_j as usize) {
+                        if *max < *value {
+                            *max = *value;
+                        }
+                    }
+                }
+                weight.saturating_accrue(
+                    T::DbWeight::get()
+                        .reads_writes(1, 1)
+                        .saturating_add(Self::staged_entries_weight(ema_bonds.len())),
+                );
+                Self::put_staged_bonds_row(netuid, uid, ema_bonds);
+            } else {
+                if !neuron.weighted {
+                    continue;
+                }
+                // d_i = s_i * SUM(j) w_ij * inc_j.
+                let stake_i: I32F32 = active_stake.get(uid as usize).copied().unwrap_or_default();
+                let row: Vec<(u16, I32F32)> =
+                    Self::take_staged_weights_row(netuid, uid, segments, &mut weight);
+                for (uid_j, value) in row.iter() {
+                    let incentive_j: I32F32 =
+                        incentive.get(*uid_j as usize).copied().unwrap_or_default();
+                    dividend = dividend
+                        .saturating_add(incentive_j.saturating_mul(value.saturating_mul(stake_i)));
+                }
+            }
+            if let Some(dividend_i) = dividends.get_mut(uid as usize) {
+                *dividend_i = dividend;
+            }
+        }
+
+        if cursor.next >= n {
+            inplace_normalize(&mut dividends);
+            cursor.phase = StagedEpochPhase::Write;
+            cursor.next = 0;
+        }
+
+        if yuma {
+            weight.saturating_accrue(Self::set_staged_term(
+                netuid,
+                StagedEpochTerm::EmaBondsMax,
+                ema_bonds_max,
+            ));
+        }
+        weight.saturating_accrue(Self::set_staged_term(
+            netuid,
+            StagedEpochTerm::Dividends,
+            dividends,
+        ));
+        weight
+    }
+
+    /// Writes the staged epoch. The first chunk writes the per-uid terms and records the epoch
+    /// summary, then each chunk writes the validator agreement and bonds of a range of uids.
+    /// Uids replaced since they were gathered keep their current terms and have their bonds
+    /// dropped, as with `write_epoch_outputs`.
+    fn write_staged_epoch(netuid: u16, cursor: &mut StagedEpochCursor, budget: Weight) -> Weight {
+        let n: u16 = cursor.n;
+        let mut weight: Weight = Weight::zero();
+        let mut neurons: Vec<StagedNeuron> = Self::get_staged_neurons(netuid, &mut weight);
+
+        if cursor.next == 0 {
+            let gathered: Vec<u64> = neurons.iter().map(|neuron| neuron.registered).collect();
+            let replaced: Vec<bool> = Self::get_replaced_uids(netuid, &gathered);
+            for (neuron, replaced) in neurons.iter_mut().zip(&replaced) {
+                neuron.replaced = *replaced;
+            }
+            let outputs: EpochOutputs =
+                Self::get_staged_epoch_outputs(netuid, cursor, &mut neurons, &mut weight);
+            Self::write_epoch_terms(netuid, &outputs, &replaced);
+            Self::record_epoch_summary(netuid, cursor.start_block, cursor.rao_emission);
+            // Registrations of every uid, ten epoch terms and the epoch summary.
+            weight.saturating_accrue(
+                T::DbWeight::get()
+                    .reads_writes((n as u64).saturating_mul(2).saturating_add(17), 12)
+                    .saturating_add(Self::staged_entries_weight((n as usize).saturating_mul(13))),
+            );
+            weight.saturating_accrue(
+                T::DbWeight::get()
+                    .writes(1)
+                    .saturating_add(Self::staged_entries_weight(neurons.len())),
+            );
+            StagedEpochNeurons::<T>::insert(netuid, neurons.clone());
+        }
+
+        let consensus_similarity: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::ConsensusSimilarity, n, &mut weight);
+        let unclipped_weight: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::UnclippedWeight, n, &mut weight);
+        let validator_trust: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::ValidatorTrust, n, &mut weight);
+        let ema_bonds_max: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::EmaBondsMax, n, &mut weight);
+        let validator_permits: Vec<bool> = Self::get_validator_permit(netuid);
+        weight.saturating_accrue(
+            T::DbWeight::get()
+                .reads(1)
+                .saturating_add(Self::staged_entries_weight(validator_permits.len())),
+        );
+        let is_replaced = |uid: u16| -> bool {
+            neurons
+                .get(uid as usize)
+                .is_some_and(|neuron| neuron.replaced)
+        };
+
+        let start: u16 = cursor.next;
+        while cursor.next < n && (cursor.next == start || weight.ref_time() < budget.ref_time()) {
+            let uid: u16 = cursor.next;
+            cursor.next = uid.saturating_add(1);
+            let Some(neuron) = neurons.get(uid as usize) else {
+                continue;
+            };
+            // Uids replaced after the terms were written are dropped as well.
+            let replaced: bool = neuron.replaced
+                || Self::get_neuron_block_at_registration(netuid, uid) != neuron.registered;
+            let ema_bonds: Vec<(u16, I32F32)> = StagedEpochBonds::<T>::take(netuid, uid);
+            weight.saturating_accrue(T::DbWeight::get().reads_writes(3, 3));
+
+            // Replaced uids start over without agreement metrics. Mechanisms which do not
+            // clip weights report none of it as clipped.
+            let term = |values: &[I32F32]| values.get(uid as usize).copied().unwrap_or_default();
+            Self::update_uid_validator_agreement(
+                netuid,
+                uid,
+                neuron.permit && !replaced,
+                term(&consensus_similarity),
+                term(&unclipped_weight),
+                if cursor.mechanism.clips_weights() {
+                    term(&validator_trust)
+                } else {
+                    term(&unclipped_weight)
+                },
+                neuron.identical,
+            );
+            if replaced {
+                continue;
+            }
+
+            // Set bonds only if uid retains validator permit, otherwise clear bonds.
+            if validator_permits
+                .get(uid as usize)
+                .copied()
+                .unwrap_or(false)
+            {
+                // Column max-upscale EMA bonds for storage: max_i w_ij = 1.
+                let new_bonds_row: Vec<(u16, u16)> = ema_bonds
+                    .into_iter()
+                    .filter(|(uid_j, _)| !is_replaced(*uid_j))
+                    .map(|(uid_j, mut value)| {
+                        let max: I32F32 = ema_bonds_max
+                            .get(uid_j as usize)
+                            .copied()
+                            .unwrap_or_default();
+                        if max != I32F32::from_num(0) {
+                            value = value.saturating_div(max);
+                        }
+                        (uid_j, fixed_proportion_to_u16(value))
+                    })
+                    .collect();
+                weight.saturating_accrue(Self::staged_entries_weight(new_bonds_row.len()));
+                Bonds::<T>::insert(netuid, uid, new_bonds_row);
+            } else if neuron.permit {
+                // Only overwrite the intersection.
+                let new_empty_bonds_row: Vec<(u16, u16)> = vec![];
+                Bonds::<T>::insert(netuid, uid, new_empty_bonds_row);
+            }
+        }
+
+        if cursor.next >= n {
+            cursor.phase = StagedEpochPhase::Done;
+            cursor.next = 0;
+        }
+        weight
+    }
+
+    /// Returns the epoch terms of the staged epoch, recording the emission of each uid.
+    fn get_staged_epoch_outputs(
+        netuid: u16,
+        cursor: &StagedEpochCursor,
+        neurons: &mut [StagedNeuron],
+        weight: &mut Weight,
+    ) -> EpochOutputs {
+        let n: u16 = cursor.n;
+        let stake: Vec<I32F32> = Self::get_staged_term(netuid, StagedEpochTerm::Stake, n, weight);
+        let active_stake: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::ActiveStake, n, weight);
+        let ranks: Vec<I32F32> = Self::get_staged_term(netuid, StagedEpochTerm::Ranks, n, weight);
+        let dividends: Vec<I32F32> =
+            Self::get_staged_term(netuid, StagedEpochTerm::Dividends, n, weight);
+
+        // Get new validator permits.
+        let max_allowed_validators: u16 = Self::get_max_allowed_validators(netuid);
+        let new_validator_permits: Vec<bool> = is_topk(&stake, max_allowed_validators as usize);
+
+        let (server_emission, validator_emission, combined_emission, pruning_scores) =
+            Self::compute_epoch_emission(
+                cursor.rao_emission,
+                &stake,
+                &active_stake,
+                &ranks,
+                &dividends,
+            );
+        for ((neuron, server), validator) in neurons
+            .iter_mut()
+            .zip(&server_emission)
+            .zip(&validator_emission)
+        {
+            neuron.server_emission = *server;
+            neuron.validator_emission = *validator;
+        }
+
+        EpochOutputs {
+            active: neurons.iter().map(|neuron| neuron.active).collect(),
+            ranks: ranks.clone(),
+            trust: Self::get_staged_term(netuid, StagedEpochTerm::Trust, n, weight),
+            consensus: Self::get_staged_term(netuid, StagedEpochTerm::Consensus, n, weight),
+            incentive: ranks,
+            dividends,
+            validator_trust: Self::get_staged_term(
+                netuid,
+                StagedEpochTerm::ValidatorTrust,
+                n,
+                weight,
+            ),
+            pruning_scores,
+            server_emission,
+            validator_emission,
+            combined_emission,
+            validator_permits: new_validator_permits,
+            bonds: vec![],
+            consensus_similarity: Self::get_staged_term(
+                netuid,
+                StagedEpochTerm::ConsensusSimilarity,
+                n,
+                weight,
+            ),
+            unclipped_weight: Self::get_staged_term(
+                netuid,
+                StagedEpochTerm::UnclippedWeight,
+                n,
+                weight,
+            ),
+            identical_weights: neurons.iter().map(|neuron| neuron.identical).collect(),
+        }
+    }
+
+    /// Returns the number of segments the weights rows of `n` uids are stored in.
+    fn get_staged_epoch_segments(n: u16) -> u16 {
+        n.saturating_add(STAGED_EPOCH_SEGMENT_COLUMNS.saturating_sub(1))
+            .saturating_div(STAGED_EPOCH_SEGMENT_COLUMNS)
+    }
+
+    /// Returns the weight charged for processing `entries` weights, bonds or per-uid terms.
+    fn staged_entries_weight(entries: usize) -> Weight {
+        Weight::from_parts(
+            STAGED_EPOCH_ENTRY_REF_TIME.saturating_mul(entries as u64),
+            0,
+        )
+    }
+
+    /// Converts a stored weights or bonds row, dropping entries of uids past `n`.
+    fn get_staged_row(row: Vec<(u16, u16)>, n: u16) -> Vec<(u16, I32F32)> {
+        row.into_iter()
+            .filter(|(uid_j, _)| *uid_j < n)
+            .map(|(uid_j, value)| (uid_j, I32F32::from_num(value)))
+            .collect()
+    }
+
+    /// Divides `value` by its column sum, unless the sum is zero.
+    fn normalize_staged_value(value: &mut I32F32, sum: Option<&I32F32>) {
+        if let Some(sum) = sum.filter(|sum| **sum != I32F32::from_num(0)) {
+            *value = value.saturating_div(*sum);
+        }
+    }
+
+    fn get_staged_neurons(netuid: u16, weight: &mut Weight) -> Vec<StagedNeuron> {
+        let neurons: Vec<StagedNeuron> = StagedEpochNeurons::<T>::get(netuid);
+        weight.saturating_accrue(
+            T::DbWeight::get()
+                .reads(1)
+                .saturating_add(Self::staged_entries_weight(neurons.len())),
+        );
+        neurons
+    }
+
+    /// Returns a per-uid term of the staged epoch, zero for uids it was not computed for.
+    fn get_staged_term(
+        netuid: u16,
+        term: StagedEpochTerm,
+        n: u16,
+        weight: &mut Weight,
+    ) -> Vec<I32F32> {
+        let mut values: Vec<I32F32> = StagedEpochTerms::<T>::get(netuid, term);
+        values.resize(n as usize, I32F32::from_num(0));
+        weight.saturating_accrue(
+            T::DbWeight::get()
+                .reads(1)
+                .saturating_add(Self::staged_entries_weight(values.len())),
+        );
+        values
+    }
+
+    fn set_staged_term(netuid: u16, term: StagedEpochTerm, values: Vec<I32F32>) -> Weight {
+        let weight: Weight = T::DbWeight::get()
+            .writes(1)
+            .saturating_add(Self::staged_entries_weight(values.len()));
+        StagedEpochTerms::<T>::insert(netuid, term, values);
+        weight
+    }
+
+    fn get_staged_weights_row(
+        netuid: u16,
+        uid: u16,
+        segments: u16,
+        weight: &mut Weight,
+    ) -> Vec<(u16, I32F32)> {
+        let row: Vec<(u16, I32F32)> = (0..segments)
+            .flat_map(|segment| StagedEpochWeights::<T>::get(netuid, (uid, segment)))
+            .collect();
+        weight.saturating_accrue(
+            T::DbWeight::get()
+                .reads(segments.into())
+                .saturating_add(Self::staged_entries_weight(row.len())),
+        );
+        row
+    }
+
+    fn take_staged_weights_row(
+        netuid: u16,
+        uid: u16,
+        segments: u16,
+        weight: &mut Weight,
+    ) -> Vec<(u16, I32F32)> {
+        let row: Vec<(u16, I32F32)> = (0..segments)
+            .flat_map(|segment| StagedEpochWeights::<T>::take(netuid, (uid, segment)))
+            .collect();
+        weight.saturating_accrue(
+            T::DbWeight::get()
+                .reads_writes(segments.into(), segments.into())
+                .saturating_add(Self::staged_entries_weight(row.len())),
+        );
+        row
+    }
+
+    /// Stores a weights row in segments of `STAGED_EPOCH_SEGMENT_COLUMNS` columns, removing
+    /// empty segments. Returns the weight used.
+    fn put_staged_weights_row(
+        netuid: u16,
+        uid: u16,
+        row: Vec<(u16, I32F32)>,
+        segments: u16,
+    ) -> Weight {
+        let weight: Weight = T::DbWeight::get()
+            .writes(segments.into())
+            .saturating_add(Self::staged_entries_weight(row.len()));
+        let mut segment_rows: Vec<Vec<(u16, I32F32)>> = vec![vec![]; segments as usize];
+        for (uid_j, value) in row {
+            let segment: u16 = uid_j.saturating_div(STAGED_EPOCH_SEGMENT_COLUMNS);
+            if let Some(segment_row) = segment_rows.get_mut(segment as usize) {
+                segment_row.push((uid_j, value));
+            }
+        }
+        for (segment, segment_row) in segment_rows.into_iter().enumerate() {
+            if segment_row.is_empty() {
+                StagedEpochWeights::<T>::remove(netuid, (uid, segment as u16));
+            } else {
+                StagedEpochWeights::<T>::insert(netuid, (uid, segment as u16), segment_row);
+            }
+        }
+        weight
+    }
+
+    fn put_staged_bonds_row(netuid: u16, uid: u16, row: Vec<(u16, I32F32)>) {
+        if row.is_empty() {
+            StagedEpochBonds::<T>::remove(netuid, uid);
+        } else {
+            StagedEpochBonds::<T>::insert(netuid, uid, row);
+        }
+    }
+}
@@ -12,6 +12,24 @@ pub struct StakeInfo<T: Config> {
     stake: Compact<u64>,
 }
 
+#[freeze_struct("3ed287503752b59c")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug)]
+pub struct UnbondingInfo<T: Config> {
+    coldkey: T::AccountId,
+    amount: Compact<u64>,
+    unlock_block: Compact<u64>,
+}
+
+#[freeze_struct("34bb6f97b4085a00")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug)]
+pub struct RewardsInfo<T: Config> {
+    hotkey: T::AccountId,
+    coldkey: T::AccountId,
+    total_rewards: Compact<u64>,
+    last_epoch_rewards: Compact<u64>,
+    last_reward_block: Compact<u64>,
+}
+
 impl<T: Config> Pallet<T> {
     fn _get_stake_info_for_coldkeys(
         coldkeys: Vec<T::AccountId>,
@@ -22,17 +40,19 @@ impl<T: Config> Pallet<T> {
 
         let mut stake_info: Vec<(T::AccountId, Vec<StakeInfo<T>>)> = Vec::new();
         for coldkey_ in coldkeys {
-            let mut stake_info_for_coldkey: Vec<StakeInfo<T>> = Vec::new();
-
-            for (hotkey, coldkey, stake) in <Stake<T>>::iter() {
-                if coldkey == coldkey_ {
-                    stake_info_for_coldkey.push(StakeInfo {
-                        hotkey,
-                        coldkey,
-                        stake: stake.into(),
-                    });
-                }
-            }
+            // Only the hotkeys indexed for the coldkey are visited, not the whole Stake map.
+            let stake_info_for_coldkey: Vec<StakeInfo<T>> =
+                Self::get_indexed_hotkeys_for_coldkey(&coldkey_)
+                    .into_iter()
+                    .filter_map(|hotkey| {
+                        let stake = Stake::<T>::try_get(&hotkey, &coldkey_).ok()?;
+                        Some(StakeInfo {
+                            hotkey,
+                            coldkey: coldkey_.clone(),
+                            stake: stake.into(),
+                        })
+                    })
+                    .collect();
 
             stake_info.push((coldkey_, stake_info_for_coldkey));
         }
@@ -81,4 +101,48 @@ impl<T: Config> Pallet<T> {
             first.1.clone()
         }
     }
+
+    pub fn get_unbonding_for_coldkey(coldkey_account_vec: Vec<u8>) -> Vec<UnbondingInfo<T>> {
+        if coldkey_account_vec.len() != 32 {
+            return Vec::new(); // Invalid coldkey
+        }
+
+        let Ok(coldkey) = T::AccountId::decode(&mut coldkey_account_vec.as_bytes_ref()) else {
+            return Vec::new();
+        };
+
+        UnbondingQueue::<T>::get(&coldkey)
+            .into_iter()
+            .map(|(amount, unlock_block)| UnbondingInfo {
+                coldkey: coldkey.clone(),
+                amount: amount.into(),
+                unlock_block: unlock_block.into(),
+            })
+            .collect()
+    }
+
+    pub fn get_rewards_for_coldkey(coldkey_account_vec: Vec<u8>) -> Vec<RewardsInfo<T>> {
+        if coldkey_account_vec.len() != 32 {
+            return Vec::new(); // Invalid coldkey
+        }
+
+        let Ok(coldkey) = T::AccountId::decode(&mut coldkey_account_vec.as_bytes_ref()) else {
+            return Vec::new();
+        };
+
+        NominatorRewardHotkeys::<T>::get(&coldkey)
+            .into_iter()
+            .filter_map(|hotkey| {
+                let (total_rewards, last_epoch_rewards, last_reward_block) =
+                    NominatorRewards::<T>::try_get(&hotkey, &coldkey).ok()?;
+                Some(RewardsInfo {
+                    hotkey,
+                    coldkey: coldkey.clone(),
+                    total_rewards: total_rewards.into(),
+                    last_epoch_rewards: last_epoch_rewards.into(),
+                    last_reward_block: last_reward_block.into(),
+                })
+            })
+            .collect()
+    }
 }
@@ -8,7 +8,9 @@ use frame_support::{
         },
         Imbalance,
     },
+    weights::Weight,
 };
+use sp_core::Get;
 
 impl<T: Config> Pallet<T> {
     /// ---- The implementation for the extrinsic become_delegate: signals that this hotkey allows delegated stake.
@@ -155,8 +157,9 @@ impl<T: Config> Pallet<T> {
         let min_take = MinTake::<T>::get();
         ensure!(take >= min_take, Error::<T>::DelegateTakeTooLow);
 
-        // --- 4. Set the new take value.
+        // --- 4. Set the new take value. A pending take increase is dropped.
         Delegates::<T>::insert(hotkey.clone(), take);
+        Self::cancel_pending_take_increase(&hotkey);
 
         // --- 5. Emit the take value.
         log::info!(
@@ -184,8 +187,14 @@ impl<T: Config> Pallet<T> {
     ///     - The stake proportion that this hotkey takes from delegations for subnet ID.
     ///
     /// # Event:
+    /// * TakeIncreaseAnnounced;
+    ///     - On successfully announcing an increased take for this hotkey.
+    ///
     /// * TakeIncreased;
-    ///     - On successfully setting a increased take for this hotkey.
+    ///     - When the increased take is applied, immediately if there is no take increase delay.
+    ///
+    /// * TakeIncreaseApplied;
+    ///     - When the increased take is applied, immediately if there is no take increase delay.
     ///
     /// # Raises:
     /// * 'NotRegistered':
@@ -244,20 +253,92 @@ impl<T: Config> Pallet<T> {
         // Set last block for rate limiting
         Self::set_last_tx_block_delegate_take(&coldkey, block);
 
-        // --- 6. Set the new take value.
-        Delegates::<T>::insert(hotkey.clone(), take);
+        // --- 6. Without a notice period the new take is applied right away.
+        let effective_block: u64 = block.saturating_add(TakeIncreaseDelay::<T>::get());
+        if effective_block <= block {
+            Self::cancel_pending_take_increase(&hotkey);
+            Self::apply_take_increase(&coldkey, &hotkey, take);
+            return Ok(());
+        }
 
-        // --- 7. Emit the take value.
+        // --- 7. Otherwise announce the new take, replacing any earlier announcement.
+        Self::cancel_pending_take_increase(&hotkey);
+        PendingTakeIncreases::<T>::insert(&hotkey, (take, effective_block));
+        TakeIncreasesAtBlock::<T>::mutate(effective_block, |hotkeys| hotkeys.push(hotkey.clone()));
+
+        // --- 8. Emit the announcement.
+        log::info!(
+            "TakeIncreaseAnnounced( coldkey:{:?}, hotkey:{:?}, take:{:?}, effective_block:{:?} )",
+            coldkey,
+            hotkey,
+            take,
+            effective_block
+        );
+        Self::deposit_event(Event::TakeIncreaseAnnounced {
+            coldkey,
+            hotkey,
+            take,
+            effective_block,
+        });
+
+        // --- 9. Ok and return.
+        Ok(())
+    }
+
+    /// Applies the take increases announced to become effective on the block and
+    /// returns the weight consumed, charged per announcement.
+    ///
+    pub fn apply_pending_take_increases(block_number: u64) -> Weight {
+        let hotkeys: Vec<T::AccountId> = TakeIncreasesAtBlock::<T>::take(block_number);
+        let mut weight: Weight = T::DbWeight::get().reads_writes(1, 1);
+        for hotkey in hotkeys {
+            weight.saturating_accrue(T::DbWeight::get().reads(1));
+            let Some((take, effective_block)) = PendingTakeIncreases::<T>::get(&hotkey) else {
+                continue;
+            };
+            if effective_block != block_number {
+                continue;
+            }
+            PendingTakeIncreases::<T>::remove(&hotkey);
+            weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+
+            // The delegate may have been swapped away in the meantime.
+            if !Self::hotkey_is_delegate(&hotkey) {
+                continue;
+            }
+            // The maximum take may have been lowered since the announcement.
+            let coldkey: T::AccountId = Self::get_owning_coldkey_for_hotkey(&hotkey);
+            Self::apply_take_increase(&coldkey, &hotkey, take.min(MaxTake::<T>::get()));
+            weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 1));
+        }
+        weight
+    }
+
+    /// Sets the take of the delegate and emits the take increase events.
+    ///
+    fn apply_take_increase(coldkey: &T::AccountId, hotkey: &T::AccountId, take: u16) {
+        Delegates::<T>::insert(hotkey, take);
         log::info!(
             "TakeIncreased( coldkey:{:?}, hotkey:{:?}, take:{:?} )",
             coldkey,
             hotkey,
             take
         );
-        Self::deposit_event(Event::TakeIncreased(coldkey, hotkey, take));
+        Self::deposit_event(Event::TakeIncreased(coldkey.clone(), hotkey.clone(), take));
+        Self::deposit_event(Event::TakeIncreaseApplied {
+            hotkey: hotkey.clone(),
+            take,
+        });
+    }
 
-        // --- 8. Ok and return.
-        Ok(())
+    /// Drops the announced take increase of the delegate, if any.
+    ///
+    pub fn cancel_pending_take_increase(hotkey: &T::AccountId) {
+        if let Some((_, effective_block)) = PendingTakeIncreases::<T>::take(hotkey) {
+            TakeIncreasesAtBlock::<T>::mutate(effective_block, |hotkeys| {
+                hotkeys.retain(|h| h != hotkey)
+            });
+        }
     }
 
     /// ---- The implementation for the extrinsic add_stake: Adds stake to a hotkey account.
@@ -289,6 +370,12 @@ impl<T: Config> Pallet<T> {
     /// * 'TxRateLimitExceeded':
     ///     -  Thrown if key has hit transaction rate limit
     ///
+    /// * 'NominatorNotAllowlisted':
+    ///     -  Thrown if the delegate only accepts nominations from its allowlist.
+    ///
+    /// * 'DelegateNominatorLimitReached', 'DelegateNominatedStakeLimitReached':
+    ///     -  Thrown if the nomination would exceed the caps of the delegate.
+    ///
     pub fn do_add_stake(
         origin: T::RuntimeOrigin,
         hotkey: T::AccountId,
@@ -328,10 +415,13 @@ impl<T: Config> Pallet<T> {
         // Ensure we don't exceed stake rate limit
         let stakes_this_interval =
             Self::get_stakes_this_interval_for_coldkey_hotkey(&coldkey, &hotkey);
-        ensure!(
-            stakes_this_interval < Self::get_target_stakes_per_interval(),
-            Error::<T>::StakeRateLimitExceeded
-        );
+        Self::ensure_stake_rate_limit(
+            &coldkey,
+            &hotkey,
+            stakes_this_interval,
+            stake_to_be_added,
+            Error::<T>::StakeRateLimitExceeded,
+        )?;
 
         // If this is a nomination stake, check if total stake after adding will be above
         // the minimum required stake.
@@ -345,6 +435,9 @@ impl<T: Config> Pallet<T> {
                 total_stake_after_add >= NominatorMinRequiredStake::<T>::get(),
                 Error::<T>::NomStakeBelowMinimumThreshold
             );
+
+            // Ensure the delegate accepts the nomination.
+            Self::ensure_nomination_allowed(&hotkey, &coldkey, stake_to_be_added)?;
         }
 
         // Ensure the remove operation from the coldkey is a success.
@@ -365,6 +458,7 @@ impl<T: Config> Pallet<T> {
             stakes_this_interval.saturating_add(1),
             block,
         );
+        Self::record_stake_amount_for_coldkey_hotkey(&coldkey, &hotkey, actual_amount_to_stake);
         log::info!(
             "StakeAdded( hotkey:{:?}, stake_to_be_added:{:?} )",
             hotkey,
@@ -405,6 +499,9 @@ impl<T: Config> Pallet<T> {
     /// * 'TxRateLimitExceeded':
     ///     -  Thrown if key has hit transaction rate limit
     ///
+    /// * 'UnbondingQueueFull':
+    ///     -  Thrown if an unbonding period applies and the coldkey has too many pending unbonding chunks.
+    ///
     pub fn do_remove_stake(
         origin: T::RuntimeOrigin,
         hotkey: T::AccountId,
@@ -447,16 +544,30 @@ impl<T: Config> Pallet<T> {
         // Ensure we don't exceed stake rate limit
         let unstakes_this_interval =
             Self::get_stakes_this_interval_for_coldkey_hotkey(&coldkey, &hotkey);
-        ensure!(
-            unstakes_this_interval < Self::get_target_stakes_per_interval(),
-            Error::<T>::UnstakeRateLimitExceeded
-        );
+        Self::ensure_stake_rate_limit(
+            &coldkey,
+            &hotkey,
+            unstakes_this_interval,
+            stake_to_be_removed,
+            Error::<T>::UnstakeRateLimitExceeded,
+        )?;
+
+        // If an unbonding period applies, ensure the removed stake can be queued.
+        let unbonding_period = Self::get_unbonding_period_for_hotkey(&hotkey);
+        if unbonding_period > 0 {
+            Self::ensure_can_unbond(&coldkey)?;
+        }
 
         // We remove the balance from the hotkey.
         Self::decrease_stake_on_coldkey_hotkey_account(&coldkey, &hotkey, stake_to_be_removed);
 
         // We add the balance to the coldkey.  If the above fails we will not credit this coldkey.
-        Self::add_balance_to_coldkey_account(&coldkey, stake_to_be_removed);
+        // With an unbonding period the balance is queued and released once the period has passed.
+        if unbonding_period > 0 {
+            Self::schedule_unbonding(&coldkey, &hotkey, stake_to_be_removed, unbonding_period);
+        } else {
+            Self::add_balance_to_coldkey_account(&coldkey, stake_to_be_removed);
+        }
 
         // If the stake is below the minimum, we clear the nomination from storage.
         // This only applies to nominator stakes.
@@ -475,6 +586,7 @@ impl<T: Config> Pallet<T> {
             unstakes_this_interval.saturating_add(1),
             block,
         );
+        Self::record_stake_amount_for_coldkey_hotkey(&coldkey, &hotkey, stake_to_be_removed);
         log::info!(
             "StakeRemoved( hotkey:{:?}, stake_to_be_removed:{:?} )",
             hotkey,
@@ -486,6 +598,774 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// ---- The implementation for the extrinsic add_stake_multiple: Adds stake to many hotkey accounts.
+    ///
+    /// # Args:
+    /// * 'origin': (<T as frame_system::Config>RuntimeOrigin):
+    ///     -  The signature of the caller's coldkey.
+    ///
+    /// * 'stakes' (Vec<(T::AccountId, u64)>):
+    ///     -  The hotkeys and the amounts of stake to be added to each of them.
+    ///
+    /// # Event:
+    /// * StakeAdded;
+    ///     -  For every leg which adds stake to a hotkey account.
+    ///
+    /// # Raises:
+    /// * 'InvalidStakeBatchSize':
+    ///     -  The batch is empty or has more than MAX_STAKE_BATCH_SIZE legs.
+    ///
+    /// * Any error raised by do_add_stake for one of the legs.
+    ///
+    pub fn do_add_stake_multiple(
+        origin: T::RuntimeOrigin,
+        stakes: Vec<(T::AccountId, u64)>,
+    ) -> dispatch::DispatchResult {
+        // --- 1. Ensure the batch size is within bounds.
+        Self::ensure_stake_batch_size(stakes.len())?;
+
+        // --- 2. Add every leg. The call is transactional, so a failing leg reverts the batch.
+        for (hotkey, stake_to_be_added) in stakes {
+            Self::do_add_stake(origin.clone(), hotkey, stake_to_be_added)?;
+        }
+
+        // --- 3. Ok and return.
+        Ok(())
+    }
+
+    /// ---- The implementation for the extrinsic remove_stake_multiple: Removes stake from many hotkey accounts.
+    ///
+    /// # Args:
+    /// * 'origin': (<T as frame_system::Config>RuntimeOrigin):
+    ///     -  The signature of the caller's coldkey.
+    ///
+    /// * 'unstakes' (Vec<(T::AccountId, u64)>):
+    ///     -  The hotkeys and the amounts of stake to be removed from each of them.
+    ///
+    /// # Event:
+    /// * StakeRemoved;
+    ///     -  For every leg which removes stake from a hotkey account.
+    ///
+    /// # Raises:
+    /// * 'InvalidStakeBatchSize':
+    ///     -  The batch is empty or has more than MAX_STAKE_BATCH_SIZE legs.
+    ///
+    /// * Any error raised by do_remove_stake for one of the legs.
+    ///
+    pub fn do_remove_stake_multiple(
+        origin: T::RuntimeOrigin,
+        unstakes: Vec<(T::AccountId, u64)>,
+    ) -> dispatch::DispatchResult {
+        // --- 1. Ensure the batch size is within bounds.
+        Self::ensure_stake_batch_size(unstakes.len())?;
+
+        // --- 2. Remove every leg. The call is transactional, so a failing leg reverts the batch.
+        for (hotkey, stake_to_be_removed) in unstakes {
+            Self::do_remove_stake(origin.clone(), hotkey, stake_to_be_removed)?;
+        }
+
+        // --- 3. Ok and return.
+        Ok(())
+    }
+
+    // Ensures a batched staking call has at least one and at most MAX_STAKE_BATCH_SIZE legs.
+    //
+    fn ensure_stake_batch_size(len: usize) -> dispatch::DispatchResult {
+        ensure!(
+            len > 0 && len <= MAX_STAKE_BATCH_SIZE as usize,
+            Error::<T>::InvalidStakeBatchSize
+        );
+        Ok(())
+    }
+
+    /// ---- The implementation for the extrinsic move_stake: Moves stake from one hotkey to another
+    /// without passing through the coldkey balance.
+    ///
+    /// # Args:
+    /// * 'origin': (<T as frame_system::Config>RuntimeOrigin):
+    ///     -  The signature of the caller's coldkey.
+    ///
+    /// * 'origin_hotkey' (T::AccountId):
+    ///     -  The hotkey the stake is moved from.
+    ///
+    /// * 'destination_hotkey' (T::AccountId):
+    ///     -  The hotkey the stake is moved to.
+    ///
+    /// * 'amount' (u64):
+    ///     -  The amount of stake to be moved.
+    ///
+    /// # Event:
+    /// * StakeMoved;
+    ///     -  On the successfully moving stake between the hotkeys.
+    ///
+    /// # Raises:
+    /// * 'HotKeyAccountNotExists':
+    ///     -  Thrown if either hotkey is not registered.
+    ///
+    /// * 'HotKeyNotDelegateAndSignerNotOwnHotKey':
+    ///     -  Thrown if either hotkey is not a delegate and not owned by the calling coldkey.
+    ///
+    /// * 'SameHotkeyStakeMove':
+    ///     -  Thrown if the origin and destination hotkeys are the same.
+    ///
+    /// * 'NotEnoughStakeToWithdraw':
+    ///     -  Thrown if there is not enough stake on the origin hotkey to move this amount.
+    ///
+    /// * 'NomStakeBelowMinimumThreshold':
+    ///     -  Thrown if the resulting nomination on the destination is below the minimum.
+    ///
+    /// * 'StakeRateLimitExceeded':
+    ///     -  Thrown if key has hit transaction rate limit
+    ///
+    /// * 'NominatorNotAllowlisted', 'DelegateNominatorLimitReached', 'DelegateNominatedStakeLimitReached':
+    ///     -  Thrown if the delegate does not accept the resulting nomination.
+    ///
+    pub fn do_move_stake(
+        origin: T::RuntimeOrigin,
+        origin_hotkey: T::AccountId,
+        destination_hotkey: T::AccountId,
+        amount: u64,
+    ) -> dispatch::DispatchResult {
+        // --- 1. We check the transaction is signed by the caller and retrieve the T::AccountId coldkey information.
+        let coldkey = ensure_signed(origin)?;
+        log::info!(
+            "do_move_stake( origin:{:?} origin_hotkey:{:?}, destination_hotkey:{:?}, amount:{:?} )",
+            coldkey,
+            origin_hotkey,
+            destination_hotkey,
+            amount
+        );
+        ensure!(
+            !Self::coldkey_in_arbitration(&coldkey),
+            Error::<T>::ColdkeyIsInArbitration
+        );
+
+        // --- 2. Ensure we are moving between two different hotkeys.
+        ensure!(
+            origin_hotkey != destination_hotkey,
+            Error::<T>::SameHotkeyStakeMove
+        );
+
+        // --- 3. Ensure that both hotkey accounts exist.
+        ensure!(
+            Self::hotkey_account_exists(&origin_hotkey),
+            Error::<T>::HotKeyAccountNotExists
+        );
+        ensure!(
+            Self::hotkey_account_exists(&destination_hotkey),
+            Error::<T>::HotKeyAccountNotExists
+        );
+
+        // --- 4. Ensure that both hotkeys allow delegation or are owned by the calling coldkey.
+        ensure!(
+            Self::hotkey_is_delegate(&origin_hotkey)
+                || Self::coldkey_owns_hotkey(&coldkey, &origin_hotkey),
+            Error::<T>::HotKeyNotDelegateAndSignerNotOwnHotKey
+        );
+        ensure!(
+            Self::hotkey_is_delegate(&destination_hotkey)
+                || Self::coldkey_owns_hotkey(&coldkey, &destination_hotkey),
+            Error::<T>::HotKeyNotDelegateAndSignerNotOwnHotKey
+        );
+
+        // --- 5. Ensure that the amount is above zero and available on the origin hotkey.
+        ensure!(amount > 0, Error::<T>::StakeToWithdrawIsZero);
+        ensure!(
+            Self::has_enough_stake(&coldkey, &origin_hotkey, amount),
+            Error::<T>::NotEnoughStakeToWithdraw
+        );
+
+        // --- 6. Ensure we don't exceed stake rate limit. A move counts once against the origin pair.
+        let stakes_this_interval =
+            Self::get_stakes_this_interval_for_coldkey_hotkey(&coldkey, &origin_hotkey);
+        Self::ensure_stake_rate_limit(
+            &coldkey,
+            &origin_hotkey,
+            stakes_this_interval,
+            amount,
+            Error::<T>::StakeRateLimitExceeded,
+        )?;
+
+        // --- 7. If the destination is a nomination, the resulting stake must be above the minimum
+        // and the destination delegate must accept it.
+        if !Self::coldkey_owns_hotkey(&coldkey, &destination_hotkey) {
+            let total_stake_after_move =
+                Stake::<T>::get(&destination_hotkey, &coldkey).saturating_add(amount);
+            ensure!(
+                total_stake_after_move >= NominatorMinRequiredStake::<T>::get(),
+                Error::<T>::NomStakeBelowMinimumThreshold
+            );
+            Self::ensure_nomination_allowed(&destination_hotkey, &coldkey, amount)?;
+        }
+
+        // --- 8. Move the stake between the hotkeys.
+        Self::decrease_stake_on_coldkey_hotkey_account(&coldkey, &origin_hotkey, amount);
+        Self::increase_stake_on_coldkey_hotkey_account(&coldkey, &destination_hotkey, amount);
+
+        // --- 9. Clear what is left on the origin if it is below the nomination minimum,
+        // and stop tracking the origin hotkey once nothing is staked on it.
+        let remaining_stake = Self::get_stake_for_coldkey_and_hotkey(&coldkey, &origin_hotkey);
+        Self::clear_small_nomination_if_required(&origin_hotkey, &coldkey, remaining_stake);
+        // The rewards ledger follows the stake once the origin position is closed.
+        if Self::get_stake_for_coldkey_and_hotkey(&coldkey, &origin_hotkey) == 0 {
+            StakingHotkeys::<T>::mutate(&coldkey, |hotkeys| {
+                hotkeys.retain(|h| *h != origin_hotkey)
+            });
+            Self::merge_nominator_rewards(
+                &origin_hotkey,
+                &coldkey,
+                &destination_hotkey,
+                &coldkey,
+                &mut Weight::zero(),
+            );
+        }
+
+        // --- 10. Set last block for rate limiting
+        let block: u64 = Self::get_current_block_as_u64();
+        Self::set_last_tx_block(&coldkey, block);
+        Self::set_stakes_this_interval_for_coldkey_hotkey(
+            &coldkey,
+            &origin_hotkey,
+            stakes_this_interval.saturating_add(1),
+            block,
+        );
+        Self::record_stake_amount_for_coldkey_hotkey(&coldkey, &origin_hotkey, amount);
+
+        // --- 11. Emit the move event.
+        log::info!(
+            "StakeMoved( coldkey:{:?}, origin_hotkey:{:?}, destination_hotkey:{:?}, amount:{:?} )",
+            coldkey,
+            origin_hotkey,
+            destination_hotkey,
+            amount
+        );
+        Self::deposit_event(Event::StakeMoved {
+            coldkey,
+            origin_hotkey,
+            destination_hotkey,
+            amount,
+        });
+
+        // --- 12. Ok and return.
+        Ok(())
+    }
+
+    /// ---- The implementation for the extrinsic transfer_stake: Transfers stake on a hotkey between coldkeys.
+    ///
+    /// # Args:
+    /// * 'origin': (<T as frame_system::Config>RuntimeOrigin):
+    ///     -  The signature of the caller's coldkey.
+    ///
+    /// * 'destination_coldkey' (T::AccountId):
+    ///     -  The coldkey the stake is transferred to.
+    ///
+    /// * 'hotkey' (T::AccountId):
+    ///     -  The hotkey the stake is staked on.
+    ///
+    /// * 'amount' (u64):
+    ///     -  The amount of stake to be transferred.
+    ///
+    /// # Event:
+    /// * StakeTransferred;
+    ///     -  On the successfully transferring stake between the coldkeys.
+    ///
+    /// # Raises:
+    /// * 'ColdkeyIsInArbitration':
+    ///     -  Thrown if either coldkey is in arbitration.
+    ///
+    /// * 'SameColdkeyStakeTransfer':
+    ///     -  Thrown if the origin and destination coldkeys are the same.
+    ///
+    /// * 'HotKeyAccountNotExists':
+    ///     -  Thrown if the hotkey is not registered.
+    ///
+    /// * 'HotKeyNotDelegateAndSignerNotOwnHotKey':
+    ///     -  Thrown if the hotkey is not a delegate and not owned by the destination coldkey.
+    ///
+    /// * 'NotEnoughStakeToWithdraw':
+    ///     -  Thrown if there is not enough stake on the hotkey to transfer this amount.
+    ///
+    /// * 'NomStakeBelowMinimumThreshold':
+    ///     -  Thrown if the resulting nomination of the destination coldkey is below the minimum.
+    ///
+    /// * 'StakeRateLimitExceeded':
+    ///     -  Thrown if key has hit transaction rate limit
+    ///
+    /// * 'NominatorNotAllowlisted', 'DelegateNominatorLimitReached', 'DelegateNominatedStakeLimitReached':
+    ///     -  Thrown if the delegate does not accept the resulting nomination.
+    ///
+    pub fn do_transfer_stake(
+        origin: T::RuntimeOrigin,
+        destination_coldkey: T::AccountId,
+        hotkey: T::AccountId,
+        amount: u64,
+    ) -> dispatch::DispatchResult {
+        // --- 1. We check the transaction is signed by the caller and retrieve the T::AccountId coldkey information.
+        let coldkey = ensure_signed(origin)?;
+        log::info!(
+            "do_transfer_stake( origin:{:?} destination_coldkey:{:?}, hotkey:{:?}, amount:{:?} )",
+            coldkey,
+            destination_coldkey,
+            hotkey,
+            amount
+        );
+        ensure!(
+            !Self::coldkey_in_arbitration(&coldkey),
+            Error::<T>::ColdkeyIsInArbitration
+        );
+        ensure!(
+            !Self::coldkey_in_arbitration(&destination_coldkey),
+            Error::<T>::ColdkeyIsInArbitration
+        );
+
+        // --- 2. Ensure we are transferring between two different coldkeys.
+        ensure!(
+            coldkey != destination_coldkey,
+            Error::<T>::SameColdkeyStakeTransfer
+        );
+
+        // --- 3. Ensure that the hotkey account exists.
+        ensure!(
+            Self::hotkey_account_exists(&hotkey),
+            Error::<T>::HotKeyAccountNotExists
+        );
+
+        // --- 4. Ensure that the destination coldkey is allowed to stake on the hotkey.
+        ensure!(
+            Self::hotkey_is_delegate(&hotkey)
+                || Self::coldkey_owns_hotkey(&destination_coldkey, &hotkey),
+            Error::<T>::HotKeyNotDelegateAndSignerNotOwnHotKey
+        );
+
+        // --- 5. Ensure that the amount is above zero and available on the hotkey.
+        ensure!(amount > 0, Error::<T>::StakeToWithdrawIsZero);
+        ensure!(
+            Self::has_enough_stake(&coldkey, &hotkey, amount),
+            Error::<T>::NotEnoughStakeToWithdraw
+        );
+
+        // --- 6. Ensure we don't exceed stake rate limit. A transfer counts once against the origin pair.
+        let stakes_this_interval =
+            Self::get_stakes_this_interval_for_coldkey_hotkey(&coldkey, &hotkey);
+        Self::ensure_stake_rate_limit(
+            &coldkey,
+            &hotkey,
+            stakes_this_interval,
+            amount,
+            Error::<T>::StakeRateLimitExceeded,
+        )?;
+
+        // --- 7. If the destination is a nomination, the resulting stake must be above the minimum
+        // and the delegate must accept it.
+        if !Self::coldkey_owns_hotkey(&destination_coldkey, &hotkey) {
+            let total_stake_after_transfer =
+                Stake::<T>::get(&hotkey, &destination_coldkey).saturating_add(amount);
+            ensure!(
+                total_stake_after_transfer >= NominatorMinRequiredStake::<T>::get(),
+                Error::<T>::NomStakeBelowMinimumThreshold
+            );
+            Self::ensure_nomination_allowed(&hotkey, &destination_coldkey, amount)?;
+        }
+
+        // --- 8. Transfer the stake between the coldkeys.
+        Self::decrease_stake_on_coldkey_hotkey_account(&coldkey, &hotkey, amount);
+        Self::increase_stake_on_coldkey_hotkey_account(&destination_coldkey, &hotkey, amount);
+
+        // --- 9. Clear what is left on the origin if it is below the nomination minimum,
+        // and stop tracking the hotkey for the origin once nothing is staked on it.
+        let remaining_stake = Self::get_stake_for_coldkey_and_hotkey(&coldkey, &hotkey);
+        Self::clear_small_nomination_if_required(&hotkey, &coldkey, remaining_stake);
+        // The rewards ledger follows the stake once the origin position is closed.
+        if Self::get_stake_for_coldkey_and_hotkey(&coldkey, &hotkey) == 0 {
+            StakingHotkeys::<T>::mutate(&coldkey, |hotkeys| hotkeys.retain(|h| *h != hotkey));
+            Self::merge_nominator_rewards(
+                &hotkey,
+                &coldkey,
+                &hotkey,
+                &destination_coldkey,
+                &mut Weight::zero(),
+            );
+        }
+
+        // --- 10. Set last block for rate limiting
+        let block: u64 = Self::get_current_block_as_u64();
+        Self::set_last_tx_block(&coldkey, block);
+        Self::set_stakes_this_interval_for_coldkey_hotkey(
+            &coldkey,
+            &hotkey,
+            stakes_this_interval.saturating_add(1),
+            block,
+        );
+        Self::record_stake_amount_for_coldkey_hotkey(&coldkey, &hotkey, amount);
+
+        // --- 11. Emit the transfer event.
+        log::info!(
+            "StakeTransferred( origin_coldkey:{:?}, destination_coldkey:{:?}, hotkey:{:?}, amount:{:?} )",
+            coldkey,
+            destination_coldkey,
+            hotkey,
+            amount
+        );
+        Self::deposit_event(Event::StakeTransferred {
+            origin_coldkey: coldkey,
+            destination_coldkey,
+            hotkey,
+            amount,
+        });
+
+        // --- 12. Ok and return.
+        Ok(())
+    }
+
+    /// ---- The implementation for the extrinsic set_reward_destination: Sets where the emission of a nomination is paid to.
+    ///
+    /// # Args:
+    /// * 'origin': (<T as frame_system::Config>RuntimeOrigin):
+    ///     -  The signature of the caller's coldkey.
+    ///
+    /// * 'hotkey' (T::AccountId):
+    ///     -  The hotkey the caller's stake is delegated to.
+    ///
+    /// * 'destination' (RewardDestination<T::AccountId>):
+    ///     -  Where the emission of the nomination is paid to.
+    ///
+    /// # Event:
+    /// * RewardDestinationSet;
+    ///     -  On successfully setting the reward destination.
+    ///
+    /// # Raises:
+    /// * 'HotKeyAccountNotExists':
+    ///     -  Thrown if the hotkey account does not exist.
+    ///
+    pub fn do_set_reward_destination(
+        origin: T::RuntimeOrigin,
+        hotkey: T::AccountId,
+        destination: RewardDestination<T::AccountId>,
+    ) -> dispatch::DispatchResult {
+        // --- 1. We check the transaction is signed by the caller and retrieve the T::AccountId coldkey information.
+        let coldkey = ensure_signed(origin)?;
+        log::info!(
+            "do_set_reward_destination( origin:{:?} hotkey:{:?}, destination:{:?} )",
+            coldkey,
+            hotkey,
+            destination
+        );
+        ensure!(
+            !Self::coldkey_in_arbitration(&coldkey),
+            Error::<T>::ColdkeyIsInArbitration
+        );
+
+        // --- 2. Ensure that the hotkey account exists.
+        ensure!(
+            Self::hotkey_account_exists(&hotkey),
+            Error::<T>::HotKeyAccountNotExists
+        );
+
+        // --- 3. Store the destination, compounding is the default and needs no entry.
+        if destination == RewardDestination::Compound {
+            RewardDestinations::<T>::remove(&coldkey, &hotkey);
+        } else {
+            RewardDestinations::<T>::insert(&coldkey, &hotkey, destination.clone());
+        }
+
+        // --- 4. Emit the event.
+        log::info!(
+            "RewardDestinationSet( coldkey:{:?}, hotkey:{:?}, destination:{:?} )",
+            coldkey,
+            hotkey,
+            destination
+        );
+        Self::deposit_event(Event::RewardDestinationSet {
+            coldkey,
+            hotkey,
+            destination,
+        });
+
+        // --- 5. Ok and return.
+        Ok(())
+    }
+
+    /// ---- The implementation for the extrinsic set_nomination_allowlist_enabled: Restricts nominations to the allowlist of the delegate.
+    ///
+    /// # Args:
+    /// * 'origin': (<T as frame_system::Config>RuntimeOrigin):
+    ///     -  The signature of the caller's coldkey.
+    ///
+    /// * 'hotkey' (T::AccountId):
+    ///     -  The delegate hotkey.
+    ///
+    /// * 'enabled' (bool):
+    ///     -  Whether only allowlisted coldkeys may nominate to the delegate.
+    ///
+    /// # Event:
+    /// * NominationAllowlistEnabledSet;
+    ///     -  On successfully setting the restriction.
+    ///
+    /// # Raises:
+    /// * 'HotKeyAccountNotExists':
+    ///     -  Thrown if the hotkey is not registered.
+    ///
+    /// * 'NonAssociatedColdKey':
+    ///     -  Thrown if the caller does not own the hotkey.
+    ///
+    /// * 'HotKeyNotDelegateAndSignerNotOwnHotKey':
+    ///     -  Thrown if the hotkey is not a delegate.
+    ///
+    pub fn do_set_nomination_allowlist_enabled(
+        origin: T::RuntimeOrigin,
+        hotkey: T::AccountId,
+        enabled: bool,
+    ) -> dispatch::DispatchResult {
+        // --- 1. Ensure the caller owns the delegate.
+        let coldkey = ensure_signed(origin)?;
+        Self::ensure_delegate_owner(&coldkey, &hotkey)?;
+
+        // --- 2. Store the setting, an open delegate needs no entry.
+        if enabled {
+            NominationAllowlistEnabled::<T>::insert(&hotkey, true);
+        } else {
+            NominationAllowlistEnabled::<T>::remove(&hotkey);
+        }
+
+        // --- 3. Emit the event.
+        log::info!(
+            "NominationAllowlistEnabledSet( hotkey:{:?}, enabled:{:?} )",
+            hotkey,
+            enabled
+        );
+        Self::deposit_event(Event::NominationAllowlistEnabledSet { hotkey, enabled });
+
+        // --- 4. Ok and return.
+        Ok(())
+    }
+
+    /// ---- The implementation for the extrinsic set_nomination_allowed: Updates the nomination allowlist of the delegate.
+    ///
+    /// # Args:
+    /// * 'origin': (<T as frame_system::Config>RuntimeOrigin):
+    ///     -  The signature of the caller's coldkey.
+    ///
+    /// * 'hotkey' (T::AccountId):
+    ///     -  The delegate hotkey.
+    ///
+    /// * 'nominator' (T::AccountId):
+    ///     -  The coldkey to allow or disallow.
+    ///
+    /// * 'allowed' (bool):
+    ///     -  Whether the coldkey is on the allowlist.
+    ///
+    /// # Event:
+    /// * NominationAllowlistUpdated;
+    ///     -  On successfully updating the allowlist.
+    ///
+    /// # Raises:
+    /// * 'HotKeyAccountNotExists':
+    ///     -  Thrown if the hotkey is not registered.
+    ///
+    /// * 'NonAssociatedColdKey':
+    ///     -  Thrown if the caller does not own the hotkey.
+    ///
+    /// * 'HotKeyNotDelegateAndSignerNotOwnHotKey':
+    ///     -  Thrown if the hotkey is not a delegate.
+    ///
+    /// * 'NominationAllowlistFull':
+    ///     -  Thrown if the allowlist already holds MAX_NOMINATION_ALLOWLIST_LEN coldkeys.
+    ///
+    pub fn do_set_nomination_allowed(
+        origin: T::RuntimeOrigin,
+        hotkey: T::AccountId,
+        nominator: T::AccountId,
+        allowed: bool,
+    ) -> dispatch::DispatchResult {
+        // --- 1. Ensure the caller owns the delegate.
+        let coldkey = ensure_signed(origin)?;
+        Self::ensure_delegate_owner(&coldkey, &hotkey)?;
+
+        // --- 2. Update the allowlist, which holds at most MAX_NOMINATION_ALLOWLIST_LEN coldkeys.
+        let listed: bool = NominationAllowlist::<T>::get(&hotkey, &nominator);
+        if allowed && !listed {
+            let len: u32 = NominationAllowlistLen::<T>::get(&hotkey);
+            ensure!(
+                len < MAX_NOMINATION_ALLOWLIST_LEN,
+                Error::<T>::NominationAllowlistFull
+            );
+            NominationAllowlist::<T>::insert(&hotkey, &nominator, true);
+            NominationAllowlistLen::<T>::insert(&hotkey, len.saturating_add(1));
+        } else if !allowed && listed {
+            NominationAllowlist::<T>::remove(&hotkey, &nominator);
+            NominationAllowlistLen::<T>::mutate(&hotkey, |len| *len = len.saturating_sub(1));
+        }
+
+        // --- 3. Emit the event.
+        log::info!(
+            "NominationAllowlistUpdated( hotkey:{:?}, nominator:{:?}, allowed:{:?} )",
+            hotkey,
+            nominator,
+            allowed
+        );
+        Self::deposit_event(Event::NominationAllowlistUpdated {
+            hotkey,
+            nominator,
+            allowed,
+        });
+
+        // --- 4. Ok and return.
+        Ok(())
+    }
+
+    /// ---- The implementation for the extrinsic set_nomination_limits: Caps the nominations of the delegate.
+    ///
+    /// # Args:
+    /// * 'origin': (<T as frame_system::Config>RuntimeOrigin):
+    ///     -  The signature of the caller's coldkey.
+    ///
+    /// * 'hotkey' (T::AccountId):
+    ///     -  The delegate hotkey.
+    ///
+    /// * 'max_nominators' (u32):
+    ///     -  The maximum number of nominators, zero is uncapped.
+    ///
+    /// * 'max_nominated_stake' (u64):
+    ///     -  The maximum total stake of the nominators, zero is uncapped.
+    ///
+    /// # Event:
+    /// * NominationLimitsSet;
+    ///     -  On successfully setting the caps.
+    ///
+    /// # Raises:
+    /// * 'HotKeyAccountNotExists':
+    ///     -  Thrown if the hotkey is not registered.
+    ///
+    /// * 'NonAssociatedColdKey':
+    ///     -  Thrown if the caller does not own the hotkey.
+    ///
+    /// * 'HotKeyNotDelegateAndSignerNotOwnHotKey':
+    ///     -  Thrown if the hotkey is not a delegate.
+    ///
+    pub fn do_set_nomination_limits(
+        origin: T::RuntimeOrigin,
+        hotkey: T::AccountId,
+        max_nominators: u32,
+        max_nominated_stake: u64,
+    ) -> dispatch::DispatchResult {
+        // --- 1. Ensure the caller owns the delegate.
+        let coldkey = ensure_signed(origin)?;
+        Self::ensure_delegate_owner(&coldkey, &hotkey)?;
+
+        // --- 2. Store the caps, an uncapped delegate needs no entry.
+        if max_nominators == 0 && max_nominated_stake == 0 {
+            NominationLimits::<T>::remove(&hotkey);
+        } else {
+            NominationLimits::<T>::insert(&hotkey, (max_nominators, max_nominated_stake));
+        }
+
+        // --- 3. Emit the event.
+        log::info!(
+            "NominationLimitsSet( hotkey:{:?}, max_nominators:{:?}, max_nominated_stake:{:?} )",
+            hotkey,
+            max_nominators,
+            max_nominated_stake
+        );
+        Self::deposit_event(Event::NominationLimitsSet {
+            hotkey,
+            max_nominators,
+            max_nominated_stake,
+        });
+
+        // --- 4. Ok and return.
+        Ok(())
+    }
+
+    // Ensures the coldkey may manage the nomination settings of the delegate hotkey.
+    //
+    fn ensure_delegate_owner(
+        coldkey: &T::AccountId,
+        hotkey: &T::AccountId,
+    ) -> dispatch::DispatchResult {
+        ensure!(
+            !Self::coldkey_in_arbitration(coldkey),
+            Error::<T>::ColdkeyIsInArbitration
+        );
+        Self::do_take_checks(coldkey, hotkey)?;
+        ensure!(
+            Self::hotkey_is_delegate(hotkey),
+            Error::<T>::HotKeyNotDelegateAndSignerNotOwnHotKey
+        );
+        Ok(())
+    }
+
+    /// Ensures the delegate accepts a nomination of `amount` from the coldkey.
+    ///
+    /// Checks the allowlist of the delegate, the number of nominators when the coldkey is not
+    /// yet nominating and the total stake of the nominators. Stake of the owner is not capped.
+    pub fn ensure_nomination_allowed(
+        hotkey: &T::AccountId,
+        coldkey: &T::AccountId,
+        amount: u64,
+    ) -> dispatch::DispatchResult {
+        if NominationAllowlistEnabled::<T>::get(hotkey) {
+            ensure!(
+                NominationAllowlist::<T>::get(hotkey, coldkey),
+                Error::<T>::NominatorNotAllowlisted
+            );
+        }
+
+        let (max_nominators, max_nominated_stake) = NominationLimits::<T>::get(hotkey);
+        if max_nominators == 0 && max_nominated_stake == 0 {
+            return Ok(());
+        }
+
+        let owner: T::AccountId = Self::get_owning_coldkey_for_hotkey(hotkey);
+        if max_nominators > 0 && Stake::<T>::get(hotkey, coldkey) == 0 {
+            // The count includes the owner whenever it stakes to its own hotkey.
+            let mut nominators: u32 = DelegateNominatorCount::<T>::get(hotkey);
+            if Stake::<T>::get(hotkey, &owner) > 0 {
+                nominators = nominators.saturating_sub(1);
+            }
+            ensure!(
+                nominators < max_nominators,
+                Error::<T>::DelegateNominatorLimitReached
+            );
+        }
+
+        if max_nominated_stake > 0 {
+            let nominated_stake = Self::get_total_stake_for_hotkey(hotkey)
+                .saturating_sub(Stake::<T>::get(hotkey, &owner));
+            ensure!(
+                nominated_stake.saturating_add(amount) <= max_nominated_stake,
+                Error::<T>::DelegateNominatedStakeLimitReached
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Pays the emission earned by a nomination to its reward destination.
+    ///
+    /// Compounding nominations have the emission added to their stake, otherwise it is
+    /// credited to the free balance of the payee.
+    pub fn pay_nominator_reward(coldkey: &T::AccountId, hotkey: &T::AccountId, amount: u64) {
+        let payee: T::AccountId = match RewardDestinations::<T>::get(coldkey, hotkey) {
+            RewardDestination::Compound => {
+                Self::increase_stake_on_coldkey_hotkey_account(coldkey, hotkey, amount);
+                return;
+            }
+            RewardDestination::FreeBalance => coldkey.clone(),
+            RewardDestination::Account(payee) => payee,
+        };
+        if amount == 0 {
+            return;
+        }
+
+        Self::add_balance_to_coldkey_account(&payee, amount);
+        Self::deposit_event(Event::NominatorRewardPaid {
+            coldkey: coldkey.clone(),
+            hotkey: hotkey.clone(),
+            payee,
+            amount,
+        });
+    }
+
     // Returns true if the passed hotkey allow delegative staking.
     //
     pub fn hotkey_is_delegate(hotkey: &T::AccountId) -> bool {
@@ -574,6 +1454,103 @@ impl<T: Config> Pallet<T> {
         TargetStakesPerInterval::<T>::get()
     }
 
+    /// Ensures a staking operation of `amount` on the pair is within the selected rate limit.
+    ///
+    /// With the count based limiter `stakes_this_interval` must be below the target, with the
+    /// amount based limiter the stake moved within the window plus `amount` must not exceed
+    /// the maximum.
+    pub fn ensure_stake_rate_limit(
+        coldkey: &T::AccountId,
+        hotkey: &T::AccountId,
+        stakes_this_interval: u64,
+        amount: u64,
+        error: Error<T>,
+    ) -> Result<(), Error<T>> {
+        match StakeRateLimitSelector::<T>::get() {
+            StakeRateLimitMode::Count => ensure!(
+                stakes_this_interval < Self::get_target_stakes_per_interval(),
+                error
+            ),
+            StakeRateLimitMode::Amount => ensure!(
+                Self::get_stake_amount_this_window_for_coldkey_hotkey(coldkey, hotkey)
+                    .saturating_add(amount)
+                    <= MaxStakeAmountPerWindow::<T>::get(),
+                error
+            ),
+        }
+        Ok(())
+    }
+
+    // Returns the (window, amount_this_window, amount_last_window) of the pair rolled forward
+    // to the window of the current block.
+    fn get_rolled_stake_amounts(coldkey: &T::AccountId, hotkey: &T::AccountId) -> (u64, u64, u64) {
+        let window_length = StakeAmountWindow::<T>::get().max(1);
+        let current_window = Self::get_current_block_as_u64()
+            .checked_div(window_length)
+            .unwrap_or_default();
+        let (window, amount_this_window, amount_last_window) =
+            TotalHotkeyColdkeyStakeAmountThisWindow::<T>::get(coldkey, hotkey);
+
+        if window == current_window {
+            (window, amount_this_window, amount_last_window)
+        } else if window.saturating_add(1) == current_window {
+            (current_window, 0, amount_this_window)
+        } else {
+            (current_window, 0, 0)
+        }
+    }
+
+    /// Returns the stake moved by the pair within the sliding window ending at the current block.
+    ///
+    /// The amount of the previous window is weighted by the part of it still inside the
+    /// sliding window.
+    pub fn get_stake_amount_this_window_for_coldkey_hotkey(
+        coldkey: &T::AccountId,
+        hotkey: &T::AccountId,
+    ) -> u64 {
+        let window_length = StakeAmountWindow::<T>::get().max(1);
+        let (_, amount_this_window, amount_last_window) =
+            Self::get_rolled_stake_amounts(coldkey, hotkey);
+
+        let blocks_into_window = Self::get_current_block_as_u64()
+            .checked_rem(window_length)
+            .unwrap_or_default();
+        let overlap = window_length.saturating_sub(blocks_into_window);
+        let weighted_last_window: u64 = (amount_last_window as u128)
+            .saturating_mul(overlap as u128)
+            .checked_div(window_length as u128)
+            .unwrap_or_default()
+            .try_into()
+            .unwrap_or(u64::MAX);
+
+        amount_this_window.saturating_add(weighted_last_window)
+    }
+
+    /// Adds `amount` to the stake moved by the pair in the current window.
+    ///
+    /// The moved stake is only tracked while the amount based limiter is selected, so the
+    /// window starts empty when governance switches to it.
+    pub fn record_stake_amount_for_coldkey_hotkey(
+        coldkey: &T::AccountId,
+        hotkey: &T::AccountId,
+        amount: u64,
+    ) {
+        if StakeRateLimitSelector::<T>::get() != StakeRateLimitMode::Amount {
+            return;
+        }
+        let (window, amount_this_window, amount_last_window) =
+            Self::get_rolled_stake_amounts(coldkey, hotkey);
+        TotalHotkeyColdkeyStakeAmountThisWindow::<T>::insert(
+            coldkey,
+            hotkey,
+            (
+                window,
+                amount_this_window.saturating_add(amount),
+                amount_last_window,
+            ),
+        );
+    }
+
     // Creates a cold - hot pairing account if the hotkey is not already an active account.
     //
     pub fn create_account_if_non_existent(coldkey: &T::AccountId, hotkey: &T::AccountId) {
@@ -667,11 +1644,10 @@ impl<T: Config> Pallet<T> {
             hotkey,
             TotalHotkeyStake::<T>::get(hotkey).saturating_add(increment),
         );
-        Stake::<T>::insert(
-            hotkey,
-            coldkey,
-            Stake::<T>::get(hotkey, coldkey).saturating_add(increment),
-        );
+        let current_stake: u64 = Stake::<T>::get(hotkey, coldkey);
+        let new_stake: u64 = current_stake.saturating_add(increment);
+        Stake::<T>::insert(hotkey, coldkey, new_stake);
+        Self::update_delegate_nominator_count(hotkey, current_stake, new_stake);
         TotalStake::<T>::put(TotalStake::<T>::get().saturating_add(increment));
 
         // Update StakingHotkeys map
@@ -694,16 +1670,35 @@ impl<T: Config> Pallet<T> {
             hotkey,
             TotalHotkeyStake::<T>::get(hotkey).saturating_sub(decrement),
         );
-        Stake::<T>::insert(
-            hotkey,
-            coldkey,
-            Stake::<T>::get(hotkey, coldkey).saturating_sub(decrement),
-        );
+        let current_stake: u64 = Stake::<T>::get(hotkey, coldkey);
+        let new_stake: u64 = current_stake.saturating_sub(decrement);
+        Stake::<T>::insert(hotkey, coldkey, new_stake);
+        Self::update_delegate_nominator_count(hotkey, current_stake, new_stake);
         TotalStake::<T>::put(TotalStake::<T>::get().saturating_sub(decrement));
 
+        // The reward destination goes with the nomination.
+        if new_stake == 0 {
+            RewardDestinations::<T>::remove(coldkey, hotkey);
+        }
+
         // TODO: Tech debt: Remove StakingHotkeys entry if stake goes to 0
     }
 
+    /// Keeps DelegateNominatorCount in step with a change of the stake of a coldkey on the
+    /// hotkey, counting the coldkey while its stake is non-zero.
+    ///
+    pub fn update_delegate_nominator_count(
+        hotkey: &T::AccountId,
+        current_stake: u64,
+        new_stake: u64,
+    ) {
+        if current_stake == 0 && new_stake > 0 {
+            DelegateNominatorCount::<T>::mutate(hotkey, |count| *count = count.saturating_add(1));
+        } else if current_stake > 0 && new_stake == 0 {
+            DelegateNominatorCount::<T>::mutate(hotkey, |count| *count = count.saturating_sub(1));
+        }
+    }
+
     /// Empties the stake associated with a given coldkey-hotkey account pairing.
     /// This function retrieves the current stake for the specified coldkey-hotkey pairing,
     /// then subtracts this stake amount from both the TotalColdkeyStake and TotalHotkeyStake.
@@ -724,6 +1719,8 @@ impl<T: Config> Pallet<T> {
         TotalColdkeyStake::<T>::mutate(coldkey, |old| *old = old.saturating_sub(current_stake));
         TotalHotkeyStake::<T>::mutate(hotkey, |stake| *stake = stake.saturating_sub(current_stake));
         Stake::<T>::remove(hotkey, coldkey);
+        Self::update_delegate_nominator_count(hotkey, current_stake, 0);
+        RewardDestinations::<T>::remove(coldkey, hotkey);
         TotalStake::<T>::mutate(|stake| *stake = stake.saturating_sub(current_stake));
         TotalIssuance::<T>::mutate(|issuance| *issuance = issuance.saturating_sub(current_stake));
 
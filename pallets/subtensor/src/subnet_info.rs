@@ -3,6 +3,7 @@ use frame_support::pallet_prelude::{Decode, Encode};
 use frame_support::storage::IterableStorageMap;
 extern crate alloc;
 use codec::Compact;
+use sp_core::H256;
 
 #[freeze_struct("fe79d58173da662a")]
 #[derive(Decode, Encode, PartialEq, Eq, Clone, Debug)]
@@ -27,7 +28,7 @@ pub struct SubnetInfo<T: Config> {
     owner: T::AccountId,
 }
 
-#[freeze_struct("55b472510f10e76a")]
+#[freeze_struct("5d4eaf3bb12d8995")]
 #[derive(Decode, Encode, PartialEq, Eq, Clone, Debug)]
 pub struct SubnetHyperparams {
     rho: Compact<u16>,
@@ -57,6 +58,19 @@ pub struct SubnetHyperparams {
     alpha_high: Compact<u16>,
     alpha_low: Compact<u16>,
     liquid_alpha_enabled: bool,
+    weights_max_version: Compact<u64>,
+    banned_weights_versions: Vec<Compact<u64>>,
+    weights_window: Compact<u64>,
+}
+
+#[freeze_struct("b21f337ab9b26001")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug)]
+pub struct WeightCommitInfo<T: Config> {
+    hotkey: T::AccountId,
+    commit_hash: H256,
+    commit_block: Compact<u64>,
+    reveal_start_block: Compact<u64>,
+    reveal_end_block: Compact<u64>,
 }
 
 impl<T: Config> Pallet<T> {
@@ -162,6 +176,9 @@ impl<T: Config> Pallet<T> {
         let commit_reveal_weights_enabled = Self::get_commit_reveal_weights_enabled(netuid);
         let liquid_alpha_enabled = Self::get_liquid_alpha_enabled(netuid);
         let (alpha_low, alpha_high): (u16, u16) = Self::get_alpha_values(netuid);
+        let weights_max_version = Self::get_weights_max_version_key(netuid);
+        let banned_weights_versions = Self::get_banned_weights_version_keys(netuid);
+        let weights_window = Self::get_weights_window(netuid);
 
         Some(SubnetHyperparams {
             rho: rho.into(),
@@ -191,6 +208,35 @@ impl<T: Config> Pallet<T> {
             alpha_high: alpha_high.into(),
             alpha_low: alpha_low.into(),
             liquid_alpha_enabled,
+            weights_max_version: weights_max_version.into(),
+            banned_weights_versions: banned_weights_versions
+                .into_iter()
+                .map(Compact::from)
+                .collect(),
+            weights_window: weights_window.into(),
         })
     }
+
+    /// Returns the pending weight commits on the subnet. A commit can be revealed from its
+    /// reveal_start_block up to, but excluding, its reveal_end_block.
+    pub fn get_pending_weight_commits(netuid: u16) -> Vec<WeightCommitInfo<T>> {
+        let mut pending: Vec<WeightCommitInfo<T>> = Vec::new();
+        for (hotkey, commits) in WeightCommits::<T>::iter_prefix(netuid) {
+            for (commit_hash, commit_block) in commits {
+                if Self::is_commit_expired(netuid, commit_block) {
+                    continue;
+                }
+                let (reveal_start_block, reveal_end_block) =
+                    Self::get_reveal_window(netuid, commit_block);
+                pending.push(WeightCommitInfo {
+                    hotkey: hotkey.clone(),
+                    commit_hash,
+                    commit_block: commit_block.into(),
+                    reveal_start_block: reveal_start_block.into(),
+                    reveal_end_block: reveal_end_block.into(),
+                });
+            }
+        }
+        pending
+    }
 }
@@ -63,7 +63,12 @@ impl<T: Config> Pallet<T> {
         Self::swap_owner(old_hotkey, new_hotkey, &coldkey, &mut weight);
         Self::swap_total_hotkey_stake(old_hotkey, new_hotkey, &mut weight);
         Self::swap_delegates(old_hotkey, new_hotkey, &mut weight);
+        Self::swap_pending_take_increase(old_hotkey, new_hotkey, &mut weight);
         Self::swap_stake(old_hotkey, new_hotkey, &mut weight);
+        Self::swap_reward_destinations(old_hotkey, new_hotkey, &mut weight);
+        Self::swap_nominator_rewards(old_hotkey, new_hotkey, &mut weight);
+        Self::swap_nomination_settings(old_hotkey, new_hotkey, &mut weight);
+        Self::swap_stake_amounts_this_window(old_hotkey, new_hotkey, &mut weight);
 
         // Store the value of is_network_member for the old key
         let netuid_is_member: Vec<u16> = Self::get_netuid_is_member(old_hotkey, &mut weight);
@@ -72,6 +77,7 @@ impl<T: Config> Pallet<T> {
         Self::swap_axons(old_hotkey, new_hotkey, &netuid_is_member, &mut weight);
         Self::swap_keys(old_hotkey, new_hotkey, &netuid_is_member, &mut weight);
         Self::swap_loaded_emission(old_hotkey, new_hotkey, &netuid_is_member, &mut weight);
+        Self::swap_staged_epoch_hotkeys(old_hotkey, new_hotkey, &netuid_is_member, &mut weight);
         Self::swap_uids(old_hotkey, new_hotkey, &netuid_is_member, &mut weight);
         Self::swap_prometheus(old_hotkey, new_hotkey, &netuid_is_member, &mut weight);
 
@@ -403,6 +409,11 @@ impl<T: Config> Pallet<T> {
             &mut weight,
         );
         Self::swap_subnet_owner_for_coldkey(old_coldkey, new_coldkey, &mut weight);
+        Self::swap_unbonding_queue_for_coldkey(old_coldkey, new_coldkey, &mut weight);
+        Self::swap_reward_destinations_for_coldkey(old_coldkey, new_coldkey, &mut weight);
+        Self::swap_nominator_rewards_for_coldkey(old_coldkey, new_coldkey, &mut weight);
+        Self::swap_nomination_allowlist_for_coldkey(old_coldkey, new_coldkey, &mut weight);
+        Self::swap_stake_amounts_this_window_for_coldkey(old_coldkey, new_coldkey, &mut weight);
 
         // Transfer any remaining balance from old_coldkey to new_coldkey
         let remaining_balance = Self::get_coldkey_balance(old_coldkey);
@@ -527,6 +538,33 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Swaps the announced take increase of the hotkey.
+    ///
+    /// # Arguments
+    ///
+    /// * `old_hotkey` - The old hotkey.
+    /// * `new_hotkey` - The new hotkey.
+    /// * `weight` - The weight of the transaction.
+    pub fn swap_pending_take_increase(
+        old_hotkey: &T::AccountId,
+        new_hotkey: &T::AccountId,
+        weight: &mut Weight,
+    ) {
+        if let Some((take, effective_block)) = PendingTakeIncreases::<T>::take(old_hotkey) {
+            PendingTakeIncreases::<T>::insert(new_hotkey, (take, effective_block));
+            TakeIncreasesAtBlock::<T>::mutate(effective_block, |hotkeys| {
+                for hotkey in hotkeys.iter_mut() {
+                    if hotkey == old_hotkey {
+                        *hotkey = new_hotkey.clone();
+                    }
+                }
+            });
+            weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 3));
+        } else {
+            weight.saturating_accrue(T::DbWeight::get().reads(1));
+        }
+    }
+
     /// Swaps the stake of the hotkey.
     ///
     /// # Arguments
@@ -561,6 +599,12 @@ impl<T: Config> Pallet<T> {
         let _ = Stake::<T>::clear_prefix(old_hotkey, stake_count, None);
         writes = writes.saturating_add(1); // One write for insert; // One write for clear_prefix
 
+        // The nominators move with the stake
+        let nominator_count: u32 = DelegateNominatorCount::<T>::take(old_hotkey);
+        DelegateNominatorCount::<T>::insert(new_hotkey, nominator_count);
+        writes = writes.saturating_add(2);
+        weight.saturating_accrue(T::DbWeight::get().reads(1));
+
         // TODO: Remove all entries for old hotkey from StakingHotkeys map
 
         weight.saturating_accrue(T::DbWeight::get().writes(writes));
@@ -675,6 +719,38 @@ impl<T: Config> Pallet<T> {
         weight.saturating_accrue(T::DbWeight::get().writes(netuid_is_member.len() as u64));
     }
 
+    /// Swaps the hotkey in the epochs staged ahead of tempo, which pay the hotkeys they gathered.
+    ///
+    /// # Arguments
+    ///
+    /// * `old_hotkey` - The old hotkey.
+    /// * `new_hotkey` - The new hotkey.
+    /// * `netuid_is_member` - A vector of network IDs where the hotkey is a member.
+    /// * `weight` - The weight of the transaction.
+    ///
+    pub fn swap_staged_epoch_hotkeys(
+        old_hotkey: &T::AccountId,
+        new_hotkey: &T::AccountId,
+        netuid_is_member: &[u16],
+        weight: &mut Weight,
+    ) {
+        for netuid in netuid_is_member {
+            let mut hotkeys: Vec<(u16, T::AccountId)> = StagedEpochHotkeys::<T>::get(netuid);
+            weight.saturating_accrue(T::DbWeight::get().reads(1));
+            let mut swapped: bool = false;
+            for (_, hotkey) in hotkeys.iter_mut() {
+                if *hotkey == *old_hotkey {
+                    *hotkey = new_hotkey.clone();
+                    swapped = true;
+                }
+            }
+            if swapped {
+                StagedEpochHotkeys::<T>::insert(netuid, hotkeys);
+                weight.saturating_accrue(T::DbWeight::get().writes(1));
+            }
+        }
+    }
+
     /// Swaps the UIDs of the hotkey.
     ///
     /// # Arguments
@@ -825,14 +901,21 @@ impl<T: Config> Pallet<T> {
             log::info!("Transferring stake for hotkey {:?}: {}", hotkey, stake);
             if stake > 0 {
                 // Insert the stake for the hotkey and new coldkey
+                let replaced_stake: u64 = Stake::<T>::get(hotkey, new_coldkey);
                 Stake::<T>::insert(hotkey, new_coldkey, stake);
                 total_transferred_stake = total_transferred_stake.saturating_add(stake);
 
+                // The new coldkey takes the place of the old one, unless it already staked here
+                if replaced_stake > 0 {
+                    Self::update_delegate_nominator_count(hotkey, stake, 0);
+                    weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+                }
+
                 // Update the owner of the hotkey to the new coldkey
                 Owner::<T>::insert(hotkey, new_coldkey);
 
                 // Update the transaction weight
-                weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 2));
+                weight.saturating_accrue(T::DbWeight::get().reads_writes(3, 2));
             }
         }
 
@@ -948,4 +1031,223 @@ impl<T: Config> Pallet<T> {
         }
         weight.saturating_accrue(T::DbWeight::get().reads(TotalNetworks::<T>::get() as u64));
     }
+
+    /// Swaps the reward destinations of the nominations on the hotkey.
+    ///
+    /// Must be called after the stake has been swapped to the new hotkey.
+    ///
+    /// # Arguments
+    ///
+    /// * `old_hotkey` - The old hotkey.
+    /// * `new_hotkey` - The new hotkey.
+    /// * `weight` - The weight of the transaction.
+    pub fn swap_reward_destinations(
+        old_hotkey: &T::AccountId,
+        new_hotkey: &T::AccountId,
+        weight: &mut Weight,
+    ) {
+        let coldkeys: Vec<T::AccountId> = Stake::<T>::iter_key_prefix(new_hotkey).collect();
+        weight.saturating_accrue(T::DbWeight::get().reads(coldkeys.len() as u64));
+        for coldkey in coldkeys {
+            if let Ok(destination) = RewardDestinations::<T>::try_get(&coldkey, old_hotkey) {
+                RewardDestinations::<T>::remove(&coldkey, old_hotkey);
+                RewardDestinations::<T>::insert(&coldkey, new_hotkey, destination);
+                weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 2));
+            } else {
+                weight.saturating_accrue(T::DbWeight::get().reads(1));
+            }
+        }
+    }
+
+    /// Swaps the reward destinations set by the old coldkey to the new coldkey.
+    ///
+    /// # Arguments
+    ///
+    /// * `old_coldkey` - The AccountId of the old coldkey.
+    /// * `new_coldkey` - The AccountId of the new coldkey.
+    /// * `weight` - Mutable reference to the weight of the transaction.
+    pub fn swap_reward_destinations_for_coldkey(
+        old_coldkey: &T::AccountId,
+        new_coldkey: &T::AccountId,
+        weight: &mut Weight,
+    ) {
+        let destinations: Vec<(T::AccountId, RewardDestination<T::AccountId>)> =
+            RewardDestinations::<T>::drain_prefix(old_coldkey).collect();
+        weight.saturating_accrue(
+            T::DbWeight::get().reads_writes(destinations.len() as u64, destinations.len() as u64),
+        );
+        for (hotkey, destination) in destinations {
+            RewardDestinations::<T>::insert(new_coldkey, &hotkey, destination);
+            weight.saturating_accrue(T::DbWeight::get().writes(1));
+        }
+    }
+
+    /// Swaps the rewards ledger of the nominations on the hotkey.
+    ///
+    /// Ledgers already recorded on the new hotkey are merged rather than overwritten.
+    ///
+    /// # Arguments
+    ///
+    /// * `old_hotkey` - The old hotkey.
+    /// * `new_hotkey` - The new hotkey.
+    /// * `weight` - The weight of the transaction.
+    pub fn swap_nominator_rewards(
+        old_hotkey: &T::AccountId,
+        new_hotkey: &T::AccountId,
+        weight: &mut Weight,
+    ) {
+        let coldkeys: Vec<T::AccountId> =
+            NominatorRewards::<T>::iter_key_prefix(old_hotkey).collect();
+        weight.saturating_accrue(T::DbWeight::get().reads(coldkeys.len() as u64));
+        for coldkey in coldkeys {
+            Self::merge_nominator_rewards(old_hotkey, &coldkey, new_hotkey, &coldkey, weight);
+        }
+    }
+
+    /// Swaps the rewards ledger of the old coldkey to the new coldkey.
+    ///
+    /// Ledgers already recorded for the new coldkey are merged rather than overwritten.
+    ///
+    /// # Arguments
+    ///
+    /// * `old_coldkey` - The AccountId of the old coldkey.
+    /// * `new_coldkey` - The AccountId of the new coldkey.
+    /// * `weight` - Mutable reference to the weight of the transaction.
+    pub fn swap_nominator_rewards_for_coldkey(
+        old_coldkey: &T::AccountId,
+        new_coldkey: &T::AccountId,
+        weight: &mut Weight,
+    ) {
+        let hotkeys: Vec<T::AccountId> = NominatorRewardHotkeys::<T>::take(old_coldkey);
+        weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+        for hotkey in hotkeys {
+            Self::merge_nominator_rewards(&hotkey, old_coldkey, &hotkey, new_coldkey, weight);
+        }
+    }
+
+    /// Swaps the nomination allowlist and caps of the delegate hotkey.
+    ///
+    /// # Arguments
+    ///
+    /// * `old_hotkey` - The old hotkey.
+    /// * `new_hotkey` - The new hotkey.
+    /// * `weight` - The weight of the transaction.
+    pub fn swap_nomination_settings(
+        old_hotkey: &T::AccountId,
+        new_hotkey: &T::AccountId,
+        weight: &mut Weight,
+    ) {
+        if NominationAllowlistEnabled::<T>::take(old_hotkey) {
+            NominationAllowlistEnabled::<T>::insert(new_hotkey, true);
+            weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 2));
+        } else {
+            weight.saturating_accrue(T::DbWeight::get().reads(1));
+        }
+
+        let allowlist_len: u32 = NominationAllowlistLen::<T>::take(old_hotkey);
+        NominationAllowlistLen::<T>::insert(new_hotkey, allowlist_len);
+        weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 2));
+
+        if let Ok(limits) = NominationLimits::<T>::try_get(old_hotkey) {
+            NominationLimits::<T>::remove(old_hotkey);
+            NominationLimits::<T>::insert(new_hotkey, limits);
+            weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 2));
+        } else {
+            weight.saturating_accrue(T::DbWeight::get().reads(1));
+        }
+
+        let allowlist: Vec<(T::AccountId, bool)> =
+            NominationAllowlist::<T>::drain_prefix(old_hotkey).collect();
+        weight.saturating_accrue(
+            T::DbWeight::get().reads_writes(allowlist.len() as u64, allowlist.len() as u64),
+        );
+        for (nominator, allowed) in allowlist {
+            NominationAllowlist::<T>::insert(new_hotkey, &nominator, allowed);
+            weight.saturating_accrue(T::DbWeight::get().writes(1));
+        }
+    }
+
+    /// Swaps the nomination allowlist entries of the old coldkey to the new coldkey.
+    ///
+    /// Only the delegates the coldkey stakes to are visited, so this must be called after the
+    /// staking hotkeys have been swapped to the new coldkey.
+    ///
+    /// # Arguments
+    ///
+    /// * `old_coldkey` - The AccountId of the old coldkey.
+    /// * `new_coldkey` - The AccountId of the new coldkey.
+    /// * `weight` - Mutable reference to the weight of the transaction.
+    pub fn swap_nomination_allowlist_for_coldkey(
+        old_coldkey: &T::AccountId,
+        new_coldkey: &T::AccountId,
+        weight: &mut Weight,
+    ) {
+        let hotkeys: Vec<T::AccountId> = StakingHotkeys::<T>::get(new_coldkey);
+        weight.saturating_accrue(T::DbWeight::get().reads(1));
+        for hotkey in hotkeys {
+            if NominationAllowlist::<T>::take(&hotkey, old_coldkey) {
+                // Both coldkeys may be on the allowlist, keep a single entry
+                if NominationAllowlist::<T>::get(&hotkey, new_coldkey) {
+                    NominationAllowlistLen::<T>::mutate(&hotkey, |len| {
+                        *len = len.saturating_sub(1)
+                    });
+                    weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+                }
+                NominationAllowlist::<T>::insert(&hotkey, new_coldkey, true);
+                weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 2));
+            } else {
+                weight.saturating_accrue(T::DbWeight::get().reads(1));
+            }
+        }
+    }
+
+    /// Swaps the stake moved within the current window by the nominators of the hotkey.
+    ///
+    /// Must be called after the stake has been swapped to the new hotkey.
+    ///
+    /// # Arguments
+    ///
+    /// * `old_hotkey` - The old hotkey.
+    /// * `new_hotkey` - The new hotkey.
+    /// * `weight` - The weight of the transaction.
+    pub fn swap_stake_amounts_this_window(
+        old_hotkey: &T::AccountId,
+        new_hotkey: &T::AccountId,
+        weight: &mut Weight,
+    ) {
+        for coldkey in Stake::<T>::iter_key_prefix(new_hotkey) {
+            if let Ok(amounts) =
+                TotalHotkeyColdkeyStakeAmountThisWindow::<T>::try_get(&coldkey, old_hotkey)
+            {
+                TotalHotkeyColdkeyStakeAmountThisWindow::<T>::remove(&coldkey, old_hotkey);
+                TotalHotkeyColdkeyStakeAmountThisWindow::<T>::insert(&coldkey, new_hotkey, amounts);
+                weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 2));
+            } else {
+                weight.saturating_accrue(T::DbWeight::get().reads(2));
+            }
+        }
+    }
+
+    /// Swaps the stake moved within the current window by the old coldkey to the new coldkey.
+    ///
+    /// # Arguments
+    ///
+    /// * `old_coldkey` - The AccountId of the old coldkey.
+    /// * `new_coldkey` - The AccountId of the new coldkey.
+    /// * `weight` - Mutable reference to the weight of the transaction.
+    pub fn swap_stake_amounts_this_window_for_coldkey(
+        old_coldkey: &T::AccountId,
+        new_coldkey: &T::AccountId,
+        weight: &mut Weight,
+    ) {
+        let amounts: Vec<(T::AccountId, (u64, u64, u64))> =
+            TotalHotkeyColdkeyStakeAmountThisWindow::<T>::drain_prefix(old_coldkey).collect();
+        weight.saturating_accrue(
+            T::DbWeight::get().reads_writes(amounts.len() as u64, amounts.len() as u64),
+        );
+        for (hotkey, amount) in amounts {
+            TotalHotkeyColdkeyStakeAmountThisWindow::<T>::insert(new_coldkey, &hotkey, amount);
+            weight.saturating_accrue(T::DbWeight::get().writes(1));
+        }
+    }
 }
@@ -45,6 +45,7 @@ impl<T: Config> Pallet<T> {
         Uids::<T>::insert(netuid, new_hotkey.clone(), uid_to_replace); // Make uid - hotkey association.
         BlockAtRegistration::<T>::insert(netuid, uid_to_replace, block_number); // Fill block at registration.
         IsNetworkMember::<T>::insert(new_hotkey.clone(), netuid, true); // Fill network is member.
+        ValidatorAgreement::<T>::remove(netuid, uid_to_replace); // Reset the agreement metrics.
     }
 
     /// Appends the uid to the network.
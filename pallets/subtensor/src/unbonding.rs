@@ -0,0 +1,272 @@
+use super::*;
+use frame_support::weights::Weight;
+use sp_core::Get;
+
+impl<T: Config> Pallet<T> {
+    /// ---- The implementation for the extrinsic claim_unbonded: Releases all matured unbonding stake to the coldkey.
+    ///
+    /// # Args:
+    /// * 'origin': (<T as frame_system::Config>RuntimeOrigin):
+    ///     -  The signature of the caller's coldkey.
+    ///
+    /// # Event:
+    /// * UnbondedStakeReleased;
+    ///     -  On successfully releasing the matured stake to the coldkey balance.
+    ///
+    /// # Raises:
+    /// * 'NoUnbondedStakeToClaim':
+    ///     -  Thrown if the coldkey has no matured unbonding stake.
+    ///
+    pub fn do_claim_unbonded(origin: T::RuntimeOrigin) -> dispatch::DispatchResult {
+        // --- 1. We check the transaction is signed by the caller and retrieve the T::AccountId coldkey information.
+        let coldkey = ensure_signed(origin)?;
+        log::info!("do_claim_unbonded( origin:{:?} )", coldkey);
+
+        // --- 2. Release the matured chunks, there must be something to release.
+        let released = Self::release_unbonded_stake(&coldkey);
+        ensure!(released > 0, Error::<T>::NoUnbondedStakeToClaim);
+
+        // --- 3. Ok and return.
+        Ok(())
+    }
+
+    /// Returns the unbonding period which applies to stake removed from the hotkey.
+    ///
+    /// This is the largest of the global unbonding period and the unbonding periods of
+    /// all networks the hotkey is registered on.
+    pub fn get_unbonding_period_for_hotkey(hotkey: &T::AccountId) -> u64 {
+        Self::get_registered_networks_for_hotkey(hotkey)
+            .into_iter()
+            .map(NetworkUnbondingPeriod::<T>::get)
+            .fold(UnbondingPeriod::<T>::get(), |period, network_period| {
+                period.max(network_period)
+            })
+    }
+
+    /// Ensures the coldkey can queue another unbonding chunk.
+    ///
+    /// Chunks which have already matured are not counted, they are released before the new chunk is queued.
+    pub fn ensure_can_unbond(coldkey: &T::AccountId) -> dispatch::DispatchResult {
+        let current_block: u64 = Self::get_current_block_as_u64();
+        let pending_chunks = UnbondingQueue::<T>::get(coldkey)
+            .iter()
+            .filter(|(_, unlock_block)| *unlock_block > current_block)
+            .count();
+        ensure!(
+            pending_chunks < MaxUnbondingChunks::<T>::get() as usize,
+            Error::<T>::UnbondingQueueFull
+        );
+        Ok(())
+    }
+
+    /// Queues removed stake for release to the coldkey once the unbonding period has passed.
+    ///
+    /// Matured chunks are released first. Stake unlocking on the same block is merged into one chunk.
+    pub fn schedule_unbonding(
+        coldkey: &T::AccountId,
+        hotkey: &T::AccountId,
+        amount: u64,
+        unbonding_period: u64,
+    ) {
+        Self::release_unbonded_stake(coldkey);
+
+        let current_block: u64 = Self::get_current_block_as_u64();
+        let unlock_block: u64 = current_block.saturating_add(unbonding_period);
+
+        UnbondingQueue::<T>::mutate(coldkey, |queue| {
+            if let Some(chunk) = queue.iter_mut().find(|(_, block)| *block == unlock_block) {
+                chunk.0 = chunk.0.saturating_add(amount);
+            } else {
+                queue.push((amount, unlock_block));
+            }
+        });
+
+        ColdkeysToUnbondAtBlock::<T>::mutate(unlock_block, |coldkeys| {
+            if !coldkeys.contains(coldkey) {
+                coldkeys.push(coldkey.clone());
+            }
+        });
+
+        // Start the on_idle release from the first block anything is unbonded at.
+        if UnbondingReleaseCursor::<T>::get() == 0 {
+            UnbondingReleaseCursor::<T>::put(current_block);
+        }
+        UnbondingLastUnlockBlock::<T>::mutate(|last| *last = (*last).max(unlock_block));
+
+        log::info!(
+            "StakeUnbonding( coldkey:{:?}, hotkey:{:?}, amount:{:?}, unlock_block:{:?} )",
+            coldkey,
+            hotkey,
+            amount,
+            unlock_block
+        );
+        Self::deposit_event(Event::StakeUnbonding {
+            coldkey: coldkey.clone(),
+            hotkey: hotkey.clone(),
+            amount,
+            unlock_block,
+        });
+    }
+
+    /// Releases all matured unbonding chunks of the coldkey to its balance.
+    ///
+    /// Returns the amount released.
+    pub fn release_unbonded_stake(coldkey: &T::AccountId) -> u64 {
+        let current_block: u64 = Self::get_current_block_as_u64();
+        let (matured, pending): (Vec<(u64, u64)>, Vec<(u64, u64)>) =
+            UnbondingQueue::<T>::get(coldkey)
+                .into_iter()
+                .partition(|(_, unlock_block)| *unlock_block <= current_block);
+
+        if matured.is_empty() {
+            return 0;
+        }
+
+        if pending.is_empty() {
+            UnbondingQueue::<T>::remove(coldkey);
+        } else {
+            UnbondingQueue::<T>::insert(coldkey, pending);
+        }
+
+        let amount: u64 = matured
+            .iter()
+            .fold(0u64, |total, (chunk, _)| total.saturating_add(*chunk));
+        Self::add_balance_to_coldkey_account(coldkey, amount);
+
+        log::info!(
+            "UnbondedStakeReleased( coldkey:{:?}, amount:{:?} )",
+            coldkey,
+            amount
+        );
+        Self::deposit_event(Event::UnbondedStakeReleased {
+            coldkey: coldkey.clone(),
+            amount,
+        });
+
+        amount
+    }
+
+    /// Releases unbonded stake for the coldkeys scheduled on the blocks up to the current one,
+    /// stopping once the remaining weight is used. The cursor is reset once it has passed the
+    /// last block anything unlocks at. Returns the weight used.
+    pub fn release_unbonded_stake_on_idle(remaining_weight: Weight) -> Weight {
+        let mut weight_used: Weight = T::DbWeight::get().reads(1);
+
+        // Nothing is unbonding.
+        let mut cursor: u64 = UnbondingReleaseCursor::<T>::get();
+        if cursor == 0 {
+            return weight_used;
+        }
+
+        // Reading and writing the queue, and crediting the balance.
+        let weight_per_coldkey: Weight = T::DbWeight::get().reads_writes(2, 2);
+        // Reading and clearing the schedule of a block.
+        let weight_per_block: Weight = T::DbWeight::get().reads_writes(1, 1);
+        // Always leave room to check for a full pass and store the cursor.
+        let limit: Weight = remaining_weight.saturating_sub(T::DbWeight::get().reads_writes(1, 2));
+
+        let current_block: u64 = Self::get_current_block_as_u64();
+        'blocks: while cursor <= current_block {
+            if weight_used.saturating_add(weight_per_block).any_gt(limit) {
+                break;
+            }
+            weight_used.saturating_accrue(weight_per_block);
+
+            let mut coldkeys: Vec<T::AccountId> = ColdkeysToUnbondAtBlock::<T>::get(cursor);
+            while let Some(coldkey) = coldkeys.pop() {
+                if weight_used.saturating_add(weight_per_coldkey).any_gt(limit) {
+                    // Keep the rest of this block for the next on_idle.
+                    coldkeys.push(coldkey);
+                    ColdkeysToUnbondAtBlock::<T>::insert(cursor, coldkeys);
+                    break 'blocks;
+                }
+                Self::release_unbonded_stake(&coldkey);
+                weight_used.saturating_accrue(weight_per_coldkey);
+            }
+
+            ColdkeysToUnbondAtBlock::<T>::remove(cursor);
+            cursor = cursor.saturating_add(1);
+        }
+
+        // Everything scheduled has been released, start over on the next unbonding.
+        weight_used.saturating_accrue(T::DbWeight::get().reads(1));
+        if cursor > UnbondingLastUnlockBlock::<T>::get() {
+            UnbondingReleaseCursor::<T>::kill();
+            UnbondingLastUnlockBlock::<T>::kill();
+            return weight_used.saturating_add(T::DbWeight::get().writes(2));
+        }
+
+        UnbondingReleaseCursor::<T>::put(cursor);
+        weight_used.saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// Moves the unbonding queue of the old coldkey to the new coldkey.
+    ///
+    /// Chunks of both queues which have matured are released to the new coldkey. Pending chunks
+    /// are scheduled for release under the new coldkey. If the merged queue holds more than
+    /// `MaxUnbondingChunks` pending chunks, the earliest chunks are merged into the chunk unlocking
+    /// after them, so no stake is released early.
+    pub fn swap_unbonding_queue_for_coldkey(
+        old_coldkey: &T::AccountId,
+        new_coldkey: &T::AccountId,
+        weight: &mut Weight,
+    ) {
+        let old_queue: Vec<(u64, u64)> = UnbondingQueue::<T>::take(old_coldkey);
+        weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+        if old_queue.is_empty() {
+            return;
+        }
+
+        // --- 1. Merge the queues, stake unlocking on the same block is merged into one chunk.
+        let mut new_queue: Vec<(u64, u64)> = UnbondingQueue::<T>::get(new_coldkey);
+        for (amount, unlock_block) in old_queue {
+            if let Some(chunk) = new_queue
+                .iter_mut()
+                .find(|(_, block)| *block == unlock_block)
+            {
+                chunk.0 = chunk.0.saturating_add(amount);
+            } else {
+                new_queue.push((amount, unlock_block));
+            }
+        }
+
+        // --- 2. Fold the earliest pending chunks into the next ones until the queue fits.
+        let current_block: u64 = Self::get_current_block_as_u64();
+        let (matured, mut pending): (Vec<(u64, u64)>, Vec<(u64, u64)>) = new_queue
+            .into_iter()
+            .partition(|(_, unlock_block)| *unlock_block <= current_block);
+        pending.sort_by_key(|(_, unlock_block)| *unlock_block);
+        let max_chunks: usize = (MaxUnbondingChunks::<T>::get() as usize).max(1);
+        while pending.len() > max_chunks {
+            let (amount, _) = pending.remove(0);
+            if let Some(chunk) = pending.first_mut() {
+                chunk.0 = chunk.0.saturating_add(amount);
+            }
+        }
+
+        // --- 3. Schedule the pending chunks for release under the new coldkey.
+        for (_, unlock_block) in pending.iter() {
+            ColdkeysToUnbondAtBlock::<T>::mutate(unlock_block, |coldkeys| {
+                if !coldkeys.contains(new_coldkey) {
+                    coldkeys.push(new_coldkey.clone());
+                }
+            });
+            weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+        }
+        if let Some((_, last_unlock_block)) = pending.last() {
+            if UnbondingReleaseCursor::<T>::get() == 0 {
+                UnbondingReleaseCursor::<T>::put(current_block);
+            }
+            UnbondingLastUnlockBlock::<T>::mutate(|last| *last = (*last).max(*last_unlock_block));
+            weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 2));
+        }
+        weight.saturating_accrue(T::DbWeight::get().reads(2));
+
+        // --- 4. Store the queue and release the matured chunks to the new coldkey.
+        let mut queue: Vec<(u64, u64)> = matured;
+        queue.append(&mut pending);
+        UnbondingQueue::<T>::insert(new_coldkey, queue);
+        Self::release_unbonded_stake(new_coldkey);
+        weight.saturating_accrue(T::DbWeight::get().reads_writes(3, 3));
+    }
+}
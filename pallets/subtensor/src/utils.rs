@@ -402,6 +402,63 @@ impl<T: Config> Pallet<T> {
         Self::deposit_event(Event::WeightsVersionKeySet(netuid, weights_version_key));
     }
 
+    pub fn get_weights_window(netuid: u16) -> u64 {
+        WeightsWindow::<T>::get(netuid)
+    }
+    pub fn set_weights_window(netuid: u16, weights_window: u64) {
+        WeightsWindow::<T>::insert(netuid, weights_window);
+        Self::deposit_event(Event::WeightsWindowSet(netuid, weights_window));
+    }
+
+    pub fn get_consensus_mechanism(netuid: u16) -> ConsensusMechanism {
+        SubnetConsensusMechanism::<T>::get(netuid)
+    }
+    pub fn set_consensus_mechanism(netuid: u16, mechanism: ConsensusMechanism) {
+        SubnetConsensusMechanism::<T>::insert(netuid, mechanism);
+        Self::deposit_event(Event::ConsensusMechanismSet(netuid, mechanism));
+    }
+
+    pub fn get_epoch_history_depth(netuid: u16) -> u16 {
+        EpochHistoryDepth::<T>::get(netuid)
+    }
+    pub fn set_epoch_history_depth(netuid: u16, depth: u16) {
+        // Slots depend on the depth, so the recorded history is dropped when it changes.
+        if depth != Self::get_epoch_history_depth(netuid) {
+            let _ = EpochHistory::<T>::clear_prefix(netuid, u32::MAX, None);
+            EpochHistoryIndex::<T>::remove(netuid);
+        }
+        EpochHistoryDepth::<T>::insert(netuid, depth);
+        Self::deposit_event(Event::EpochHistoryDepthSet(netuid, depth));
+    }
+
+    pub fn get_weights_max_version_key(netuid: u16) -> u64 {
+        WeightsMaxVersionKey::<T>::get(netuid)
+    }
+    pub fn set_weights_max_version_key(netuid: u16, weights_max_version_key: u64) {
+        WeightsMaxVersionKey::<T>::insert(netuid, weights_max_version_key);
+        Self::deposit_event(Event::WeightsMaxVersionKeySet(
+            netuid,
+            weights_max_version_key,
+        ));
+    }
+
+    pub fn get_banned_weights_version_keys(netuid: u16) -> Vec<u64> {
+        BannedWeightsVersionKeys::<T>::get(netuid)
+    }
+    pub fn set_banned_weights_version_keys(netuid: u16, mut banned_version_keys: Vec<u64>) {
+        banned_version_keys.sort_unstable();
+        banned_version_keys.dedup();
+        if banned_version_keys.is_empty() {
+            BannedWeightsVersionKeys::<T>::remove(netuid);
+        } else {
+            BannedWeightsVersionKeys::<T>::insert(netuid, &banned_version_keys);
+        }
+        Self::deposit_event(Event::BannedWeightsVersionKeysSet(
+            netuid,
+            banned_version_keys,
+        ));
+    }
+
     pub fn get_weights_set_rate_limit(netuid: u16) -> u64 {
         WeightsSetRateLimit::<T>::get(netuid)
     }
@@ -623,6 +680,43 @@ impl<T: Config> Pallet<T> {
         StakingHotkeys::<T>::get(coldkey)
     }
 
+    /// Returns the hotkeys the coldkey stakes to or owns, without duplicates.
+    ///
+    /// Together the StakingHotkeys and OwnedHotkeys indices cover every non-zero Stake entry
+    /// of the coldkey, see `check_staking_hotkeys_index`.
+    pub fn get_indexed_hotkeys_for_coldkey(coldkey: &T::AccountId) -> Vec<T::AccountId> {
+        let mut hotkeys = StakingHotkeys::<T>::get(coldkey);
+        for hotkey in OwnedHotkeys::<T>::get(coldkey) {
+            if !hotkeys.contains(&hotkey) {
+                hotkeys.push(hotkey);
+            }
+        }
+        hotkeys
+    }
+
+    /// Checks that every non-zero Stake entry is reachable from the StakingHotkeys or
+    /// OwnedHotkeys index of its coldkey.
+    ///
+    /// Iterates the whole Stake map, so it is only meant for try-runtime and tests.
+    pub fn check_staking_hotkeys_index() -> Result<(), &'static str> {
+        for (hotkey, coldkey, stake) in Stake::<T>::iter() {
+            if stake == 0 {
+                continue;
+            }
+            if !StakingHotkeys::<T>::get(&coldkey).contains(&hotkey)
+                && !OwnedHotkeys::<T>::get(&coldkey).contains(&hotkey)
+            {
+                log::error!(
+                    "Stake of coldkey {:?} on hotkey {:?} is missing from the staking indices",
+                    coldkey,
+                    hotkey
+                );
+                return Err("Stake entry missing from StakingHotkeys and OwnedHotkeys");
+            }
+        }
+        Ok(())
+    }
+
     pub fn set_total_issuance(total_issuance: u64) {
         TotalIssuance::<T>::put(total_issuance);
     }
@@ -671,6 +765,69 @@ impl<T: Config> Pallet<T> {
         NominatorMinRequiredStake::<T>::put(min_stake);
     }
 
+    pub fn get_unbonding_period() -> u64 {
+        UnbondingPeriod::<T>::get()
+    }
+
+    pub fn set_unbonding_period(unbonding_period: u64) {
+        UnbondingPeriod::<T>::put(unbonding_period);
+        Self::deposit_event(Event::UnbondingPeriodSet(unbonding_period));
+    }
+
+    pub fn get_network_unbonding_period(netuid: u16) -> u64 {
+        NetworkUnbondingPeriod::<T>::get(netuid)
+    }
+
+    pub fn set_network_unbonding_period(netuid: u16, unbonding_period: u64) {
+        NetworkUnbondingPeriod::<T>::insert(netuid, unbonding_period);
+        Self::deposit_event(Event::NetworkUnbondingPeriodSet(netuid, unbonding_period));
+    }
+
+    pub fn get_max_unbonding_chunks() -> u32 {
+        MaxUnbondingChunks::<T>::get()
+    }
+
+    pub fn set_max_unbonding_chunks(max_unbonding_chunks: u32) {
+        MaxUnbondingChunks::<T>::put(max_unbonding_chunks);
+        Self::deposit_event(Event::MaxUnbondingChunksSet(max_unbonding_chunks));
+    }
+
+    pub fn get_take_increase_delay() -> u64 {
+        TakeIncreaseDelay::<T>::get()
+    }
+
+    pub fn set_take_increase_delay(take_increase_delay: u64) {
+        TakeIncreaseDelay::<T>::put(take_increase_delay);
+        Self::deposit_event(Event::TakeIncreaseDelaySet(take_increase_delay));
+    }
+
+    pub fn get_stake_rate_limit_mode() -> StakeRateLimitMode {
+        StakeRateLimitSelector::<T>::get()
+    }
+
+    pub fn set_stake_rate_limit_mode(mode: StakeRateLimitMode) {
+        StakeRateLimitSelector::<T>::put(mode);
+        Self::deposit_event(Event::StakeRateLimitModeSet(mode));
+    }
+
+    pub fn get_stake_amount_window() -> u64 {
+        StakeAmountWindow::<T>::get()
+    }
+
+    pub fn set_stake_amount_window(stake_amount_window: u64) {
+        StakeAmountWindow::<T>::put(stake_amount_window);
+        Self::deposit_event(Event::StakeAmountWindowSet(stake_amount_window));
+    }
+
+    pub fn get_max_stake_amount_per_window() -> u64 {
+        MaxStakeAmountPerWindow::<T>::get()
+    }
+
+    pub fn set_max_stake_amount_per_window(max_stake_amount: u64) {
+        MaxStakeAmountPerWindow::<T>::put(max_stake_amount);
+        Self::deposit_event(Event::MaxStakeAmountPerWindowSet(max_stake_amount));
+    }
+
     pub fn get_hotkey_swap_cost() -> u64 {
         T::HotkeySwapCost::get()
     }
@@ -1,7 +1,11 @@
 use super::*;
 use crate::math::*;
-use sp_core::H256;
+use frame_support::storage::{with_transaction, TransactionOutcome};
+use frame_support::weights::Weight;
+use sp_core::{Get, H256};
 use sp_runtime::traits::{BlakeTwo256, Hash};
+use sp_std::collections::btree_map::BTreeMap;
+use sp_std::collections::vec_deque::VecDeque;
 use sp_std::vec;
 
 impl<T: Config> Pallet<T> {
@@ -17,10 +21,17 @@ impl<T: Config> Pallet<T> {
     /// * `commit_hash` (`H256`):
     ///   - The hash representing the committed weights.
     ///
+    /// # Event:
+    /// * `WeightsCommitted`:
+    ///   - On successfully queueing the commit.
+    ///
     /// # Raises:
     /// * `WeightsCommitNotAllowed`:
     ///   - Attempting to commit when it is not allowed.
     ///
+    /// * `TooManyUnrevealedCommits`:
+    ///   - The hotkey already has the maximum number of pending commits on the subnet.
+    ///
     pub fn do_commit_weights(
         origin: T::RuntimeOrigin,
         netuid: u16,
@@ -40,14 +51,94 @@ impl<T: Config> Pallet<T> {
             Error::<T>::WeightsCommitNotAllowed
         );
 
-        WeightCommits::<T>::insert(
+        let current_block: u64 = Self::get_current_block_as_u64();
+        let mut expired: Vec<(H256, u64)> = Vec::new();
+        WeightCommits::<T>::try_mutate(netuid, &who, |maybe_commits| -> DispatchResult {
+            let commits = maybe_commits.get_or_insert_with(VecDeque::new);
+
+            // Drop the commits which can no longer be revealed.
+            expired = Self::remove_expired_commits(netuid, commits);
+            ensure!(
+                commits.len() < T::MaxWeightCommits::get() as usize,
+                Error::<T>::TooManyUnrevealedCommits
+            );
+
+            commits.push_back((commit_hash, current_block));
+            Ok(())
+        })?;
+
+        Self::deposit_commits_expired(netuid, &who, expired);
+        Self::deposit_event(Event::WeightsCommitted {
             netuid,
-            &who,
-            (commit_hash, Self::get_current_block_as_u64()),
-        );
+            hotkey: who,
+            commit_hash,
+            block: current_block,
+        });
         Ok(())
     }
 
+    /// ---- The implementation for committing weight hashes on several subnets.
+    ///
+    /// # Args:
+    /// * `origin`: (`<T as frame_system::Config>::RuntimeOrigin`):
+    ///   - The signature of the committing hotkey.
+    ///
+    /// * `commits` (`Vec<(u16, H256)>`):
+    ///   - The (netuid, commit_hash) entries to commit.
+    ///
+    /// # Event:
+    /// * `BatchWeightsCommitCompleted`:
+    ///   - With the result of every entry once the batch is processed.
+    ///
+    /// # Raises:
+    /// * `InvalidWeightsBatchSize`:
+    ///   - The batch is empty or has more than `MAX_WEIGHTS_BATCH_SIZE` entries.
+    ///
+    /// Entries are checked and committed on their own, each in its own storage
+    /// transaction, so a failing entry does not revert the others.
+    ///
+    /// # Returns:
+    /// * The weight of the entries as processed, entries on subnets with commit reveal
+    ///   disabled are charged a single read.
+    ///
+    pub fn do_batch_commit_weights(
+        origin: T::RuntimeOrigin,
+        commits: Vec<(u16, H256)>,
+    ) -> DispatchResultWithPostInfo {
+        let who = ensure_signed(origin.clone())?;
+
+        log::info!(
+            "do_batch_commit_weights( hotkey:{:?} entries:{:?} )",
+            who,
+            commits.len()
+        );
+        Self::ensure_weights_batch_size(commits.len())?;
+
+        let mut weight: Weight = Weight::zero();
+        let mut results: Vec<(u16, DispatchResult)> = Vec::with_capacity(commits.len());
+        for (netuid, commit_hash) in commits {
+            let result = if Self::get_commit_reveal_weights_enabled(netuid) {
+                weight.saturating_accrue(
+                    Weight::from_parts(46_000_000, 0)
+                        .saturating_add(T::DbWeight::get().reads_writes(1, 1)),
+                );
+                Self::with_weights_batch_entry_transaction(|| {
+                    Self::do_commit_weights(origin.clone(), netuid, commit_hash)
+                })
+            } else {
+                weight.saturating_accrue(T::DbWeight::get().reads(1));
+                Err(Error::<T>::CommitRevealDisabled.into())
+            };
+            results.push((netuid, result));
+        }
+
+        Self::deposit_event(Event::BatchWeightsCommitCompleted {
+            hotkey: who,
+            results,
+        });
+        Ok(Some(weight).into())
+    }
+
     /// ---- The implementation for revealing committed weights.
     ///
     /// # Args:
@@ -69,15 +160,19 @@ impl<T: Config> Pallet<T> {
     /// * `version_key` (`u64`):
     ///   - The network version key.
     ///
+    /// # Event:
+    /// * `WeightsRevealed`:
+    ///   - On successfully revealing a commit, after the `WeightsSet` event.
+    ///
     /// # Raises:
     /// * `NoWeightsCommitFound`:
     ///   - Attempting to reveal weights without an existing commit.
     ///
     /// * `InvalidRevealCommitHashNotMatchTempo`:
-    ///   - Attempting to reveal weights outside the valid tempo.
+    ///   - Attempting to reveal weights outside the valid tempo of every matching commit.
     ///
     /// * `InvalidRevealCommitHashNotMatch`:
-    ///   - The revealed hash does not match the committed hash.
+    ///   - The revealed hash does not match any pending commit.
     ///
     pub fn do_reveal_weights(
         origin: T::RuntimeOrigin,
@@ -96,31 +191,115 @@ impl<T: Config> Pallet<T> {
             Error::<T>::CommitRevealDisabled
         );
 
-        WeightCommits::<T>::try_mutate_exists(netuid, &who, |maybe_commit| -> DispatchResult {
-            let (commit_hash, commit_block) = maybe_commit
-                .as_ref()
+        let provided_hash: H256 = BlakeTwo256::hash_of(&(
+            who.clone(),
+            netuid,
+            uids.clone(),
+            values.clone(),
+            salt,
+            version_key,
+        ));
+
+        let mut expired: Vec<(H256, u64)> = Vec::new();
+        WeightCommits::<T>::try_mutate_exists(netuid, &who, |maybe_commits| -> DispatchResult {
+            let commits = maybe_commits
+                .as_mut()
                 .ok_or(Error::<T>::NoWeightsCommitFound)?;
 
-            ensure!(
-                Self::is_reveal_block_range(netuid, *commit_block),
-                Error::<T>::InvalidRevealCommitTempo
-            );
+            // Reveal the oldest matching commit whose reveal window is open.
+            let mut hash_matched = false;
+            let position = commits.iter().position(|(commit_hash, commit_block)| {
+                if *commit_hash != provided_hash {
+                    return false;
+                }
+                hash_matched = true;
+                Self::is_reveal_block_range(netuid, *commit_block)
+            });
+            let Some(position) = position else {
+                if hash_matched {
+                    return Err(Error::<T>::InvalidRevealCommitTempo.into());
+                }
+                return Err(Error::<T>::InvalidRevealCommitHashNotMatch.into());
+            };
 
-            let provided_hash: H256 = BlakeTwo256::hash_of(&(
-                who.clone(),
-                netuid,
-                uids.clone(),
-                values.clone(),
-                salt.clone(),
-                version_key,
-            ));
-            ensure!(
-                provided_hash == *commit_hash,
-                Error::<T>::InvalidRevealCommitHashNotMatch
-            );
+            commits.remove(position);
+            expired = Self::remove_expired_commits(netuid, commits);
+            if commits.is_empty() {
+                *maybe_commits = None;
+            }
 
             Self::do_set_weights(origin, netuid, uids, values, version_key)
-        })
+        })?;
+
+        Self::deposit_commits_expired(netuid, &who, expired);
+        Self::deposit_event(Event::WeightsRevealed {
+            netuid,
+            hotkey: who,
+            commit_hash: provided_hash,
+        });
+        Ok(())
+    }
+
+    /// ---- The implementation for revealing committed weights on several subnets.
+    ///
+    /// # Args:
+    /// * `origin`: (`<T as frame_system::Config>::RuntimeOrigin`):
+    ///   - The signature of the revealing hotkey.
+    ///
+    /// * `reveals` (`Vec<(u16, Vec<u16>, Vec<u16>, Vec<u16>, u64)>`):
+    ///   - The (netuid, uids, values, salt, version_key) entries to reveal.
+    ///
+    /// # Event:
+    /// * `BatchWeightsRevealCompleted`:
+    ///   - With the result of every entry once the batch is processed.
+    ///
+    /// # Raises:
+    /// * `InvalidWeightsBatchSize`:
+    ///   - The batch is empty or has more than `MAX_WEIGHTS_BATCH_SIZE` entries.
+    ///
+    /// Entries are checked and revealed on their own, each in its own storage
+    /// transaction, so a failing entry does not revert the others.
+    ///
+    /// # Returns:
+    /// * The weight of the entries as processed, entries on subnets with commit reveal
+    ///   disabled are charged a single read.
+    ///
+    pub fn do_batch_reveal_weights(
+        origin: T::RuntimeOrigin,
+        reveals: Vec<(u16, Vec<u16>, Vec<u16>, Vec<u16>, u64)>,
+    ) -> DispatchResultWithPostInfo {
+        let who = ensure_signed(origin.clone())?;
+
+        log::info!(
+            "do_batch_reveal_weights( hotkey:{:?} entries:{:?} )",
+            who,
+            reveals.len()
+        );
+        Self::ensure_weights_batch_size(reveals.len())?;
+
+        let mut weight: Weight = Weight::zero();
+        let mut results: Vec<(u16, DispatchResult)> = Vec::with_capacity(reveals.len());
+        for (netuid, uids, values, salt, version_key) in reveals {
+            let result = if Self::get_commit_reveal_weights_enabled(netuid) {
+                weight.saturating_accrue(
+                    Weight::from_parts(103_000_000, 0)
+                        .saturating_add(T::DbWeight::get().reads_writes(11, 3)),
+                );
+                Self::with_weights_batch_entry_transaction(|| {
+                    Self::do_reveal_weights(origin.clone(), netuid, uids, values, salt, version_key)
+                })
+            } else {
+                weight.saturating_accrue(T::DbWeight::get().reads(1));
+                Err(Error::<T>::CommitRevealDisabled.into())
+            };
+            results.push((netuid, result));
+        }
+
+        Self::deposit_event(Event::BatchWeightsRevealCompleted {
+            hotkey: who,
+            results,
+        });
+        Ok(Some(weight).into())
     }
 
     /// ---- The implementation for the extrinsic set_weights.
@@ -155,6 +334,15 @@ impl<T: Config> Pallet<T> {
     ///  * 'IncorrectWeightVersionKey':
     ///    - Attempting to set weights without having an up-to-date version_key.
     ///
+    ///  * 'WeightVersionKeyTooHigh':
+    ///    - Attempting to set weights with a version_key above the subnet maximum.
+    ///
+    ///  * 'WeightVersionKeyBanned':
+    ///    - Attempting to set weights with a version_key banned on the subnet.
+    ///
+    ///  * 'WeightsWindowClosed':
+    ///    - Attempting to set weights outside the weights window of the network.
+    ///
     ///  * 'SettingWeightsTooFast':
     ///    - Attempting to set weights faster than the weights_set_rate_limit.
     ///
@@ -233,14 +421,17 @@ impl<T: Config> Pallet<T> {
         );
 
         // --- 7. Ensure version_key is up-to-date.
+        Self::check_version_key(netuid, version_key)?;
+
+        // --- 8. Ensure weights are set within the weights window of the network.
+        let current_block: u64 = Self::get_current_block_as_u64();
         ensure!(
-            Self::check_version_key(netuid, version_key),
-            Error::<T>::IncorrectWeightVersionKey
+            Self::check_weights_window(netuid, current_block),
+            Error::<T>::WeightsWindowClosed
         );
 
         // --- 9. Ensure the uid is not setting weights faster than the weights_set_rate_limit.
         let neuron_uid = Self::get_uid_for_net_and_hotkey(netuid, &hotkey)?;
-        let current_block: u64 = Self::get_current_block_as_u64();
         ensure!(
             Self::check_rate_limit(netuid, neuron_uid, current_block),
             Error::<T>::SettingWeightsTooFast
@@ -300,20 +491,390 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// ---- The implementation for the extrinsic set_weights_delta.
+    ///
+    /// # Args:
+    ///  * 'origin': (<T as frame_system::Config>RuntimeOrigin):
+    ///    - The signature of the calling hotkey.
+    ///
+    ///  * 'netuid' (u16):
+    ///    - The u16 network identifier.
+    ///
+    ///  * 'changes' ( Vec<(u16, u16)> ):
+    ///    - The (uid, value) entries to upsert in the weights row of the caller, a zero
+    ///      value removes the uid from the row. Values are on the scale of the stored row,
+    ///      where the largest weight is u16::MAX.
+    ///
+    ///  * 'version_key' ( u64 ):
+    ///    - The network version key.
+    ///
+    /// # Event:
+    ///  * WeightsSet;
+    ///    - On successfully updating the weights on chain.
+    ///
+    /// # Raises:
+    ///  The same errors as do_set_weights, checked against the updated row, and
+    ///  'CommitRevealEnabled' on subnets where weights must be committed first.
+    ///
+    /// # Returns:
+    ///  * The weight for the length of the stored row and the number of changes.
+    ///
+    pub fn do_set_weights_delta(
+        origin: T::RuntimeOrigin,
+        netuid: u16,
+        changes: Vec<(u16, u16)>,
+        version_key: u64,
+    ) -> DispatchResultWithPostInfo {
+        // --- 1. Check the caller's signature. This is the hotkey of a registered account.
+        let hotkey = ensure_signed(origin)?;
+        log::info!(
+            "do_set_weights_delta( origin:{:?} netuid:{:?}, changes:{:?})",
+            hotkey,
+            netuid,
+            changes
+        );
+
+        // --- 2. Check that the netuid is not the root network and weights are set directly.
+        ensure!(
+            netuid != Self::get_root_netuid(),
+            Error::<T>::CanNotSetRootNetworkWeights
+        );
+        ensure!(
+            !Self::get_commit_reveal_weights_enabled(netuid),
+            Error::<T>::CommitRevealEnabled
+        );
+
+        // --- 3. Check to see if this is a valid network.
+        ensure!(
+            Self::if_subnet_exist(netuid),
+            Error::<T>::SubNetworkDoesNotExist
+        );
+
+        // --- 4. Check to see if the hotkey is registered to the passed network.
+        ensure!(
+            Self::is_hotkey_registered_on_network(netuid, &hotkey),
+            Error::<T>::HotKeyNotRegisteredInSubNet
+        );
+
+        // --- 5. Check to see if the hotkey has enought stake to set weights.
+        ensure!(
+            Self::get_total_stake_for_hotkey(&hotkey) >= Self::get_weights_min_stake(),
+            Error::<T>::NotEnoughStakeToSetWeights
+        );
+
+        // --- 6. Ensure version_key is up-to-date.
+        Self::check_version_key(netuid, version_key)?;
+
+        // --- 7. Ensure weights are set within the weights window and not faster than the weights_set_rate_limit.
+        let current_block: u64 = Self::get_current_block_as_u64();
+        ensure!(
+            Self::check_weights_window(netuid, current_block),
+            Error::<T>::WeightsWindowClosed
+        );
+        let neuron_uid = Self::get_uid_for_net_and_hotkey(netuid, &hotkey)?;
+        ensure!(
+            Self::check_rate_limit(netuid, neuron_uid, current_block),
+            Error::<T>::SettingWeightsTooFast
+        );
+
+        // --- 8. Ensure the changed uids contain no duplicates and are valid for the network.
+        let changed_uids: Vec<u16> = changes.iter().map(|(uid, _)| *uid).collect();
+        ensure!(
+            !Self::has_duplicate_uids(&changed_uids),
+            Error::<T>::DuplicateUids
+        );
+        ensure!(
+            !Self::contains_invalid_uids(netuid, &changed_uids),
+            Error::<T>::UidVecContainInvalidOne
+        );
+
+        // --- 9. Merge the changes into the current row by uid, zero values remove the uid.
+        let stored_row: Vec<(u16, u16)> = Weights::<T>::get(netuid, neuron_uid);
+        let actual_weight: Weight =
+            Self::get_set_weights_delta_weight(stored_row.len(), changes.len());
+        let mut row: BTreeMap<u16, u16> = stored_row.into_iter().collect();
+        row.extend(changes);
+        row.retain(|_, value| *value != 0);
+        let (uids, values): (Vec<u16>, Vec<u16>) = row.into_iter().unzip();
+
+        // --- 10. Run the row checks of set_weights on the updated row.
+        ensure!(
+            Self::check_len_uids_within_allowed(netuid, &uids),
+            Error::<T>::UidsLengthExceedUidsInSubNet
+        );
+        ensure!(
+            Self::check_validator_permit(netuid, neuron_uid, &uids, &values),
+            Error::<T>::NeuronNoValidatorPermit
+        );
+        ensure!(
+            Self::check_length(netuid, neuron_uid, &uids, &values),
+            Error::<T>::WeightVecLengthIsLow
+        );
+
+        // --- 11. Max-upscale the row again and ensure it is max weight limited.
+        let max_upscaled_weights: Vec<u16> = vec_u16_max_upscale_to_u16(&values);
+        ensure!(
+            Self::max_weight_limited(netuid, neuron_uid, &uids, &max_upscaled_weights),
+            Error::<T>::MaxWeightExceeded
+        );
+
+        // --- 12. Set the updated row and the activity for the weights on this network.
+        let zipped_weights: Vec<(u16, u16)> = uids.into_iter().zip(max_upscaled_weights).collect();
+        Weights::<T>::insert(netuid, neuron_uid, zipped_weights);
+        Self::set_last_update_for_uid(netuid, neuron_uid, current_block);
+
+        // --- 13. Emit the tracking event.
+        log::info!(
+            "WeightsSet( netuid:{:?}, neuron_uid:{:?} )",
+            netuid,
+            neuron_uid
+        );
+        Self::deposit_event(Event::WeightsSet(netuid, neuron_uid));
+        Ok(Some(actual_weight).into())
+    }
+
+    /// ---- Returns the weight of set_weights_delta on a stored row of row_len uids with
+    /// changes_len changes. Every uid of the merged row is checked again, at the per-uid
+    /// cost of set_weights.
+    ///
+    pub fn get_set_weights_delta_weight(row_len: usize, changes_len: usize) -> Weight {
+        let entries: u64 = (row_len as u64).saturating_add(changes_len as u64);
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(Weight::from_parts(5_400_000, 0).saturating_mul(entries))
+            .saturating_add(T::DbWeight::get().reads(12_u64.saturating_add(changes_len as u64)))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
+    /// ---- The implementation for the extrinsic batch_set_weights.
+    ///
+    /// # Args:
+    ///  * 'origin': (<T as frame_system::Config>RuntimeOrigin):
+    ///    - The signature of the calling hotkey.
+    ///
+    ///  * 'weights' ( Vec<(u16, Vec<u16>, Vec<u16>, u64)> ):
+    ///    - The (netuid, uids, values, version_key) entries to set, one per subnet.
+    ///
+    /// # Event:
+    ///  * WeightsSet;
+    ///    - For every entry successfully set on chain.
+    ///
+    ///  * BatchWeightsCompleted;
+    ///    - With the result of every entry once the batch is processed.
+    ///
+    /// # Raises:
+    ///  * 'BadOrigin':
+    ///    - The call is not signed.
+    ///
+    ///  * 'InvalidWeightsBatchSize':
+    ///    - The batch is empty or has more than MAX_WEIGHTS_BATCH_SIZE entries.
+    ///
+    /// Entries are checked and applied on their own, each in its own storage transaction,
+    /// so a failing entry does not revert the others. Entries on subnets with commit reveal
+    /// enabled fail with 'CommitRevealEnabled' as they would through set_weights.
+    ///
+    /// # Returns:
+    ///  * The weight of the entries as processed, charging reads for the uids each entry sets.
+    ///
+    pub fn do_batch_set_weights(
+        origin: T::RuntimeOrigin,
+        weights: Vec<(u16, Vec<u16>, Vec<u16>, u64)>,
+    ) -> DispatchResultWithPostInfo {
+        let hotkey = ensure_signed(origin.clone())?;
+        log::info!(
+            "do_batch_set_weights( origin:{:?} entries:{:?} )",
+            hotkey,
+            weights.len()
+        );
+        Self::ensure_weights_batch_size(weights.len())?;
+
+        let mut weight: Weight = Weight::zero();
+        let mut results: Vec<(u16, DispatchResult)> = Vec::with_capacity(weights.len());
+        for (netuid, uids, values, version_key) in weights {
+            let result = if Self::get_commit_reveal_weights_enabled(netuid) {
+                weight.saturating_accrue(T::DbWeight::get().reads(1));
+                Err(Error::<T>::CommitRevealEnabled.into())
+            } else {
+                weight.saturating_accrue(Self::get_batch_set_weights_entry_weight(uids.len()));
+                Self::with_weights_batch_entry_transaction(|| {
+                    Self::do_set_weights(origin.clone(), netuid, uids, values, version_key)
+                })
+            };
+            if let Err(e) = result {
+                log::debug!(
+                    "batch_set_weights entry failed( netuid:{:?}, error:{:?} )",
+                    netuid,
+                    e
+                );
+            }
+            results.push((netuid, result));
+        }
+
+        Self::deposit_event(Event::BatchWeightsCompleted { hotkey, results });
+        Ok(Some(weight).into())
+    }
+
+    /// ---- Returns the weight of a set_weights entry of a batch. The weight of set_weights
+    /// reads every uid of the largest subnet, an entry only reads the uids it sets.
+    ///
+    pub fn get_batch_set_weights_entry_weight(uids_len: usize) -> Weight {
+        let uid_reads: u64 = uids_len.min(4096) as u64;
+        Weight::from_parts(22_060_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(uid_reads.saturating_add(10)))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
+    /// ---- Ensures a weights batch has at least one and at most MAX_WEIGHTS_BATCH_SIZE entries.
+    ///
+    fn ensure_weights_batch_size(len: usize) -> dispatch::DispatchResult {
+        ensure!(
+            len > 0 && len <= MAX_WEIGHTS_BATCH_SIZE as usize,
+            Error::<T>::InvalidWeightsBatchSize
+        );
+        Ok(())
+    }
+
+    /// ---- Runs one entry of a weights batch in its own storage transaction, so a failing
+    /// entry leaves none of its writes behind.
+    ///
+    fn with_weights_batch_entry_transaction(
+        entry: impl FnOnce() -> DispatchResult,
+    ) -> DispatchResult {
+        with_transaction(|| {
+            let result = entry();
+            if result.is_ok() {
+                TransactionOutcome::Commit(result)
+            } else {
+                TransactionOutcome::Rollback(result)
+            }
+        })
+    }
+
+    /// ---- Runs the checks of set_weights for the hotkey without writing state.
+    ///
+    /// # Returns:
+    ///  * Every check that fails, in the order set_weights runs them, and the max-upscaled
+    ///    weights that would be stored for the uids.
+    ///
+    pub fn check_weights(
+        netuid: u16,
+        hotkey: &T::AccountId,
+        uids: &[u16],
+        values: &[u16],
+        version_key: u64,
+    ) -> (Vec<DispatchError>, Vec<(u16, u16)>) {
+        let mut failures: Vec<DispatchError> = Vec::new();
+
+        if netuid == Self::get_root_netuid() {
+            failures.push(Error::<T>::CanNotSetRootNetworkWeights.into());
+        }
+        if Self::get_commit_reveal_weights_enabled(netuid) {
+            failures.push(Error::<T>::CommitRevealEnabled.into());
+        }
+        if !Self::uids_match_values(uids, values) {
+            failures.push(Error::<T>::WeightVecNotEqualSize.into());
+        }
+        if !Self::if_subnet_exist(netuid) {
+            failures.push(Error::<T>::SubNetworkDoesNotExist.into());
+            return (failures, Vec::new());
+        }
+        if !Self::check_len_uids_within_allowed(netuid, uids) {
+            failures.push(Error::<T>::UidsLengthExceedUidsInSubNet.into());
+        }
+        let neuron_uid: Option<u16> = Self::get_uid_for_net_and_hotkey(netuid, hotkey).ok();
+        if neuron_uid.is_none() {
+            failures.push(Error::<T>::HotKeyNotRegisteredInSubNet.into());
+        }
+        if Self::get_total_stake_for_hotkey(hotkey) < Self::get_weights_min_stake() {
+            failures.push(Error::<T>::NotEnoughStakeToSetWeights.into());
+        }
+        if let Err(error) = Self::check_version_key(netuid, version_key) {
+            failures.push(error);
+        }
+        let current_block: u64 = Self::get_current_block_as_u64();
+        if !Self::check_weights_window(netuid, current_block) {
+            failures.push(Error::<T>::WeightsWindowClosed.into());
+        }
+        if let Some(neuron_uid) = neuron_uid {
+            if !Self::check_rate_limit(netuid, neuron_uid, current_block) {
+                failures.push(Error::<T>::SettingWeightsTooFast.into());
+            }
+            if !Self::check_validator_permit(netuid, neuron_uid, uids, values) {
+                failures.push(Error::<T>::NeuronNoValidatorPermit.into());
+            }
+        }
+        if Self::has_duplicate_uids(uids) {
+            failures.push(Error::<T>::DuplicateUids.into());
+        }
+        if Self::contains_invalid_uids(netuid, uids) {
+            failures.push(Error::<T>::UidVecContainInvalidOne.into());
+        }
+        let max_upscaled_weights: Vec<u16> = vec_u16_max_upscale_to_u16(values);
+        if let Some(neuron_uid) = neuron_uid {
+            if !Self::check_length(netuid, neuron_uid, uids, values) {
+                failures.push(Error::<T>::WeightVecLengthIsLow.into());
+            }
+            if !Self::max_weight_limited(netuid, neuron_uid, uids, &max_upscaled_weights) {
+                failures.push(Error::<T>::MaxWeightExceeded.into());
+            }
+        }
+
+        let weights: Vec<(u16, u16)> = uids.iter().copied().zip(max_upscaled_weights).collect();
+        (failures, weights)
+    }
+
     // ==========================
     // ==== Helper functions ====
     // ==========================
 
-    /// Returns true if version_key is up-to-date.
+    /// Checks the version_key against the version policy of the subnet: it must be at least
+    /// the weights version key, at most the max version key and not banned. A key of 0 leaves
+    /// that bound open.
     ///
-    pub fn check_version_key(netuid: u16, version_key: u64) -> bool {
+    pub fn check_version_key(netuid: u16, version_key: u64) -> DispatchResult {
         let network_version_key: u64 = WeightsVersionKey::<T>::get(netuid);
+        let network_max_version_key: u64 = WeightsMaxVersionKey::<T>::get(netuid);
         log::info!(
-            "check_version_key( network_version_key:{:?}, version_key:{:?} )",
+            "check_version_key( network_version_key:{:?}, network_max_version_key:{:?}, version_key:{:?} )",
             network_version_key,
+            network_max_version_key,
             version_key
         );
-        network_version_key == 0 || version_key >= network_version_key
+        ensure!(
+            network_version_key == 0 || version_key >= network_version_key,
+            Error::<T>::IncorrectWeightVersionKey
+        );
+        ensure!(
+            network_max_version_key == 0 || version_key <= network_max_version_key,
+            Error::<T>::WeightVersionKeyTooHigh
+        );
+        ensure!(
+            BannedWeightsVersionKeys::<T>::get(netuid)
+                .binary_search(&version_key)
+                .is_err(),
+            Error::<T>::WeightVersionKeyBanned
+        );
+        Ok(())
+    }
+
+    /// Returns true if weights set at current_block fall within the weights window of the
+    /// network, the last blocks before the epoch that will consume them. The epoch gathers the
+    /// weights when it is staged, its lead blocks before tempo, so the window closes there. A
+    /// window of 0 accepts weights at any block.
+    ///
+    pub fn check_weights_window(netuid: u16, current_block: u64) -> bool {
+        let weights_window: u64 = Self::get_weights_window(netuid);
+        if weights_window == 0 {
+            return true;
+        }
+        let tempo: u16 = Self::get_tempo(netuid);
+        let lead: u64 = Self::get_staged_epoch_lead_blocks(tempo);
+        // The epoch of the current block has already been staged, the weights go to the next one.
+        let blocks_until_epoch: u64 =
+            match Self::blocks_until_next_epoch(netuid, tempo, current_block) {
+                blocks if blocks <= lead => blocks.saturating_add(tempo as u64).saturating_add(1),
+                blocks => blocks,
+            };
+        blocks_until_epoch.saturating_sub(lead) <= weights_window
     }
 
     /// Checks if the neuron has set weights within the weights_set_rate_limit.
@@ -452,9 +1013,13 @@ impl<T: Config> Pallet<T> {
         uids.len() <= subnetwork_n as usize
     }
 
+    /// Returns true if the hotkey may commit in the current interval, which is the case if its
+    /// latest pending commit was made in an earlier interval.
     #[allow(clippy::arithmetic_side_effects)]
     pub fn can_commit(netuid: u16, who: &T::AccountId) -> bool {
-        if let Some((_hash, commit_block)) = WeightCommits::<T>::get(netuid, who) {
+        if let Some((_hash, commit_block)) =
+            WeightCommits::<T>::get(netuid, who).and_then(|commits| commits.back().copied())
+        {
             let interval: u64 = Self::get_commit_reveal_weights_interval(netuid);
             if interval == 0 {
                 return true; //prevent division by 0
@@ -478,6 +1043,7 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Returns true if a commit made at commit_block can be revealed in the current block.
     #[allow(clippy::arithmetic_side_effects)]
     pub fn is_reveal_block_range(netuid: u16, commit_block: u64) -> bool {
         let interval: u64 = Self::get_commit_reveal_weights_interval(netuid);
@@ -498,4 +1064,102 @@ impl<T: Config> Pallet<T> {
 
         false
     }
+
+    /// Returns the first block at which a commit made at commit_block can be revealed, and
+    /// the first block at which it has expired. Without an interval commits never expire.
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn get_reveal_window(netuid: u16, commit_block: u64) -> (u64, u64) {
+        let interval: u64 = Self::get_commit_reveal_weights_interval(netuid);
+        if interval == 0 {
+            return (commit_block, u64::MAX); //prevent division by 0
+        }
+
+        let commit_interval_start: u64 = commit_block.saturating_sub(commit_block % interval);
+        let reveal_interval_start: u64 = commit_interval_start.saturating_add(interval);
+        (
+            reveal_interval_start,
+            reveal_interval_start.saturating_add(interval),
+        )
+    }
+
+    /// Returns true if the reveal window of a commit made at commit_block has passed.
+    pub fn is_commit_expired(netuid: u16, commit_block: u64) -> bool {
+        let (_reveal_start, reveal_end) = Self::get_reveal_window(netuid, commit_block);
+        Self::get_current_block_as_u64() >= reveal_end
+    }
+
+    /// Removes the commits whose reveal window has passed, keeping the others in order.
+    /// Returns the removed (commit_hash, commit_block) commits.
+    pub fn remove_expired_commits(
+        netuid: u16,
+        commits: &mut VecDeque<(H256, u64)>,
+    ) -> Vec<(H256, u64)> {
+        let mut expired: Vec<(H256, u64)> = Vec::new();
+        commits.retain(|(commit_hash, commit_block)| {
+            if !Self::is_commit_expired(netuid, *commit_block) {
+                return true;
+            }
+            expired.push((*commit_hash, *commit_block));
+            false
+        });
+        expired
+    }
+
+    /// Emits WeightsCommitExpired for each removed commit, once the removal has been stored.
+    pub fn deposit_commits_expired(netuid: u16, hotkey: &T::AccountId, expired: Vec<(H256, u64)>) {
+        for (commit_hash, commit_block) in expired {
+            Self::deposit_event(Event::WeightsCommitExpired {
+                netuid,
+                hotkey: hotkey.clone(),
+                commit_hash,
+                commit_block,
+            });
+        }
+    }
+
+    /// Removes the expired weight commits, resuming after the last hotkey visited by the
+    /// previous sweep and stopping once the remaining weight is used. Returns the weight used.
+    pub fn remove_expired_weight_commits_on_idle(remaining_weight: Weight) -> Weight {
+        let mut weight_used: Weight = T::DbWeight::get().reads(1);
+
+        // Reading the commits and the subnet interval, and writing back the commits.
+        let weight_per_entry: Weight = T::DbWeight::get().reads_writes(2, 1);
+        // Always leave room to store the cursor.
+        let limit: Weight = remaining_weight.saturating_sub(T::DbWeight::get().writes(1));
+        if weight_used.saturating_add(weight_per_entry).any_gt(limit) {
+            return weight_used;
+        }
+
+        let entries = match WeightCommitsSweepCursor::<T>::get() {
+            Some(cursor) => WeightCommits::<T>::iter_from(cursor),
+            None => WeightCommits::<T>::iter(),
+        };
+        let mut last_key: Option<Vec<u8>> = None;
+        let mut finished: bool = true;
+        for (netuid, hotkey, mut commits) in entries {
+            weight_used.saturating_accrue(weight_per_entry);
+            let expired: Vec<(H256, u64)> = Self::remove_expired_commits(netuid, &mut commits);
+            if !expired.is_empty() {
+                if commits.is_empty() {
+                    WeightCommits::<T>::remove(netuid, &hotkey);
+                } else {
+                    WeightCommits::<T>::insert(netuid, &hotkey, commits);
+                }
+                Self::deposit_commits_expired(netuid, &hotkey, expired);
+            }
+            last_key = Some(WeightCommits::<T>::hashed_key_for(netuid, &hotkey));
+
+            if weight_used.saturating_add(weight_per_entry).any_gt(limit) {
+                finished = false;
+                break;
+            }
+        }
+
+        // Start over from the first commit once the whole map has been visited.
+        match last_key {
+            Some(key) if !finished => WeightCommitsSweepCursor::<T>::put(key),
+            _ => WeightCommitsSweepCursor::<T>::kill(),
+        }
+        weight_used.saturating_add(T::DbWeight::get().writes(1))
+    }
 }
@@ -1,10 +1,18 @@
-#![allow(clippy::unwrap_used)]
+#![allow(
+    clippy::arithmetic_side_effects,
+    clippy::indexing_slicing,
+    clippy::unwrap_used
+)]
 
 mod mock;
-use frame_support::assert_ok;
+use frame_support::{assert_ok, weights::Weight};
 use frame_system::Config;
 use mock::*;
-use sp_core::U256;
+use pallet_subtensor::{
+    Bonds, Event, PendingEmission, StagedEpoch, StagedEpochBonds, StagedEpochNeurons,
+    StagedEpochPhase, StagedEpochTerms,
+};
+use sp_core::{Get, U256};
 
 #[test]
 fn test_loaded_emission() {
@@ -938,3 +946,252 @@ fn test_epoch_runs_when_registration_disabled() {
         assert!(SubtensorModule::get_loaded_emission_tuples(netuid_off).is_some());
     });
 }
+
+#[test]
+fn test_staged_epoch_runs_ahead_of_tempo() {
+    new_test_ext(1).execute_with(|| {
+        let n: u16 = 16;
+        let netuid: u16 = 1;
+        add_staged_epoch_network(netuid, n);
+
+        // Nothing is staged before the lead.
+        System::set_block_number(2);
+        SubtensorModule::generate_emission(2);
+        assert_eq!(StagedEpoch::<Test>::get(netuid), None);
+        PendingEmission::<Test>::insert(netuid, 0);
+
+        // The epoch is staged ahead of tempo.
+        System::set_block_number(3);
+        SubtensorModule::generate_emission(3);
+        let cursor = StagedEpoch::<Test>::get(netuid).unwrap();
+        assert_eq!(cursor.n, n);
+        assert_eq!(cursor.epoch_block, 8);
+        assert_eq!(cursor.start_block, 3);
+        assert_eq!(cursor.phase, StagedEpochPhase::Done);
+        let gathered: u64 = cursor.rao_emission;
+        assert!(gathered > 0);
+        assert_eq!(gathered, SubtensorModule::get_pending_emission(netuid));
+        for block in 4..8 {
+            System::set_block_number(block);
+            SubtensorModule::generate_emission(block);
+        }
+        assert!(SubtensorModule::get_loaded_emission_tuples(netuid).is_none());
+
+        // The emission tuples are loaded at tempo.
+        System::set_block_number(8);
+        SubtensorModule::generate_emission(8);
+        assert_eq!(StagedEpoch::<Test>::get(netuid), None);
+        assert!(StagedEpochNeurons::<Test>::get(netuid).is_empty());
+        assert_eq!(StagedEpochTerms::<Test>::iter_prefix(netuid).count(), 0);
+        assert_eq!(StagedEpochBonds::<Test>::iter_prefix(netuid).count(), 0);
+        System::assert_last_event(Event::StagedEpochCompleted(netuid, 3).into());
+        let tuples = SubtensorModule::get_loaded_emission_tuples(netuid).unwrap();
+        assert_eq!(tuples.len(), n as usize);
+        let emitted: u64 = tuples.iter().map(|(_, se, ve)| se + ve).sum();
+        assert!(emitted > 0 && emitted <= gathered);
+        assert_eq!(SubtensorModule::get_blocks_since_last_step(netuid), 0);
+
+        // Emission queued after the epoch was staged is left for the next epoch.
+        assert_eq!(SubtensorModule::get_pending_emission(netuid), 5 * gathered);
+    });
+}
+
+#[test]
+fn test_staged_epoch_advances_in_chunks() {
+    new_test_ext(1).execute_with(|| {
+        let n: u16 = 16;
+        let netuid: u16 = 1;
+        add_staged_epoch_network(netuid, n);
+        System::set_block_number(3);
+
+        // A budget of a single chunk advances the cursor by at least one uid at a time.
+        let budget: Weight = <Test as Config>::DbWeight::get().reads(1) + Weight::from_parts(1, 0);
+        let mut phases: Vec<StagedEpochPhase> = Vec::new();
+        let mut chunks: u32 = 0;
+        loop {
+            let weight: Weight = SubtensorModule::advance_staged_epoch(netuid, 8, budget);
+            assert!(weight.ref_time() > 0);
+            chunks += 1;
+            let cursor = StagedEpoch::<Test>::get(netuid).unwrap();
+            if phases.last() != Some(&cursor.phase) {
+                phases.push(cursor.phase);
+            }
+            if cursor.phase == StagedEpochPhase::Done {
+                break;
+            }
+            assert!(chunks < 1_000);
+        }
+        assert_eq!(
+            phases,
+            vec![
+                StagedEpochPhase::Gather,
+                StagedEpochPhase::Weights,
+                StagedEpochPhase::Consensus,
+                StagedEpochPhase::Clip,
+                StagedEpochPhase::Bonds,
+                StagedEpochPhase::Dividends,
+                StagedEpochPhase::Write,
+                StagedEpochPhase::Done,
+            ]
+        );
+        assert!(chunks > 6 * n as u32);
+
+        // A cursor staged for another epoch block is started over.
+        SubtensorModule::advance_staged_epoch(netuid, 19, budget);
+        let cursor = StagedEpoch::<Test>::get(netuid).unwrap();
+        assert_eq!(cursor.epoch_block, 19);
+        assert_eq!(cursor.phase, StagedEpochPhase::Gather);
+    });
+}
+
+#[test]
+fn test_staged_epoch_carries_over_past_tempo() {
+    new_test_ext(1).execute_with(|| {
+        let n: u16 = 16;
+        let netuid: u16 = 1;
+        add_staged_epoch_network(netuid, n);
+
+        // Shrink the block budget to a single chunk, which runs out before tempo.
+        let read: u64 = <Test as Config>::DbWeight::get().reads(1).ref_time();
+        let mut block_weights = BlockWeights::get();
+        block_weights.max_block = Weight::from_parts(
+            (read + 100) * 100 / pallet_subtensor::STAGED_EPOCH_BLOCK_WEIGHT_PERCENT,
+            0,
+        );
+        BlockWeights::set(block_weights);
+        for block in 3..=8 {
+            System::set_block_number(block);
+            SubtensorModule::generate_emission(block);
+        }
+
+        // The epoch is left unfinished at tempo and nothing is loaded yet.
+        let cursor = StagedEpoch::<Test>::get(netuid).unwrap();
+        assert_eq!(cursor.epoch_block, 8);
+        assert_ne!(cursor.phase, StagedEpochPhase::Done);
+        assert!(SubtensorModule::get_loaded_emission_tuples(netuid).is_none());
+        assert_ne!(SubtensorModule::get_last_mechanism_step_block(netuid), 8);
+
+        // It carries over to the next blocks, over later tempos, until done.
+        let mut block: u64 = 8;
+        while StagedEpoch::<Test>::contains_key(netuid) {
+            block += 1;
+            System::set_block_number(block);
+            SubtensorModule::generate_emission(block);
+            assert!(block < 1_000);
+        }
+        System::assert_last_event(Event::StagedEpochCompleted(netuid, 3).into());
+        let tuples = SubtensorModule::get_loaded_emission_tuples(netuid).unwrap();
+        assert_eq!(tuples.len(), n as usize);
+        let emitted: u64 = tuples.iter().map(|(_, se, ve)| se + ve).sum();
+        assert!(emitted > 0 && emitted <= cursor.rao_emission);
+        assert_eq!(SubtensorModule::get_blocks_since_last_step(netuid), 0);
+        assert_eq!(
+            SubtensorModule::get_last_mechanism_step_block(netuid),
+            block
+        );
+    });
+}
+
+#[test]
+fn test_staged_epoch_matches_epoch() {
+    let n: u16 = 16;
+    let netuid: u16 = 1;
+    let staged = new_test_ext(1).execute_with(|| {
+        add_staged_epoch_network(netuid, n);
+        System::set_block_number(3);
+        SubtensorModule::generate_emission(3);
+        let emission: u64 = SubtensorModule::get_pending_emission(netuid);
+        System::set_block_number(8);
+        SubtensorModule::generate_emission(8);
+        (
+            emission,
+            SubtensorModule::get_rank(netuid),
+            SubtensorModule::get_trust(netuid),
+            SubtensorModule::get_consensus(netuid),
+            SubtensorModule::get_incentive(netuid),
+            SubtensorModule::get_dividends(netuid),
+            SubtensorModule::get_validator_trust(netuid),
+            SubtensorModule::get_emission(netuid),
+            SubtensorModule::get_validator_permit(netuid),
+            SubtensorModule::get_bonds(netuid),
+        )
+    });
+    new_test_ext(1).execute_with(|| {
+        add_staged_epoch_network(netuid, n);
+        System::set_block_number(3);
+        PendingEmission::<Test>::insert(netuid, staged.0);
+        SubtensorModule::epoch(netuid, None);
+        assert_eq!(
+            staged,
+            (
+                staged.0,
+                SubtensorModule::get_rank(netuid),
+                SubtensorModule::get_trust(netuid),
+                SubtensorModule::get_consensus(netuid),
+                SubtensorModule::get_incentive(netuid),
+                SubtensorModule::get_dividends(netuid),
+                SubtensorModule::get_validator_trust(netuid),
+                SubtensorModule::get_emission(netuid),
+                SubtensorModule::get_validator_permit(netuid),
+                SubtensorModule::get_bonds(netuid),
+            )
+        );
+    });
+}
+
+#[test]
+fn test_staged_epoch_keeps_neurons_registered_mid_stage() {
+    new_test_ext(1).execute_with(|| {
+        let n: u16 = 4;
+        let netuid: u16 = 1;
+        add_staged_epoch_network(netuid, n);
+
+        // Append a uid and replace uid 0 once the first uid is gathered.
+        System::set_block_number(3);
+        PendingEmission::<Test>::insert(netuid, 1_000_000_000);
+        let budget: Weight = <Test as Config>::DbWeight::get().reads(1) + Weight::from_parts(1, 0);
+        SubtensorModule::advance_staged_epoch(netuid, 8, budget);
+        assert_eq!(StagedEpoch::<Test>::get(netuid).unwrap().next, 1);
+        SubtensorModule::append_neuron(netuid, &U256::from(100), 3);
+        SubtensorModule::replace_neuron(netuid, 0, &U256::from(101), 3);
+        SubtensorModule::set_emission_values(&[netuid], vec![0]).unwrap();
+        System::set_block_number(8);
+        SubtensorModule::generate_emission(8);
+
+        // The epoch pays the hotkeys it was gathered with, except the replaced one, whose
+        // emission is left pending for the next epoch.
+        let tuples = SubtensorModule::get_loaded_emission_tuples(netuid).unwrap();
+        assert_eq!(tuples.len(), n as usize - 1);
+        assert!(tuples
+            .iter()
+            .all(|(hotkey, _, _)| *hotkey != U256::from(0) && *hotkey != U256::from(101)));
+        assert!(tuples
+            .iter()
+            .any(|(hotkey, _, ve)| *hotkey == U256::from(1) && *ve > 0));
+        let emitted: u64 = tuples.iter().map(|(_, se, ve)| se + ve).sum();
+        let pending: u64 = SubtensorModule::get_pending_emission(netuid);
+        assert!(pending > 0);
+        assert!(emitted + pending <= 1_000_000_000);
+
+        // Neither the appended nor the replaced uid takes on the epoch terms, the replaced uid
+        // keeps its current terms and bonds.
+        let emission = SubtensorModule::get_emission(netuid);
+        let permits = SubtensorModule::get_validator_permit(netuid);
+        assert_eq!(emission.len(), n as usize + 1);
+        assert_eq!(permits.len(), n as usize + 1);
+        assert_eq!(emission[0], 0);
+        assert!(emission[1] > 0);
+        assert_eq!(emission[n as usize], 0);
+        assert!(permits[0]);
+        assert!(permits[1]);
+        assert!(!permits[n as usize]);
+        assert_eq!(
+            Bonds::<Test>::get(netuid, 0),
+            vec![(2, u16::MAX), (3, u16::MAX / 2)]
+        );
+
+        // The next epoch runs over the grown subnet.
+        SubtensorModule::epoch(netuid, None);
+        assert_eq!(SubtensorModule::get_incentive(netuid).len(), n as usize + 1);
+    });
+}
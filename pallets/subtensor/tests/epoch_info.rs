@@ -0,0 +1,181 @@
+#![allow(
+    clippy::arithmetic_side_effects,
+    clippy::indexing_slicing,
+    clippy::unwrap_used
+)]
+
+mod mock;
+use codec::{Compact, Decode, Encode};
+use mock::*;
+use pallet_subtensor::{
+    epoch_info::{EpochOverrides, EpochTrace},
+    EpochHistory, EpochHistoryIndex, PendingEmission, MAX_EPOCH_TRACE_MATRIX_ENTRIES,
+};
+use sp_core::U256;
+use substrate_fixed::types::I32F32;
+
+#[test]
+#[cfg(not(tarpaulin))]
+fn test_simulate_epoch() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        assert_eq!(
+            SubtensorModule::simulate_epoch(netuid, EpochOverrides::default()),
+            None
+        );
+
+        add_weighted_network(netuid, [(1_000, 2), (1_000, 2)]);
+        SubtensorModule::epoch(netuid, None);
+        let incentive = SubtensorModule::get_incentive(netuid);
+        let kappa = SubtensorModule::get_kappa(netuid);
+        assert!(incentive[2] > 0);
+
+        // Without overrides the simulation reproduces the live epoch.
+        let simulation =
+            SubtensorModule::simulate_epoch(netuid, EpochOverrides::default()).unwrap();
+        let expected: Vec<Compact<u16>> = incentive.iter().map(|&i| Compact(i)).collect();
+        assert_eq!(simulation.incentive, expected);
+
+        // Moving both validators onto uid 3 moves the incentive with them.
+        let overrides = EpochOverrides {
+            kappa: Some(u16::MAX / 4),
+            weights: Some(vec![(0, vec![(3, u16::MAX)]), (1, vec![(3, u16::MAX)])]),
+            stake: Some(vec![(1, 0)]),
+            ..Default::default()
+        };
+        let simulation = SubtensorModule::simulate_epoch(netuid, overrides.clone()).unwrap();
+        assert_eq!(simulation.incentive[2], Compact(0));
+        assert!(simulation.incentive[3].0 > 0);
+        assert_eq!(simulation.incentive.len(), 4);
+        assert_eq!(simulation.emission.len(), 4);
+
+        // Nothing is written back, overrides included.
+        assert_eq!(SubtensorModule::get_incentive(netuid), incentive);
+        assert_eq!(SubtensorModule::get_kappa(netuid), kappa);
+        assert_eq!(
+            SubtensorModule::get_total_stake_for_hotkey(&U256::from(1)),
+            1_000
+        );
+        assert_eq!(
+            SubtensorModule::get_weights_sparse(netuid)[0],
+            vec![(2, I32F32::from_num(u16::MAX))]
+        );
+
+        // The runtime API entry point decodes the overrides.
+        assert_eq!(
+            SubtensorModule::get_epoch_simulation(netuid, overrides.encode()),
+            SubtensorModule::simulate_epoch(netuid, overrides)
+        );
+        assert!(SubtensorModule::get_epoch_simulation(netuid, vec![]).is_some());
+        assert!(SubtensorModule::get_epoch_simulation(netuid, vec![0xff]).is_none());
+    });
+}
+
+#[test]
+#[cfg(not(tarpaulin))]
+fn test_get_epoch_trace() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        assert_eq!(SubtensorModule::get_epoch_trace(netuid), None);
+
+        add_weighted_network(netuid, [(1_000, 2), (1_000, 2)]);
+        SubtensorModule::epoch(netuid, None);
+        let incentive = SubtensorModule::get_incentive(netuid);
+        let bonds = SubtensorModule::get_bonds_sparse(netuid);
+
+        let trace = SubtensorModule::get_epoch_trace(netuid).unwrap();
+        assert_eq!(trace.netuid, netuid);
+        assert_eq!(trace.block, 2);
+        assert!(!trace.truncated);
+        assert_eq!(trace.consensus.len(), 4);
+        assert_eq!(trace.preranks.len(), 4);
+        assert_eq!(trace.validator_trust.len(), 4);
+        assert_eq!(trace.dividends.len(), 4);
+        for uid in 0..2 {
+            assert_eq!(trace.weights[uid], vec![(2, I32F32::from_num(1))]);
+            assert_eq!(trace.clipped_weights[uid].len(), 1);
+            assert_eq!(trace.clipped_weights[uid][0].0, 2);
+            assert_eq!(trace.bonds_delta[uid].len(), 1);
+        }
+        assert!(trace.consensus[2] > I32F32::from_num(0));
+        assert!(trace.ranks[2] > I32F32::from_num(0));
+
+        // The traced epoch is not written back.
+        assert_eq!(SubtensorModule::get_incentive(netuid), incentive);
+        assert_eq!(SubtensorModule::get_bonds_sparse(netuid), bonds);
+    });
+}
+
+#[test]
+fn test_epoch_trace_record_sparse_cap() {
+    let cap = MAX_EPOCH_TRACE_MATRIX_ENTRIES as usize;
+    let row: Vec<(u16, I32F32)> = vec![(0, I32F32::from_num(1)); cap / 2];
+    let mut trace = EpochTrace::default();
+
+    let recorded = trace.record_sparse(&[row.clone(), row.clone()]);
+    assert_eq!(recorded, vec![row.clone(), row.clone()]);
+    assert!(!trace.truncated);
+
+    let recorded = trace.record_sparse(&[row.clone(), vec![(1, I32F32::from_num(1))], row.clone()]);
+    assert_eq!(recorded[0], row);
+    assert_eq!(recorded[1].len(), 1);
+    assert_eq!(recorded[2].len(), cap / 2 - 1);
+    assert!(trace.truncated);
+}
+
+#[test]
+#[cfg(not(tarpaulin))]
+fn test_epoch_history() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        add_weighted_network(netuid, [(1_000, 2), (1_000, 2)]);
+        SubtensorModule::epoch(netuid, None);
+        PendingEmission::<Test>::insert(netuid, 1_000_000);
+
+        // History is off by default.
+        SubtensorModule::epoch(netuid, None);
+        assert_eq!(EpochHistoryIndex::<Test>::get(netuid), 0);
+        assert!(SubtensorModule::get_epoch_history(netuid, 2, 10).is_empty());
+
+        // With a depth of 2 the third epoch overwrites the first.
+        SubtensorModule::set_epoch_history_depth(netuid, 2);
+        for block in 3..6 {
+            run_to_block(block);
+            SubtensorModule::epoch(netuid, None);
+        }
+        assert_eq!(EpochHistoryIndex::<Test>::get(netuid), 3);
+        assert_eq!(EpochHistory::<Test>::get(netuid, 0).unwrap().block, 5);
+        assert_eq!(EpochHistory::<Test>::get(netuid, 1).unwrap().block, 4);
+        assert_eq!(EpochHistory::<Test>::get(netuid, 2), None);
+
+        // Newest first, as (block, emission_total, incentive, dividends, emission).
+        type Entry = (
+            Compact<u64>,
+            Compact<u64>,
+            Compact<u16>,
+            Compact<u16>,
+            Compact<u64>,
+        );
+        let history = SubtensorModule::get_epoch_history(netuid, 2, 10);
+        let entries: Vec<Entry> = Decode::decode(&mut history.encode().as_slice()).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].0, Compact(5));
+        assert_eq!(entries[1].0, Compact(4));
+        assert_eq!(entries[0].1, Compact(1_000_000));
+        assert_eq!(entries[0].2, Compact(u16::MAX));
+        assert_eq!(
+            entries[0].4,
+            Compact(SubtensorModule::get_emission_for_uid(netuid, 2))
+        );
+        assert_eq!(SubtensorModule::get_epoch_history(netuid, 2, 1).len(), 1);
+        // Unknown uids read as zero.
+        let history = SubtensorModule::get_epoch_history(netuid, 100, 1);
+        let entries: Vec<Entry> = Decode::decode(&mut history.encode().as_slice()).unwrap();
+        assert_eq!(entries[0].2, Compact(0));
+
+        // Changing the depth drops the recorded history.
+        SubtensorModule::set_epoch_history_depth(netuid, 3);
+        assert!(SubtensorModule::get_epoch_history(netuid, 2, 10).is_empty());
+        assert_eq!(EpochHistory::<Test>::get(netuid, 0), None);
+    });
+}
@@ -0,0 +1,119 @@
+#![allow(clippy::arithmetic_side_effects, clippy::indexing_slicing)]
+
+mod mock;
+use mock::*;
+use pallet_subtensor::{ConsensusMechanism, EpochReturnType, PendingEmission, ValidatorAgreement};
+use sp_core::U256;
+
+#[test]
+#[cfg(not(tarpaulin))]
+fn test_consensus_mechanism_yuma_clips_minority_weights() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        add_weighted_network(netuid, [(3_000, 2), (1_000, 3)]);
+        assert_eq!(
+            SubtensorModule::get_consensus_mechanism(netuid),
+            ConsensusMechanism::Yuma
+        );
+
+        SubtensorModule::epoch(netuid, None);
+        let incentive = SubtensorModule::get_incentive(netuid);
+        assert_eq!(incentive[2], u16::MAX);
+        assert_eq!(incentive[3], 0);
+        assert!(!SubtensorModule::get_bonds_sparse(netuid)[0].is_empty());
+    });
+}
+
+#[test]
+#[cfg(not(tarpaulin))]
+fn test_consensus_mechanism_stake_weighted_mean() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        add_weighted_network(netuid, [(3_000, 2), (1_000, 3)]);
+        SubtensorModule::epoch(netuid, None);
+        assert!(!SubtensorModule::get_bonds_sparse(netuid)[0].is_empty());
+
+        SubtensorModule::set_consensus_mechanism(netuid, ConsensusMechanism::StakeWeightedMean);
+        SubtensorModule::epoch(netuid, None);
+
+        // Incentive follows stake without clipping the minority validator.
+        let incentive = SubtensorModule::get_incentive(netuid);
+        assert!((incentive[2] as i32 - (u16::MAX as i32 * 3 / 4)).abs() <= 1);
+        assert!((incentive[3] as i32 - (u16::MAX as i32 / 4)).abs() <= 1);
+        // Nothing is clipped, so there is no trust.
+        assert_eq!(SubtensorModule::get_trust_for_uid(netuid, 3), 0);
+        assert_eq!(SubtensorModule::get_validator_trust_for_uid(netuid, 0), 0);
+        // Nor is any of the validators' weight reported as clipped.
+        for uid in 0..2 {
+            assert_eq!(ValidatorAgreement::<Test>::get(netuid, uid).1, 0);
+        }
+
+        // Dividends follow stake times the incentive of the weighted uid.
+        let dividends = SubtensorModule::get_dividends(netuid);
+        assert!(dividends[0] > dividends[1]);
+        assert!(dividends[1] > 0);
+
+        // Bonds are not used and are cleared.
+        for uid in 0..2 {
+            assert!(SubtensorModule::get_bonds_sparse(netuid)[uid].is_empty());
+        }
+    });
+}
+
+#[test]
+#[cfg(not(tarpaulin))]
+fn test_stake_weighted_mean_emission_values() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        add_weighted_network(netuid, [(3_000, 2), (1_000, 3)]);
+        SubtensorModule::set_consensus_mechanism(netuid, ConsensusMechanism::StakeWeightedMean);
+        PendingEmission::<Test>::insert(netuid, 1_000_000);
+
+        let EpochReturnType::Emission(emission) = SubtensorModule::epoch(netuid, None) else {
+            panic!("the epoch returns emission tuples");
+        };
+        let close = |actual: u64, expected: u64| actual.abs_diff(expected) <= 1;
+
+        // Incentive is the stake-weighted mean of the weights: 3/4 and 1/4.
+        let incentive = SubtensorModule::get_incentive(netuid);
+        assert!(close(incentive[2] as u64, 49_151));
+        assert!(close(incentive[3] as u64, 16_384));
+
+        // Dividends are 3/4 * 3/4 and 1/4 * 1/4, normalized to 0.9 and 0.1.
+        let dividends = SubtensorModule::get_dividends(netuid);
+        assert!(close(dividends[0] as u64, 58_982));
+        assert!(close(dividends[1] as u64, 6_554));
+
+        // Servers and validators split the emission evenly.
+        let by_uid = |uid: u64| {
+            emission
+                .iter()
+                .find(|(hotkey, _, _)| *hotkey == U256::from(uid))
+                .map(|(_, server, validator)| (*server, *validator))
+                .unwrap_or_default()
+        };
+        assert!(close(by_uid(2).0, 375_000));
+        assert!(close(by_uid(3).0, 125_000));
+        assert!(close(by_uid(0).1, 450_000));
+        assert!(close(by_uid(1).1, 50_000));
+    });
+}
+
+#[test]
+#[cfg(not(tarpaulin))]
+fn test_epoch_dense_uses_consensus_mechanism() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        add_weighted_network(netuid, [(3_000, 2), (1_000, 3)]);
+        SubtensorModule::set_consensus_mechanism(netuid, ConsensusMechanism::StakeWeightedMean);
+
+        SubtensorModule::epoch_dense(netuid, 1_000_000);
+
+        // The minority server is not clipped away as it would be under Yuma consensus.
+        let incentive = SubtensorModule::get_incentive(netuid);
+        assert!((incentive[3] as i32 - (u16::MAX as i32 / 4)).abs() <= 1);
+        for uid in 0..2 {
+            assert!(SubtensorModule::get_bonds_sparse(netuid)[uid].is_empty());
+        }
+    });
+}
@@ -1208,6 +1208,31 @@ fn test_math_row_sum_sparse() {
     assert_vec_compare(&result, &target, I32F32::from_num(0));
 }
 
+#[test]
+fn test_math_row_cosine_similarity_sparse() {
+    let matrix: Vec<f32> = vec![1., 0., 0., 0., 2., 0., 1., 1., 0., 0., 0., 0.];
+    let matrix = vec_to_sparse_mat_fixed(&matrix, 4, false);
+    let vector: Vec<I32F32> = vec_to_fixed(&[1., 1., 0.]);
+    let result = row_cosine_similarity_sparse(&matrix, &vector);
+    let target: Vec<I32F32> = vec_to_fixed(&[0.70710678, 0.70710678, 1., 0.]);
+    assert_vec_compare(&result, &target, I32F32::from_num(0.0001));
+    let result = row_cosine_similarity_sparse(&matrix, &vec_to_fixed(&[0., 0., 0.]));
+    let target: Vec<I32F32> = vec_to_fixed(&[0., 0., 0., 0.]);
+    assert_vec_compare(&result, &target, I32F32::from_num(0));
+}
+
+#[test]
+fn test_math_duplicate_rows_sparse() {
+    let matrix: Vec<f32> = vec![1., 2., 0., 1., 2., 3., 0., 0., 0., 1., 2., 0., 0., 0., 0.];
+    let matrix = vec_to_sparse_mat_fixed(&matrix, 5, false);
+    assert_eq!(
+        duplicate_rows_sparse(&matrix),
+        vec![true, false, false, true, false]
+    );
+    let matrix = vec_to_sparse_mat_fixed(&[1., 2., 3., 4.], 2, false);
+    assert_eq!(duplicate_rows_sparse(&matrix), vec![false, false]);
+}
+
 #[test]
 fn test_math_col_sum() {
     let matrix: Vec<f32> = vec![1., 2., 3., 4., 5., 6., 7., 8., 9., 10., 11., 12.];
@@ -2250,6 +2275,41 @@ fn test_mat_ema_alpha_vec_sparse_sparse_matrix() {
     );
 }
 
+#[test]
+fn test_row_ema_alpha_vec_sparse_matches_matrix() {
+    let new: Vec<Vec<(u16, I32F32)>> = vec![
+        vec![(0, I32F32::from_num(1.0)), (2, I32F32::from_num(2.0))],
+        vec![(1, I32F32::from_num(4.0))],
+        vec![],
+    ];
+    let old: Vec<Vec<(u16, I32F32)>> = vec![
+        vec![(1, I32F32::from_num(5.0)), (2, I32F32::from_num(6.0))],
+        vec![],
+        vec![(0, I32F32::from_num(8.0))],
+    ];
+    let alpha: Vec<I32F32> = vec![
+        I32F32::from_num(0.3),
+        I32F32::from_num(0.5),
+        I32F32::from_num(0.7),
+    ];
+    let expected = mat_ema_alpha_vec_sparse(&new, &old, &alpha);
+    for (i, (new_row, old_row)) in new.iter().zip(&old).enumerate() {
+        assert_eq!(
+            row_ema_alpha_vec_sparse(new_row, old_row, &alpha, 3),
+            expected[i]
+        );
+    }
+}
+
+#[test]
+fn test_row_ema_alpha_vec_sparse_drops_out_of_range_columns() {
+    let new: Vec<(u16, I32F32)> = vec![(0, I32F32::from_num(1.0)), (3, I32F32::from_num(1.0))];
+    let old: Vec<(u16, I32F32)> = vec![(0, I32F32::from_num(3.0))];
+    let alpha: Vec<I32F32> = vec![I32F32::from_num(0.5); 4];
+    let result = row_ema_alpha_vec_sparse(&new, &old, &alpha, 2);
+    assert_eq!(result, vec![(0, I32F32::from_num(2.0))]);
+}
+
 #[test]
 fn test_mat_ema_alpha_vec_basic() {
     let new = mat_to_fixed(&[vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
@@ -1,10 +1,16 @@
 #![allow(clippy::unwrap_used)]
 
 mod mock;
+use codec::Encode;
 use frame_support::assert_ok;
+use frame_support::traits::{GetStorageVersion, StorageVersion};
 use frame_system::Config;
 use mock::*;
-use sp_core::U256;
+use pallet_subtensor::{
+    DelegateNominatorCount, NominationAllowlist, NominationAllowlistLen, Stake, StakeRateLimitMode,
+    WeightCommits,
+};
+use sp_core::{H256, U256};
 
 #[test]
 fn test_migration_fix_total_stake_maps() {
@@ -276,3 +282,68 @@ fn test_migration_delete_subnet_21() {
         assert!(!SubtensorModule::if_subnet_exist(21));
     })
 }
+
+#[test]
+fn test_migration_to_v7_stake_amount_rate_limit() {
+    new_test_ext(1).execute_with(|| {
+        StorageVersion::new(6).put::<SubtensorModule>();
+        SubtensorModule::set_stake_interval(100);
+        SubtensorModule::set_stake_rate_limit_mode(StakeRateLimitMode::Amount);
+
+        pallet_subtensor::migration::migrate_to_v7_stake_amount_rate_limit::<Test>();
+
+        // The count based limiter stays selected and both limiters share the interval.
+        assert_eq!(
+            SubtensorModule::get_stake_rate_limit_mode(),
+            StakeRateLimitMode::Count
+        );
+        assert_eq!(SubtensorModule::get_stake_amount_window(), 100);
+        assert_eq!(SubtensorModule::on_chain_storage_version(), 7);
+
+        // Running it again does nothing.
+        SubtensorModule::set_stake_amount_window(10);
+        pallet_subtensor::migration::migrate_to_v7_stake_amount_rate_limit::<Test>();
+        assert_eq!(SubtensorModule::get_stake_amount_window(), 10);
+    });
+}
+
+#[test]
+fn test_migration_to_v8_weight_commits_queue() {
+    new_test_ext(1).execute_with(|| {
+        StorageVersion::new(7).put::<SubtensorModule>();
+        let hotkey = U256::from(1);
+        let commit = (H256::repeat_byte(1), 5u64);
+
+        // Write a commit in the single entry format.
+        frame_support::storage::unhashed::put_raw(
+            &WeightCommits::<Test>::hashed_key_for(1, hotkey),
+            &commit.encode(),
+        );
+
+        pallet_subtensor::migration::migrate_to_v8_weight_commits_queue::<Test>();
+
+        assert_eq!(
+            WeightCommits::<Test>::get(1, hotkey).map(Vec::from),
+            Some(vec![commit])
+        );
+        assert_eq!(SubtensorModule::on_chain_storage_version(), 8);
+    });
+}
+
+#[test]
+fn test_migration_to_v9_delegate_nominator_count() {
+    new_test_ext(1).execute_with(|| {
+        StorageVersion::new(8).put::<SubtensorModule>();
+        let hotkey = U256::from(1);
+        Stake::<Test>::insert(hotkey, U256::from(2), 1_000);
+        Stake::<Test>::insert(hotkey, U256::from(3), 2_000);
+        Stake::<Test>::insert(hotkey, U256::from(4), 0);
+        NominationAllowlist::<Test>::insert(hotkey, U256::from(3), true);
+
+        pallet_subtensor::migration::migrate_to_v9_delegate_nominator_count::<Test>();
+
+        assert_eq!(DelegateNominatorCount::<Test>::get(hotkey), 2);
+        assert_eq!(NominationAllowlistLen::<Test>::get(hotkey), 1);
+        assert_eq!(SubtensorModule::on_chain_storage_version(), 9);
+    });
+}
@@ -115,7 +115,7 @@ parameter_types! {
     pub const InitialMinAllowedWeights: u16 = 0;
     pub const InitialEmissionValue: u16 = 0;
     pub const InitialMaxWeightsLimit: u16 = u16::MAX;
-    pub BlockWeights: limits::BlockWeights = limits::BlockWeights::with_sensible_defaults(
+    pub static BlockWeights: limits::BlockWeights = limits::BlockWeights::with_sensible_defaults(
         Weight::from_parts(2_000_000_000_000, u64::MAX),
         Perbill::from_percent(75),
     );
@@ -137,6 +137,7 @@ parameter_types! {
     pub const InitialServingRateLimit: u64 = 0; // No limit.
     pub const InitialTxRateLimit: u64 = 0; // Disable rate limit for testing
     pub const InitialTxDelegateTakeRateLimit: u64 = 1; // 1 block take rate limit for testing
+    pub const InitialTakeIncreaseDelay: u64 = 10; // 10 blocks notice period for testing
     pub const InitialBurn: u64 = 0;
     pub const InitialMinBurn: u64 = 0;
     pub const InitialMaxBurn: u64 = 1_000_000_000;
@@ -164,11 +165,14 @@ parameter_types! {
     pub const InitialSubnetLimit: u16 = 10; // Max 10 subnets.
     pub const InitialNetworkRateLimit: u64 = 0;
     pub const InitialTargetStakesPerInterval: u16 = 2;
+    pub const InitialStakeRateLimitMode: pallet_subtensor::StakeRateLimitMode = pallet_subtensor::StakeRateLimitMode::Count;
+    pub const InitialMaxStakeAmountPerWindow: u64 = u64::MAX; // Uncapped until set by governance
     pub const InitialHotkeySwapCost: u64 = 1_000_000_000;
     pub const InitialAlphaHigh: u16 = 58982; // Represents 0.9 as per the production default
     pub const InitialAlphaLow: u16 = 45875; // Represents 0.7 as per the production default
     pub const InitialLiquidAlphaOn: bool = false; // Default value for LiquidAlphaOn
     pub const SubtensorInitialBaseDifficulty: u64 = 10_000; // Base difficulty
+    pub const MaxWeightCommits: u32 = 10;
 }
 
 // Configure collective pallet for council
@@ -356,6 +360,7 @@ impl pallet_subtensor::Config for Test {
     type InitialServingRateLimit = InitialServingRateLimit;
     type InitialTxRateLimit = InitialTxRateLimit;
     type InitialTxDelegateTakeRateLimit = InitialTxDelegateTakeRateLimit;
+    type InitialTakeIncreaseDelay = InitialTakeIncreaseDelay;
     type InitialBurn = InitialBurn;
     type InitialMaxBurn = InitialMaxBurn;
     type InitialMinBurn = InitialMinBurn;
@@ -369,11 +374,14 @@ impl pallet_subtensor::Config for Test {
     type InitialSubnetLimit = InitialSubnetLimit;
     type InitialNetworkRateLimit = InitialNetworkRateLimit;
     type InitialTargetStakesPerInterval = InitialTargetStakesPerInterval;
+    type InitialStakeRateLimitMode = InitialStakeRateLimitMode;
+    type InitialMaxStakeAmountPerWindow = InitialMaxStakeAmountPerWindow;
     type HotkeySwapCost = InitialHotkeySwapCost;
     type AlphaHigh = InitialAlphaHigh;
     type AlphaLow = InitialAlphaLow;
     type LiquidAlphaOn = InitialLiquidAlphaOn;
     type InitialBaseDifficulty = SubtensorInitialBaseDifficulty;
+    type MaxWeightCommits = MaxWeightCommits;
 }
 
 impl pallet_utility::Config for Test {
@@ -488,3 +496,62 @@ pub fn add_network(netuid: u16, tempo: u16, _modality: u16) {
     SubtensorModule::set_network_registration_allowed(netuid, true);
     SubtensorModule::set_network_pow_registration_allowed(netuid, true);
 }
+
+// Registers two staked validators (uids 0 and 1) and two servers (uids 2 and 3) and runs an
+// epoch to hand out the validator permits. Each validator then puts all of its weight on the
+// server given with its stake in `validators`.
+#[allow(dead_code)]
+pub fn add_weighted_network(netuid: u16, validators: [(u64, u16); 2]) {
+    add_network(netuid, u16::MAX - 1, 0);
+    SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+    SubtensorModule::set_max_registrations_per_block(netuid, 4);
+    SubtensorModule::set_target_registrations_per_interval(netuid, 4);
+    for uid in 0..4u64 {
+        register_ok_neuron(netuid, U256::from(uid), U256::from(uid), uid * 100_000);
+    }
+    for (uid, (stake, _)) in validators.iter().enumerate() {
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(
+            &U256::from(uid),
+            &U256::from(uid),
+            *stake,
+        );
+    }
+    SubtensorModule::epoch(netuid, None);
+    run_to_block(2);
+    for (uid, (_, server)) in validators.iter().enumerate() {
+        assert_ok!(SubtensorModule::set_weights(
+            RuntimeOrigin::signed(U256::from(uid)),
+            netuid,
+            vec![*server],
+            vec![u16::MAX],
+            0
+        ));
+    }
+}
+
+// Adds a subnet with tempo 10 and `n` neurons. Its epoch runs at block 8, so the epoch is
+// staged from block 3. Uids 0 and 1 hold stake and validator permits and weight every uid.
+#[allow(dead_code)]
+pub fn add_staged_epoch_network(netuid: u16, n: u16) {
+    add_network(netuid, 10, 0);
+    SubtensorModule::set_max_allowed_uids(netuid, n + 1);
+    SubtensorModule::set_emission_values(&[netuid], vec![1_000_000_000]).unwrap();
+    for i in 0..n {
+        SubtensorModule::append_neuron(netuid, &U256::from(i), 0);
+    }
+    for i in 0..2 {
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(
+            &U256::from(i),
+            &U256::from(i),
+            1_000 * (i as u64 + 1),
+        );
+        SubtensorModule::set_validator_permit_for_uid(netuid, i, true);
+        let row: Vec<(u16, u16)> = (0..n).map(|j| (j, (j + i + 1) * 1_000)).collect();
+        pallet_subtensor::Weights::<Test>::insert(netuid, i, row);
+        pallet_subtensor::Bonds::<Test>::insert(netuid, i, vec![(2, u16::MAX), (3, u16::MAX / 2)]);
+    }
+    assert_eq!(
+        SubtensorModule::blocks_until_next_epoch(netuid, 10, 3),
+        SubtensorModule::get_staged_epoch_lead_blocks(10)
+    );
+}
@@ -1,5 +1,7 @@
 mod mock;
+use frame_support::assert_ok;
 use mock::*;
+use pallet_subtensor::ValidatorAgreement;
 
 use sp_core::U256;
 
@@ -71,3 +73,122 @@ fn test_get_neurons_empty() {
         assert_eq!(neurons.len(), neuron_count as usize);
     });
 }
+
+#[test]
+fn test_validator_agreement() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        add_network(netuid, u16::MAX - 1, 0);
+        SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+        SubtensorModule::set_max_registrations_per_block(netuid, 3);
+        SubtensorModule::set_target_registrations_per_interval(netuid, 3);
+
+        // Two validators with equal stake and one server.
+        for uid in 0..3u64 {
+            register_ok_neuron(netuid, U256::from(uid), U256::from(uid), uid * 100_000);
+        }
+        for uid in 0..2u64 {
+            SubtensorModule::increase_stake_on_coldkey_hotkey_account(
+                &U256::from(uid),
+                &U256::from(uid),
+                1_000,
+            );
+        }
+
+        // The first epoch hands out the validator permits.
+        SubtensorModule::epoch(netuid, None);
+        assert!(SubtensorModule::get_validator_agreement(netuid).is_empty());
+
+        // Both validators put all weight on the server.
+        run_to_block(2);
+        for uid in 0..2u64 {
+            assert_ok!(SubtensorModule::set_weights(
+                RuntimeOrigin::signed(U256::from(uid)),
+                netuid,
+                vec![2],
+                vec![u16::MAX],
+                0
+            ));
+        }
+        SubtensorModule::epoch(netuid, None);
+        SubtensorModule::epoch(netuid, None);
+
+        for uid in 0..2u16 {
+            let (similarity, clipped_share, identical_epochs) =
+                ValidatorAgreement::<Test>::get(netuid, uid);
+            assert!(similarity >= u16::MAX - 1);
+            assert_eq!(clipped_share, 0);
+            assert_eq!(identical_epochs, 2);
+        }
+        // The server has a permit but no weights.
+        assert_eq!(ValidatorAgreement::<Test>::get(netuid, 2), (0, 0, 0));
+        assert_eq!(SubtensorModule::get_validator_agreement(netuid).len(), 3);
+
+        // Replacing a neuron resets its metrics.
+        SubtensorModule::replace_neuron(netuid, 1, &U256::from(5), 2);
+        assert!(!ValidatorAgreement::<Test>::contains_key(netuid, 1));
+    });
+}
+
+#[test]
+fn test_validator_agreement_after_weights_diverge() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        add_network(netuid, u16::MAX - 1, 0);
+        SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+        SubtensorModule::set_max_registrations_per_block(netuid, 5);
+        SubtensorModule::set_target_registrations_per_interval(netuid, 5);
+
+        // Three validators holding 40%, 40% and 20% of the stake, and two servers.
+        for uid in 0..5u64 {
+            register_ok_neuron(netuid, U256::from(uid), U256::from(uid), uid * 100_000);
+        }
+        for (uid, stake) in [(0u64, 2_000u64), (1, 2_000), (2, 1_000)] {
+            SubtensorModule::increase_stake_on_coldkey_hotkey_account(
+                &U256::from(uid),
+                &U256::from(uid),
+                stake,
+            );
+        }
+        SubtensorModule::epoch(netuid, None);
+
+        // All validators put all weight on the first server for two epochs.
+        run_to_block(2);
+        let set_weights = |uid: u64, dest: u16| {
+            assert_ok!(SubtensorModule::set_weights(
+                RuntimeOrigin::signed(U256::from(uid)),
+                netuid,
+                vec![dest],
+                vec![u16::MAX],
+                0
+            ));
+        };
+        for uid in 0..3u64 {
+            set_weights(uid, 3);
+        }
+        SubtensorModule::epoch(netuid, None);
+        SubtensorModule::epoch(netuid, None);
+        for uid in 0..3u16 {
+            assert_eq!(ValidatorAgreement::<Test>::get(netuid, uid).2, 2);
+        }
+
+        // The smallest validator moves to the second server, below the consensus majority.
+        run_to_block(3);
+        set_weights(2, 4);
+        SubtensorModule::epoch(netuid, None);
+
+        for uid in 0..2u16 {
+            let (similarity, clipped_share, identical_epochs) =
+                ValidatorAgreement::<Test>::get(netuid, uid);
+            assert!(similarity >= u16::MAX - 1);
+            assert_eq!(clipped_share, 0);
+            assert_eq!(identical_epochs, 3);
+        }
+        // Its weight is clipped away entirely and its streak starts over.
+        let (similarity, clipped_share, identical_epochs) =
+            ValidatorAgreement::<Test>::get(netuid, 2);
+        assert_eq!(similarity, 0);
+        assert_eq!(clipped_share, u16::MAX);
+        assert_eq!(identical_epochs, 0);
+    });
+}
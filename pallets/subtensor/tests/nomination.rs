@@ -0,0 +1,463 @@
+#![allow(clippy::unwrap_used)]
+
+mod mock;
+use codec::{Compact, Encode};
+use frame_support::weights::Weight;
+use frame_support::{assert_noop, assert_ok};
+use frame_system::Config;
+use mock::*;
+use pallet_subtensor::*;
+use sp_core::U256;
+
+#[test]
+fn test_nomination_allowlist() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner = U256::from(2);
+        let allowed = U256::from(3);
+        let other = U256::from(4);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, owner, 0);
+        SubtensorModule::delegate_hotkey(&hotkey, 0);
+        SubtensorModule::add_balance_to_coldkey_account(&owner, 100_000);
+        SubtensorModule::add_balance_to_coldkey_account(&allowed, 100_000);
+        SubtensorModule::add_balance_to_coldkey_account(&other, 100_000);
+
+        assert_ok!(SubtensorModule::set_nomination_allowlist_enabled(
+            <<Test as Config>::RuntimeOrigin>::signed(owner),
+            hotkey,
+            true
+        ));
+        System::assert_last_event(
+            Event::NominationAllowlistEnabledSet {
+                hotkey,
+                enabled: true,
+            }
+            .into(),
+        );
+        assert_ok!(SubtensorModule::set_nomination_allowed(
+            <<Test as Config>::RuntimeOrigin>::signed(owner),
+            hotkey,
+            allowed,
+            true
+        ));
+        System::assert_last_event(
+            Event::NominationAllowlistUpdated {
+                hotkey,
+                nominator: allowed,
+                allowed: true,
+            }
+            .into(),
+        );
+
+        assert_ok!(SubtensorModule::add_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(allowed),
+            hotkey,
+            1_000
+        ));
+        assert_noop!(
+            SubtensorModule::add_stake(
+                <<Test as Config>::RuntimeOrigin>::signed(other),
+                hotkey,
+                1_000
+            ),
+            Error::<Test>::NominatorNotAllowlisted
+        );
+
+        // The owner is never restricted.
+        assert_ok!(SubtensorModule::add_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(owner),
+            hotkey,
+            1_000
+        ));
+
+        // Opening the delegate again lets everyone nominate.
+        assert_ok!(SubtensorModule::set_nomination_allowlist_enabled(
+            <<Test as Config>::RuntimeOrigin>::signed(owner),
+            hotkey,
+            false
+        ));
+        assert!(!NominationAllowlistEnabled::<Test>::contains_key(hotkey));
+        assert_ok!(SubtensorModule::add_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(other),
+            hotkey,
+            1_000
+        ));
+    });
+}
+
+#[test]
+fn test_nomination_settings_require_delegate_owner() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner = U256::from(2);
+        let other = U256::from(3);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, owner, 0);
+        SubtensorModule::delegate_hotkey(&hotkey, 0);
+        SubtensorModule::add_balance_to_coldkey_account(&owner, 100_000);
+
+        assert_noop!(
+            SubtensorModule::set_nomination_allowlist_enabled(
+                <<Test as Config>::RuntimeOrigin>::signed(other),
+                hotkey,
+                true
+            ),
+            Error::<Test>::NonAssociatedColdKey
+        );
+        assert_noop!(
+            SubtensorModule::set_nomination_allowed(
+                <<Test as Config>::RuntimeOrigin>::signed(other),
+                hotkey,
+                other,
+                true
+            ),
+            Error::<Test>::NonAssociatedColdKey
+        );
+        assert_noop!(
+            SubtensorModule::set_nomination_limits(
+                <<Test as Config>::RuntimeOrigin>::signed(other),
+                hotkey,
+                1,
+                0
+            ),
+            Error::<Test>::NonAssociatedColdKey
+        );
+
+        // The hotkey must be a delegate.
+        let non_delegate = U256::from(4);
+        register_ok_neuron(1, non_delegate, owner, 1);
+        assert_noop!(
+            SubtensorModule::set_nomination_limits(
+                <<Test as Config>::RuntimeOrigin>::signed(owner),
+                non_delegate,
+                1,
+                0
+            ),
+            Error::<Test>::HotKeyNotDelegateAndSignerNotOwnHotKey
+        );
+    });
+}
+
+#[test]
+fn test_max_nominators() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner = U256::from(2);
+        let nominator = U256::from(3);
+        let other = U256::from(4);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, owner, 0);
+        SubtensorModule::delegate_hotkey(&hotkey, 0);
+        SubtensorModule::add_balance_to_coldkey_account(&owner, 100_000);
+        SubtensorModule::add_balance_to_coldkey_account(&nominator, 100_000);
+        SubtensorModule::add_balance_to_coldkey_account(&other, 100_000);
+
+        assert_ok!(SubtensorModule::add_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(owner),
+            hotkey,
+            1_000
+        ));
+        assert_ok!(SubtensorModule::set_nomination_limits(
+            <<Test as Config>::RuntimeOrigin>::signed(owner),
+            hotkey,
+            1,
+            0
+        ));
+        System::assert_last_event(
+            Event::NominationLimitsSet {
+                hotkey,
+                max_nominators: 1,
+                max_nominated_stake: 0,
+            }
+            .into(),
+        );
+
+        // The owner does not count as a nominator.
+        assert_ok!(SubtensorModule::add_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(nominator),
+            hotkey,
+            1_000
+        ));
+        assert_noop!(
+            SubtensorModule::add_stake(
+                <<Test as Config>::RuntimeOrigin>::signed(other),
+                hotkey,
+                1_000
+            ),
+            Error::<Test>::DelegateNominatorLimitReached
+        );
+
+        // Existing nominators can still top up.
+        assert_ok!(SubtensorModule::add_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(nominator),
+            hotkey,
+            1_000
+        ));
+        assert_eq!(DelegateNominatorCount::<Test>::get(hotkey), 2);
+
+        // Unstaking everything frees the slot.
+        assert_ok!(SubtensorModule::remove_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(nominator),
+            hotkey,
+            2_000
+        ));
+        assert_eq!(DelegateNominatorCount::<Test>::get(hotkey), 1);
+        assert_ok!(SubtensorModule::add_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(other),
+            hotkey,
+            1_000
+        ));
+        assert_eq!(DelegateNominatorCount::<Test>::get(hotkey), 2);
+    });
+}
+
+#[test]
+fn test_nomination_allowlist_is_bounded() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner = U256::from(2);
+        let allowed = U256::from(3);
+        let other = U256::from(4);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, owner, 0);
+        SubtensorModule::delegate_hotkey(&hotkey, 0);
+        SubtensorModule::add_balance_to_coldkey_account(&owner, 100_000);
+
+        assert_ok!(SubtensorModule::set_nomination_allowed(
+            <<Test as Config>::RuntimeOrigin>::signed(owner),
+            hotkey,
+            allowed,
+            true
+        ));
+        assert_eq!(NominationAllowlistLen::<Test>::get(hotkey), 1);
+
+        // Fill the allowlist up to its bound.
+        NominationAllowlistLen::<Test>::insert(hotkey, MAX_NOMINATION_ALLOWLIST_LEN);
+        assert_noop!(
+            SubtensorModule::set_nomination_allowed(
+                <<Test as Config>::RuntimeOrigin>::signed(owner),
+                hotkey,
+                other,
+                true
+            ),
+            Error::<Test>::NominationAllowlistFull
+        );
+
+        // Coldkeys already on the allowlist do not take another slot.
+        assert_ok!(SubtensorModule::set_nomination_allowed(
+            <<Test as Config>::RuntimeOrigin>::signed(owner),
+            hotkey,
+            allowed,
+            true
+        ));
+        assert_eq!(
+            NominationAllowlistLen::<Test>::get(hotkey),
+            MAX_NOMINATION_ALLOWLIST_LEN
+        );
+
+        // Removing a coldkey frees its slot.
+        assert_ok!(SubtensorModule::set_nomination_allowed(
+            <<Test as Config>::RuntimeOrigin>::signed(owner),
+            hotkey,
+            allowed,
+            false
+        ));
+        assert_ok!(SubtensorModule::set_nomination_allowed(
+            <<Test as Config>::RuntimeOrigin>::signed(owner),
+            hotkey,
+            other,
+            true
+        ));
+    });
+}
+
+#[test]
+fn test_max_nominated_stake() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner = U256::from(2);
+        let nominator = U256::from(3);
+        let other = U256::from(4);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, owner, 0);
+        SubtensorModule::delegate_hotkey(&hotkey, 0);
+        SubtensorModule::add_balance_to_coldkey_account(&owner, 100_000);
+        SubtensorModule::add_balance_to_coldkey_account(&nominator, 100_000);
+        SubtensorModule::add_balance_to_coldkey_account(&other, 100_000);
+
+        assert_ok!(SubtensorModule::set_nomination_limits(
+            <<Test as Config>::RuntimeOrigin>::signed(owner),
+            hotkey,
+            0,
+            5_000
+        ));
+
+        // Stake of the owner is not capped.
+        assert_ok!(SubtensorModule::add_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(owner),
+            hotkey,
+            10_000
+        ));
+        assert_ok!(SubtensorModule::add_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(nominator),
+            hotkey,
+            4_000
+        ));
+        assert_noop!(
+            SubtensorModule::add_stake(
+                <<Test as Config>::RuntimeOrigin>::signed(other),
+                hotkey,
+                1_001
+            ),
+            Error::<Test>::DelegateNominatedStakeLimitReached
+        );
+        assert_ok!(SubtensorModule::add_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(other),
+            hotkey,
+            1_000
+        ));
+
+        // Removing the caps clears the entry.
+        assert_ok!(SubtensorModule::set_nomination_limits(
+            <<Test as Config>::RuntimeOrigin>::signed(owner),
+            hotkey,
+            0,
+            0
+        ));
+        assert!(!NominationLimits::<Test>::contains_key(hotkey));
+    });
+}
+
+#[test]
+fn test_move_and_transfer_stake_respect_allowlist() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner = U256::from(2);
+        let nominator = U256::from(3);
+        let other_hotkey = U256::from(5);
+        let other_owner = U256::from(6);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, owner, 0);
+        SubtensorModule::delegate_hotkey(&hotkey, 0);
+        SubtensorModule::add_balance_to_coldkey_account(&owner, 100_000);
+        SubtensorModule::add_balance_to_coldkey_account(&nominator, 100_000);
+        register_ok_neuron(1, other_hotkey, other_owner, 1);
+        SubtensorModule::delegate_hotkey(&other_hotkey, 0);
+
+        assert_ok!(SubtensorModule::add_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(nominator),
+            other_hotkey,
+            1_000
+        ));
+        assert_ok!(SubtensorModule::set_nomination_allowlist_enabled(
+            <<Test as Config>::RuntimeOrigin>::signed(owner),
+            hotkey,
+            true
+        ));
+
+        assert_noop!(
+            SubtensorModule::move_stake(
+                <<Test as Config>::RuntimeOrigin>::signed(nominator),
+                other_hotkey,
+                hotkey,
+                1_000
+            ),
+            Error::<Test>::NominatorNotAllowlisted
+        );
+
+        assert_ok!(SubtensorModule::add_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(owner),
+            hotkey,
+            1_000
+        ));
+        assert_noop!(
+            SubtensorModule::transfer_stake(
+                <<Test as Config>::RuntimeOrigin>::signed(owner),
+                nominator,
+                hotkey,
+                1_000
+            ),
+            Error::<Test>::NominatorNotAllowlisted
+        );
+    });
+}
+
+#[test]
+fn test_nomination_settings_in_delegate_info() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner = U256::from(2);
+        let nominator = U256::from(3);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, owner, 0);
+        SubtensorModule::delegate_hotkey(&hotkey, 0);
+        SubtensorModule::add_balance_to_coldkey_account(&owner, 100_000);
+
+        assert_ok!(SubtensorModule::set_nomination_allowlist_enabled(
+            <<Test as Config>::RuntimeOrigin>::signed(owner),
+            hotkey,
+            true
+        ));
+        assert_ok!(SubtensorModule::set_nomination_allowed(
+            <<Test as Config>::RuntimeOrigin>::signed(owner),
+            hotkey,
+            nominator,
+            true
+        ));
+        assert_ok!(SubtensorModule::set_nomination_limits(
+            <<Test as Config>::RuntimeOrigin>::signed(owner),
+            hotkey,
+            7,
+            5_000
+        ));
+
+        // The nomination settings are the last fields of the delegate info.
+        let tail = (true, vec![nominator], Compact(7u32), Compact(5_000u64)).encode();
+        let info = SubtensorModule::get_delegate(hotkey.encode()).unwrap();
+        assert!(info.encode().ends_with(&tail));
+    });
+}
+
+#[test]
+fn test_swap_nomination_settings() {
+    new_test_ext(1).execute_with(|| {
+        let old_hotkey = U256::from(1);
+        let new_hotkey = U256::from(2);
+        let old_coldkey = U256::from(3);
+        let new_coldkey = U256::from(4);
+        let mut weight = Weight::zero();
+
+        NominationAllowlistEnabled::<Test>::insert(old_hotkey, true);
+        NominationAllowlist::<Test>::insert(old_hotkey, old_coldkey, true);
+        NominationAllowlistLen::<Test>::insert(old_hotkey, 1);
+        NominationLimits::<Test>::insert(old_hotkey, (3, 1_000));
+        SubtensorModule::swap_nomination_settings(&old_hotkey, &new_hotkey, &mut weight);
+        assert_eq!(NominationAllowlistLen::<Test>::get(old_hotkey), 0);
+        assert_eq!(NominationAllowlistLen::<Test>::get(new_hotkey), 1);
+        assert!(!NominationAllowlistEnabled::<Test>::contains_key(
+            old_hotkey
+        ));
+        assert!(!NominationAllowlist::<Test>::contains_key(
+            old_hotkey,
+            old_coldkey
+        ));
+        assert!(!NominationLimits::<Test>::contains_key(old_hotkey));
+        assert!(NominationAllowlistEnabled::<Test>::get(new_hotkey));
+        assert!(NominationAllowlist::<Test>::get(new_hotkey, old_coldkey));
+        assert_eq!(NominationLimits::<Test>::get(new_hotkey), (3, 1_000));
+
+        // The staking hotkeys have already been moved to the new coldkey.
+        StakingHotkeys::<Test>::insert(new_coldkey, vec![new_hotkey]);
+        SubtensorModule::swap_nomination_allowlist_for_coldkey(
+            &old_coldkey,
+            &new_coldkey,
+            &mut weight,
+        );
+        assert!(!NominationAllowlist::<Test>::contains_key(
+            new_hotkey,
+            old_coldkey
+        ));
+        assert!(NominationAllowlist::<Test>::get(new_hotkey, new_coldkey));
+    });
+}
@@ -0,0 +1,479 @@
+#![allow(clippy::unwrap_used)]
+
+mod mock;
+use codec::Encode;
+use frame_support::weights::Weight;
+use frame_support::{assert_noop, assert_ok};
+use frame_system::Config;
+use mock::*;
+use pallet_subtensor::*;
+use sp_core::U256;
+
+#[test]
+fn test_reward_destination_defaults_to_compound() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner = U256::from(2);
+        let nominator = U256::from(3);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, owner, 0);
+        SubtensorModule::delegate_hotkey(&hotkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&nominator, &hotkey, 1_000);
+
+        assert_eq!(
+            RewardDestinations::<Test>::get(nominator, hotkey),
+            RewardDestination::Compound
+        );
+
+        SubtensorModule::emit_inflation_through_hotkey_account(&hotkey, 0, 1_000);
+
+        assert_eq!(
+            SubtensorModule::get_stake_for_coldkey_and_hotkey(&nominator, &hotkey),
+            2_000
+        );
+        assert_eq!(SubtensorModule::get_coldkey_balance(&nominator), 0);
+    });
+}
+
+#[test]
+fn test_reward_destination_free_balance() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner = U256::from(2);
+        let nominator = U256::from(3);
+        let other_nominator = U256::from(4);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, owner, 0);
+        SubtensorModule::delegate_hotkey(&hotkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&nominator, &hotkey, 500);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&other_nominator, &hotkey, 500);
+
+        assert_ok!(SubtensorModule::set_reward_destination(
+            <<Test as Config>::RuntimeOrigin>::signed(nominator),
+            hotkey,
+            RewardDestination::FreeBalance
+        ));
+        System::assert_last_event(
+            Event::RewardDestinationSet {
+                coldkey: nominator,
+                hotkey,
+                destination: RewardDestination::FreeBalance,
+            }
+            .into(),
+        );
+
+        SubtensorModule::emit_inflation_through_hotkey_account(&hotkey, 0, 1_000);
+
+        // The nominator is paid out, the other nominator still compounds.
+        assert_eq!(
+            SubtensorModule::get_stake_for_coldkey_and_hotkey(&nominator, &hotkey),
+            500
+        );
+        assert_eq!(SubtensorModule::get_coldkey_balance(&nominator), 500);
+        assert_eq!(
+            SubtensorModule::get_stake_for_coldkey_and_hotkey(&other_nominator, &hotkey),
+            1_000
+        );
+        assert_eq!(SubtensorModule::get_coldkey_balance(&other_nominator), 0);
+        System::assert_has_event(
+            Event::NominatorRewardPaid {
+                coldkey: nominator,
+                hotkey,
+                payee: nominator,
+                amount: 500,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn test_reward_destination_account() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner = U256::from(2);
+        let nominator = U256::from(3);
+        let payee = U256::from(5);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, owner, 0);
+        SubtensorModule::delegate_hotkey(&hotkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&nominator, &hotkey, 1_000);
+
+        assert_ok!(SubtensorModule::set_reward_destination(
+            <<Test as Config>::RuntimeOrigin>::signed(nominator),
+            hotkey,
+            RewardDestination::Account(payee)
+        ));
+
+        SubtensorModule::emit_inflation_through_hotkey_account(&hotkey, 0, 1_000);
+
+        assert_eq!(
+            SubtensorModule::get_stake_for_coldkey_and_hotkey(&nominator, &hotkey),
+            1_000
+        );
+        assert_eq!(SubtensorModule::get_coldkey_balance(&nominator), 0);
+        assert_eq!(SubtensorModule::get_coldkey_balance(&payee), 1_000);
+        System::assert_has_event(
+            Event::NominatorRewardPaid {
+                coldkey: nominator,
+                hotkey,
+                payee,
+                amount: 1_000,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn test_set_reward_destination_compound_clears_entry() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner = U256::from(2);
+        let nominator = U256::from(3);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, owner, 0);
+        SubtensorModule::delegate_hotkey(&hotkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&nominator, &hotkey, 1_000);
+
+        assert_ok!(SubtensorModule::set_reward_destination(
+            <<Test as Config>::RuntimeOrigin>::signed(nominator),
+            hotkey,
+            RewardDestination::FreeBalance
+        ));
+        assert!(RewardDestinations::<Test>::contains_key(nominator, hotkey));
+
+        assert_ok!(SubtensorModule::set_reward_destination(
+            <<Test as Config>::RuntimeOrigin>::signed(nominator),
+            hotkey,
+            RewardDestination::Compound
+        ));
+        assert!(!RewardDestinations::<Test>::contains_key(nominator, hotkey));
+    });
+}
+
+#[test]
+fn test_reward_destination_removed_with_stake() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner = U256::from(2);
+        let nominator = U256::from(3);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, owner, 0);
+        SubtensorModule::delegate_hotkey(&hotkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&nominator, &hotkey, 1_000);
+
+        assert_ok!(SubtensorModule::set_reward_destination(
+            <<Test as Config>::RuntimeOrigin>::signed(nominator),
+            hotkey,
+            RewardDestination::Account(U256::from(4))
+        ));
+
+        // A partial unstake keeps the destination.
+        SubtensorModule::decrease_stake_on_coldkey_hotkey_account(&nominator, &hotkey, 400);
+        assert!(RewardDestinations::<Test>::contains_key(nominator, hotkey));
+
+        // Unstaking the rest removes it.
+        SubtensorModule::decrease_stake_on_coldkey_hotkey_account(&nominator, &hotkey, 600);
+        assert!(!RewardDestinations::<Test>::contains_key(nominator, hotkey));
+
+        // So does emptying the nomination.
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&nominator, &hotkey, 1_000);
+        assert_ok!(SubtensorModule::set_reward_destination(
+            <<Test as Config>::RuntimeOrigin>::signed(nominator),
+            hotkey,
+            RewardDestination::FreeBalance
+        ));
+        SubtensorModule::empty_stake_on_coldkey_hotkey_account(&nominator, &hotkey);
+        assert!(!RewardDestinations::<Test>::contains_key(nominator, hotkey));
+    });
+}
+
+#[test]
+fn test_set_reward_destination_hotkey_not_exists() {
+    new_test_ext(1).execute_with(|| {
+        assert_noop!(
+            SubtensorModule::set_reward_destination(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(3)),
+                U256::from(1),
+                RewardDestination::FreeBalance
+            ),
+            Error::<Test>::HotKeyAccountNotExists
+        );
+    });
+}
+
+#[test]
+fn test_swap_reward_destinations() {
+    new_test_ext(1).execute_with(|| {
+        let old_hotkey = U256::from(1);
+        let new_hotkey = U256::from(2);
+        let old_coldkey = U256::from(3);
+        let new_coldkey = U256::from(4);
+        let mut weight = Weight::zero();
+
+        // Hotkey swap, the stake has already been moved to the new hotkey.
+        Stake::<Test>::insert(new_hotkey, old_coldkey, 1_000);
+        RewardDestinations::<Test>::insert(old_coldkey, old_hotkey, RewardDestination::FreeBalance);
+        SubtensorModule::swap_reward_destinations(&old_hotkey, &new_hotkey, &mut weight);
+        assert!(!RewardDestinations::<Test>::contains_key(
+            old_coldkey,
+            old_hotkey
+        ));
+        assert_eq!(
+            RewardDestinations::<Test>::get(old_coldkey, new_hotkey),
+            RewardDestination::FreeBalance
+        );
+
+        // Coldkey swap.
+        SubtensorModule::swap_reward_destinations_for_coldkey(
+            &old_coldkey,
+            &new_coldkey,
+            &mut weight,
+        );
+        assert!(!RewardDestinations::<Test>::contains_key(
+            old_coldkey,
+            new_hotkey
+        ));
+        assert_eq!(
+            RewardDestinations::<Test>::get(new_coldkey, new_hotkey),
+            RewardDestination::FreeBalance
+        );
+    });
+}
+
+#[test]
+fn test_nominator_rewards_recorded_on_drain() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner = U256::from(2);
+        let nominator = U256::from(3);
+        let other_nominator = U256::from(4);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, owner, 0);
+        SubtensorModule::delegate_hotkey(&hotkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&nominator, &hotkey, 500);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&other_nominator, &hotkey, 500);
+        assert_ok!(SubtensorModule::set_reward_destination(
+            <<Test as Config>::RuntimeOrigin>::signed(nominator),
+            hotkey,
+            RewardDestination::FreeBalance
+        ));
+
+        // Paid out and compounded rewards are both recorded.
+        SubtensorModule::emit_inflation_through_hotkey_account(&hotkey, 100, 1_000);
+        assert_eq!(
+            NominatorRewards::<Test>::get(hotkey, nominator),
+            (500, 500, 1)
+        );
+        assert_eq!(
+            NominatorRewards::<Test>::get(hotkey, other_nominator),
+            (500, 500, 1)
+        );
+        // The owner earns the server emission.
+        assert_eq!(NominatorRewards::<Test>::get(hotkey, owner), (100, 100, 1));
+
+        // A drain on a later block starts a new epoch.
+        run_to_block(5);
+        SubtensorModule::emit_inflation_through_hotkey_account(&hotkey, 0, 1_000);
+        let (total_rewards, last_epoch_rewards, last_reward_block) =
+            NominatorRewards::<Test>::get(hotkey, nominator);
+        assert!(total_rewards > 500);
+        assert_eq!(last_epoch_rewards, total_rewards - 500);
+        assert_eq!(last_reward_block, 5);
+    });
+}
+
+#[test]
+fn test_nominator_rewards_recorded_for_non_delegate() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner = U256::from(2);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, owner, 0);
+
+        SubtensorModule::emit_inflation_through_hotkey_account(&hotkey, 100, 1_000);
+        assert_eq!(
+            NominatorRewards::<Test>::get(hotkey, owner),
+            (1_100, 1_100, 1)
+        );
+    });
+}
+
+#[test]
+fn test_get_rewards_for_coldkey() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner = U256::from(2);
+        let nominator = U256::from(3);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, owner, 0);
+        SubtensorModule::delegate_hotkey(&hotkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&nominator, &hotkey, 1_000);
+
+        assert!(SubtensorModule::get_rewards_for_coldkey(nominator.encode()).is_empty());
+
+        SubtensorModule::emit_inflation_through_hotkey_account(&hotkey, 0, 1_000);
+
+        assert_eq!(
+            SubtensorModule::get_rewards_for_coldkey(nominator.encode()).len(),
+            1
+        );
+        assert!(SubtensorModule::get_rewards_for_coldkey(vec![0; 3]).is_empty());
+    });
+}
+
+#[test]
+fn test_get_rewards_for_coldkey_keeps_closed_positions() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner = U256::from(2);
+        let nominator = U256::from(3);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, owner, 0);
+        SubtensorModule::delegate_hotkey(&hotkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&nominator, &hotkey, 1_000);
+
+        SubtensorModule::emit_inflation_through_hotkey_account(&hotkey, 0, 1_000);
+        SubtensorModule::empty_stake_on_coldkey_hotkey_account(&nominator, &hotkey);
+        assert!(!StakingHotkeys::<Test>::get(nominator).contains(&hotkey));
+
+        // The history is still reported once nothing is staked on the hotkey.
+        assert_eq!(
+            SubtensorModule::get_rewards_for_coldkey(nominator.encode()).len(),
+            1
+        );
+    });
+}
+
+#[test]
+fn test_nominator_rewards_follow_closed_transfer() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let owner = U256::from(2);
+        let nominator = U256::from(3);
+        let destination_coldkey = U256::from(4);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, owner, 0);
+        SubtensorModule::delegate_hotkey(&hotkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&nominator, &hotkey, 1_000);
+        SubtensorModule::record_nominator_reward(&hotkey, &nominator, 10);
+        SubtensorModule::record_nominator_reward(&hotkey, &destination_coldkey, 4);
+
+        // A partial transfer leaves the ledger with the origin.
+        assert_ok!(SubtensorModule::transfer_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(nominator),
+            destination_coldkey,
+            hotkey,
+            400
+        ));
+        assert_eq!(
+            NominatorRewards::<Test>::get(hotkey, nominator),
+            (10, 10, 1)
+        );
+
+        // Closing the position merges the ledger into the destination.
+        assert_ok!(SubtensorModule::transfer_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(nominator),
+            destination_coldkey,
+            hotkey,
+            600
+        ));
+        assert!(!NominatorRewards::<Test>::contains_key(hotkey, nominator));
+        assert!(NominatorRewardHotkeys::<Test>::get(nominator).is_empty());
+        assert_eq!(
+            NominatorRewards::<Test>::get(hotkey, destination_coldkey),
+            (14, 14, 1)
+        );
+    });
+}
+
+#[test]
+fn test_nominator_rewards_follow_closed_move() {
+    new_test_ext(1).execute_with(|| {
+        let origin_hotkey = U256::from(1);
+        let destination_hotkey = U256::from(5);
+        let owner = U256::from(2);
+        let nominator = U256::from(3);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, origin_hotkey, owner, 0);
+        SubtensorModule::delegate_hotkey(&origin_hotkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(
+            &nominator,
+            &origin_hotkey,
+            1_000,
+        );
+        register_ok_neuron(1, destination_hotkey, owner, 0);
+        SubtensorModule::delegate_hotkey(&destination_hotkey, 0);
+        SubtensorModule::record_nominator_reward(&origin_hotkey, &nominator, 10);
+
+        assert_ok!(SubtensorModule::move_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(nominator),
+            origin_hotkey,
+            destination_hotkey,
+            1_000
+        ));
+        assert!(!NominatorRewards::<Test>::contains_key(
+            origin_hotkey,
+            nominator
+        ));
+        assert_eq!(
+            NominatorRewards::<Test>::get(destination_hotkey, nominator),
+            (10, 10, 1)
+        );
+        assert_eq!(
+            NominatorRewardHotkeys::<Test>::get(nominator),
+            vec![destination_hotkey]
+        );
+    });
+}
+
+#[test]
+fn test_swap_nominator_rewards() {
+    new_test_ext(1).execute_with(|| {
+        let old_hotkey = U256::from(1);
+        let new_hotkey = U256::from(2);
+        let old_coldkey = U256::from(3);
+        let new_coldkey = U256::from(4);
+        let mut weight = Weight::zero();
+
+        // Ledgers on the new hotkey are merged with the swapped ones.
+        SubtensorModule::record_nominator_reward(&old_hotkey, &old_coldkey, 10);
+        SubtensorModule::record_nominator_reward(&new_hotkey, &old_coldkey, 4);
+        SubtensorModule::swap_nominator_rewards(&old_hotkey, &new_hotkey, &mut weight);
+        assert!(!NominatorRewards::<Test>::contains_key(
+            old_hotkey,
+            old_coldkey
+        ));
+        assert_eq!(
+            NominatorRewards::<Test>::get(new_hotkey, old_coldkey),
+            (14, 14, 1)
+        );
+        assert_eq!(
+            NominatorRewardHotkeys::<Test>::get(old_coldkey),
+            vec![new_hotkey]
+        );
+
+        // The most recent epoch wins when the ledgers were rewarded on different blocks.
+        run_to_block(3);
+        SubtensorModule::record_nominator_reward(&new_hotkey, &new_coldkey, 3);
+        SubtensorModule::swap_nominator_rewards_for_coldkey(
+            &old_coldkey,
+            &new_coldkey,
+            &mut weight,
+        );
+        assert!(!NominatorRewards::<Test>::contains_key(
+            new_hotkey,
+            old_coldkey
+        ));
+        assert_eq!(
+            NominatorRewards::<Test>::get(new_hotkey, new_coldkey),
+            (17, 3, 3)
+        );
+        assert!(NominatorRewardHotkeys::<Test>::get(old_coldkey).is_empty());
+        assert_eq!(
+            NominatorRewardHotkeys::<Test>::get(new_coldkey),
+            vec![new_hotkey]
+        );
+    });
+}
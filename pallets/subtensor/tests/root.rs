@@ -916,6 +916,42 @@ fn test_dissolve_network_ok() {
     });
 }
 
+#[test]
+fn test_dissolve_network_removes_weights_version_policy() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 30;
+        add_network(netuid, 0, 0);
+        let owner_coldkey = SubtensorModule::get_subnet_owner(netuid);
+        SubtensorModule::set_weights_max_version_key(netuid, 10);
+        SubtensorModule::set_banned_weights_version_keys(netuid, vec![3, 5]);
+
+        assert_ok!(SubtensorModule::dissolve_network(
+            RuntimeOrigin::signed(owner_coldkey),
+            netuid
+        ));
+        assert!(!pallet_subtensor::WeightsMaxVersionKey::<Test>::contains_key(netuid));
+        assert!(!pallet_subtensor::BannedWeightsVersionKeys::<Test>::contains_key(netuid));
+    });
+}
+
+#[test]
+fn test_dissolve_network_removes_weights_window() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 30;
+        add_network(netuid, 0, 0);
+        let owner_coldkey = SubtensorModule::get_subnet_owner(netuid);
+        SubtensorModule::set_weights_window(netuid, 5);
+
+        assert_ok!(SubtensorModule::dissolve_network(
+            RuntimeOrigin::signed(owner_coldkey),
+            netuid
+        ));
+        assert!(!pallet_subtensor::WeightsWindow::<Test>::contains_key(
+            netuid
+        ));
+    });
+}
+
 #[test]
 fn test_dissolve_network_refund_coldkey_ok() {
     new_test_ext(1).execute_with(|| {
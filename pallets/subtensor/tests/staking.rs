@@ -1,6 +1,7 @@
 #![allow(clippy::unwrap_used)]
 #![allow(clippy::arithmetic_side_effects)]
 
+use codec::Encode;
 use frame_support::pallet_prelude::{
     InvalidTransaction, TransactionValidity, TransactionValidityError,
 };
@@ -820,6 +821,614 @@ fn test_remove_stake_total_issuance_no_change() {
     });
 }
 
+/***********************************************************
+    staking::move_stake() tests
+************************************************************/
+#[test]
+fn test_move_stake_ok() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let origin_hotkey = U256::from(2);
+        let destination_hotkey = U256::from(3);
+        let netuid: u16 = 1;
+        let amount = 10_000;
+
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, origin_hotkey, coldkey, 0);
+        register_ok_neuron(netuid, destination_hotkey, coldkey, 100);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &origin_hotkey, amount);
+
+        let total_stake_before = SubtensorModule::get_total_stake();
+        let balance_before = SubtensorModule::get_coldkey_balance(&coldkey);
+
+        assert_ok!(SubtensorModule::move_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            origin_hotkey,
+            destination_hotkey,
+            amount
+        ));
+
+        assert_eq!(
+            SubtensorModule::get_stake_for_coldkey_and_hotkey(&coldkey, &origin_hotkey),
+            0
+        );
+        assert_eq!(
+            SubtensorModule::get_stake_for_coldkey_and_hotkey(&coldkey, &destination_hotkey),
+            amount
+        );
+        assert_eq!(
+            SubtensorModule::get_total_stake_for_hotkey(&origin_hotkey),
+            0
+        );
+        assert_eq!(
+            SubtensorModule::get_total_stake_for_hotkey(&destination_hotkey),
+            amount
+        );
+        assert_eq!(
+            SubtensorModule::get_total_stake_for_coldkey(&coldkey),
+            amount
+        );
+        assert_eq!(SubtensorModule::get_total_stake(), total_stake_before);
+        assert_eq!(
+            SubtensorModule::get_coldkey_balance(&coldkey),
+            balance_before
+        );
+
+        let staking_hotkeys = StakingHotkeys::<Test>::get(coldkey);
+        assert!(!staking_hotkeys.contains(&origin_hotkey));
+        assert!(staking_hotkeys.contains(&destination_hotkey));
+
+        System::assert_last_event(
+            Event::StakeMoved {
+                coldkey,
+                origin_hotkey,
+                destination_hotkey,
+                amount,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn test_move_stake_partial_keeps_origin() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let origin_hotkey = U256::from(2);
+        let destination_hotkey = U256::from(3);
+        let netuid: u16 = 1;
+
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, origin_hotkey, coldkey, 0);
+        register_ok_neuron(netuid, destination_hotkey, coldkey, 100);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &origin_hotkey, 10_000);
+
+        assert_ok!(SubtensorModule::move_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            origin_hotkey,
+            destination_hotkey,
+            4_000
+        ));
+
+        assert_eq!(
+            SubtensorModule::get_stake_for_coldkey_and_hotkey(&coldkey, &origin_hotkey),
+            6_000
+        );
+        assert_eq!(
+            SubtensorModule::get_stake_for_coldkey_and_hotkey(&coldkey, &destination_hotkey),
+            4_000
+        );
+        let staking_hotkeys = StakingHotkeys::<Test>::get(coldkey);
+        assert!(staking_hotkeys.contains(&origin_hotkey));
+        assert!(staking_hotkeys.contains(&destination_hotkey));
+    });
+}
+
+#[test]
+fn test_move_stake_counts_once_against_rate_limit() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let origin_hotkey = U256::from(2);
+        let destination_hotkey = U256::from(3);
+        let netuid: u16 = 1;
+
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, origin_hotkey, coldkey, 0);
+        register_ok_neuron(netuid, destination_hotkey, coldkey, 100);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &origin_hotkey, 10_000);
+        SubtensorModule::set_target_stakes_per_interval(1);
+
+        assert_ok!(SubtensorModule::move_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            origin_hotkey,
+            destination_hotkey,
+            1_000
+        ));
+        assert_eq!(
+            SubtensorModule::get_stakes_this_interval_for_coldkey_hotkey(&coldkey, &origin_hotkey),
+            1
+        );
+        assert_eq!(
+            SubtensorModule::get_stakes_this_interval_for_coldkey_hotkey(
+                &coldkey,
+                &destination_hotkey
+            ),
+            0
+        );
+
+        assert_noop!(
+            SubtensorModule::move_stake(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                origin_hotkey,
+                destination_hotkey,
+                1_000
+            ),
+            Error::<Test>::StakeRateLimitExceeded
+        );
+    });
+}
+
+#[test]
+fn test_move_stake_same_hotkey() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+        let netuid: u16 = 1;
+
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, hotkey, coldkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey, 10_000);
+
+        assert_noop!(
+            SubtensorModule::move_stake(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                hotkey,
+                hotkey,
+                1_000
+            ),
+            Error::<Test>::SameHotkeyStakeMove
+        );
+    });
+}
+
+#[test]
+fn test_move_stake_not_enough_stake() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let origin_hotkey = U256::from(2);
+        let destination_hotkey = U256::from(3);
+        let netuid: u16 = 1;
+
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, origin_hotkey, coldkey, 0);
+        register_ok_neuron(netuid, destination_hotkey, coldkey, 100);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &origin_hotkey, 1_000);
+
+        assert_noop!(
+            SubtensorModule::move_stake(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                origin_hotkey,
+                destination_hotkey,
+                1_001
+            ),
+            Error::<Test>::NotEnoughStakeToWithdraw
+        );
+        assert_noop!(
+            SubtensorModule::move_stake(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                origin_hotkey,
+                destination_hotkey,
+                0
+            ),
+            Error::<Test>::StakeToWithdrawIsZero
+        );
+    });
+}
+
+#[test]
+fn test_move_stake_destination_not_delegate() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let other_coldkey = U256::from(4);
+        let origin_hotkey = U256::from(2);
+        let destination_hotkey = U256::from(3);
+        let netuid: u16 = 1;
+
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, origin_hotkey, coldkey, 0);
+        register_ok_neuron(netuid, destination_hotkey, other_coldkey, 100);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &origin_hotkey, 1_000);
+
+        assert_noop!(
+            SubtensorModule::move_stake(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                origin_hotkey,
+                destination_hotkey,
+                1_000
+            ),
+            Error::<Test>::HotKeyNotDelegateAndSignerNotOwnHotKey
+        );
+    });
+}
+
+#[test]
+fn test_move_stake_destination_below_nominator_minimum() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let other_coldkey = U256::from(4);
+        let origin_hotkey = U256::from(2);
+        let destination_hotkey = U256::from(3);
+        let netuid: u16 = 1;
+
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, origin_hotkey, coldkey, 0);
+        register_ok_neuron(netuid, destination_hotkey, other_coldkey, 100);
+        assert_ok!(SubtensorModule::become_delegate(
+            <<Test as Config>::RuntimeOrigin>::signed(other_coldkey),
+            destination_hotkey
+        ));
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &origin_hotkey, 10_000);
+        SubtensorModule::set_nominator_min_required_stake(5_000);
+
+        assert_noop!(
+            SubtensorModule::move_stake(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                origin_hotkey,
+                destination_hotkey,
+                1_000
+            ),
+            Error::<Test>::NomStakeBelowMinimumThreshold
+        );
+        assert_ok!(SubtensorModule::move_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            origin_hotkey,
+            destination_hotkey,
+            5_000
+        ));
+        assert_eq!(
+            SubtensorModule::get_stake_for_coldkey_and_hotkey(&coldkey, &destination_hotkey),
+            5_000
+        );
+    });
+}
+
+/***********************************************************
+    staking::add_stake_multiple() and staking::remove_stake_multiple() tests
+************************************************************/
+#[test]
+fn test_add_stake_multiple_ok() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey1 = U256::from(2);
+        let hotkey2 = U256::from(3);
+        let netuid: u16 = 1;
+
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, hotkey1, coldkey, 0);
+        register_ok_neuron(netuid, hotkey2, coldkey, 100);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, 10_000);
+
+        assert_ok!(SubtensorModule::add_stake_multiple(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            vec![(hotkey1, 1_000), (hotkey2, 2_000)]
+        ));
+
+        assert_eq!(
+            SubtensorModule::get_stake_for_coldkey_and_hotkey(&coldkey, &hotkey1),
+            1_000
+        );
+        assert_eq!(
+            SubtensorModule::get_stake_for_coldkey_and_hotkey(&coldkey, &hotkey2),
+            2_000
+        );
+        assert_eq!(SubtensorModule::get_coldkey_balance(&coldkey), 7_000);
+
+        // One event per leg.
+        System::assert_has_event(Event::StakeAdded(hotkey1, 1_000).into());
+        System::assert_last_event(Event::StakeAdded(hotkey2, 2_000).into());
+    });
+}
+
+#[test]
+fn test_add_stake_multiple_is_atomic() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey1 = U256::from(2);
+        let hotkey2 = U256::from(3);
+        let netuid: u16 = 1;
+
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, hotkey1, coldkey, 0);
+        register_ok_neuron(netuid, hotkey2, coldkey, 100);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, 10_000);
+
+        // The second leg needs more balance than is left after the first.
+        assert_noop!(
+            SubtensorModule::add_stake_multiple(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                vec![(hotkey1, 5_000), (hotkey2, 6_000)]
+            ),
+            Error::<Test>::NotEnoughBalanceToStake
+        );
+
+        assert_eq!(
+            SubtensorModule::get_stake_for_coldkey_and_hotkey(&coldkey, &hotkey1),
+            0
+        );
+        assert_eq!(SubtensorModule::get_coldkey_balance(&coldkey), 10_000);
+    });
+}
+
+#[test]
+fn test_add_stake_multiple_invalid_batch_size() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+
+        assert_noop!(
+            SubtensorModule::add_stake_multiple(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                vec![]
+            ),
+            Error::<Test>::InvalidStakeBatchSize
+        );
+        assert_noop!(
+            SubtensorModule::add_stake_multiple(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                vec![(hotkey, 1); MAX_STAKE_BATCH_SIZE as usize + 1]
+            ),
+            Error::<Test>::InvalidStakeBatchSize
+        );
+    });
+}
+
+#[test]
+fn test_add_stake_multiple_weight_scales_with_length() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(2);
+        let one = pallet_subtensor::Call::<Test>::add_stake_multiple {
+            stakes: vec![(hotkey, 1)],
+        }
+        .get_dispatch_info();
+        let three = pallet_subtensor::Call::<Test>::add_stake_multiple {
+            stakes: vec![(hotkey, 1); 3],
+        }
+        .get_dispatch_info();
+
+        assert_eq!(three.weight, one.weight.saturating_mul(3));
+        assert_eq!(three.class, DispatchClass::Normal);
+        assert_eq!(three.pays_fee, Pays::No);
+    });
+}
+
+#[test]
+fn test_remove_stake_multiple_ok() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey1 = U256::from(2);
+        let hotkey2 = U256::from(3);
+        let netuid: u16 = 1;
+
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, hotkey1, coldkey, 0);
+        register_ok_neuron(netuid, hotkey2, coldkey, 100);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey1, 1_000);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey2, 2_000);
+
+        assert_ok!(SubtensorModule::remove_stake_multiple(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            vec![(hotkey1, 1_000), (hotkey2, 500)]
+        ));
+
+        assert_eq!(
+            SubtensorModule::get_stake_for_coldkey_and_hotkey(&coldkey, &hotkey1),
+            0
+        );
+        assert_eq!(
+            SubtensorModule::get_stake_for_coldkey_and_hotkey(&coldkey, &hotkey2),
+            1_500
+        );
+        assert_eq!(SubtensorModule::get_coldkey_balance(&coldkey), 1_500);
+
+        // One event per leg.
+        System::assert_has_event(Event::StakeRemoved(hotkey1, 1_000).into());
+        System::assert_last_event(Event::StakeRemoved(hotkey2, 500).into());
+    });
+}
+
+#[test]
+fn test_remove_stake_multiple_is_atomic() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey1 = U256::from(2);
+        let hotkey2 = U256::from(3);
+        let netuid: u16 = 1;
+
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, hotkey1, coldkey, 0);
+        register_ok_neuron(netuid, hotkey2, coldkey, 100);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey1, 1_000);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey2, 2_000);
+
+        assert_noop!(
+            SubtensorModule::remove_stake_multiple(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                vec![(hotkey1, 1_000), (hotkey2, 3_000)]
+            ),
+            Error::<Test>::NotEnoughStakeToWithdraw
+        );
+
+        assert_eq!(
+            SubtensorModule::get_stake_for_coldkey_and_hotkey(&coldkey, &hotkey1),
+            1_000
+        );
+        assert_eq!(SubtensorModule::get_coldkey_balance(&coldkey), 0);
+    });
+}
+
+/***********************************************************
+    staking::transfer_stake() tests
+************************************************************/
+#[test]
+fn test_transfer_stake_ok() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let destination_coldkey = U256::from(2);
+        let hotkey = U256::from(3);
+        let netuid: u16 = 1;
+
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, hotkey, coldkey, 0);
+        SubtensorModule::delegate_hotkey(&hotkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey, 10_000);
+
+        let total_stake_before = SubtensorModule::get_total_stake();
+        assert_ok!(SubtensorModule::transfer_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            destination_coldkey,
+            hotkey,
+            4_000
+        ));
+
+        assert_eq!(
+            SubtensorModule::get_stake_for_coldkey_and_hotkey(&coldkey, &hotkey),
+            6_000
+        );
+        assert_eq!(
+            SubtensorModule::get_stake_for_coldkey_and_hotkey(&destination_coldkey, &hotkey),
+            4_000
+        );
+        assert_eq!(
+            SubtensorModule::get_total_stake_for_coldkey(&coldkey),
+            6_000
+        );
+        assert_eq!(
+            SubtensorModule::get_total_stake_for_coldkey(&destination_coldkey),
+            4_000
+        );
+        assert_eq!(SubtensorModule::get_total_stake_for_hotkey(&hotkey), 10_000);
+        assert_eq!(SubtensorModule::get_total_stake(), total_stake_before);
+        assert!(StakingHotkeys::<Test>::get(destination_coldkey).contains(&hotkey));
+        System::assert_last_event(
+            Event::StakeTransferred {
+                origin_coldkey: coldkey,
+                destination_coldkey,
+                hotkey,
+                amount: 4_000,
+            }
+            .into(),
+        );
+
+        // Transferring the rest stops tracking the hotkey for the origin.
+        assert_ok!(SubtensorModule::transfer_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            destination_coldkey,
+            hotkey,
+            6_000
+        ));
+        assert!(!StakingHotkeys::<Test>::get(coldkey).contains(&hotkey));
+        assert_eq!(
+            SubtensorModule::get_stake_for_coldkey_and_hotkey(&destination_coldkey, &hotkey),
+            10_000
+        );
+    });
+}
+
+#[test]
+fn test_transfer_stake_same_coldkey() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(3);
+
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, coldkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey, 10_000);
+
+        assert_noop!(
+            SubtensorModule::transfer_stake(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                coldkey,
+                hotkey,
+                1_000
+            ),
+            Error::<Test>::SameColdkeyStakeTransfer
+        );
+    });
+}
+
+#[test]
+fn test_transfer_stake_coldkey_in_arbitration() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let destination_coldkey = U256::from(2);
+        let hotkey = U256::from(3);
+
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, coldkey, 0);
+        SubtensorModule::delegate_hotkey(&hotkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey, 10_000);
+        ColdkeyArbitrationBlock::<Test>::insert(destination_coldkey, 100);
+
+        assert_noop!(
+            SubtensorModule::transfer_stake(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                destination_coldkey,
+                hotkey,
+                1_000
+            ),
+            Error::<Test>::ColdkeyIsInArbitration
+        );
+    });
+}
+
+#[test]
+fn test_transfer_stake_destination_not_delegate() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let destination_coldkey = U256::from(2);
+        let hotkey = U256::from(3);
+
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, coldkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey, 10_000);
+
+        assert_noop!(
+            SubtensorModule::transfer_stake(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                destination_coldkey,
+                hotkey,
+                1_000
+            ),
+            Error::<Test>::HotKeyNotDelegateAndSignerNotOwnHotKey
+        );
+    });
+}
+
+#[test]
+fn test_transfer_stake_below_nominator_minimum() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let destination_coldkey = U256::from(2);
+        let hotkey = U256::from(3);
+
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, coldkey, 0);
+        SubtensorModule::delegate_hotkey(&hotkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey, 10_000);
+        SubtensorModule::set_nominator_min_required_stake(5_000);
+
+        assert_noop!(
+            SubtensorModule::transfer_stake(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                destination_coldkey,
+                hotkey,
+                1_000
+            ),
+            Error::<Test>::NomStakeBelowMinimumThreshold
+        );
+    });
+}
+
 /***********************************************************
     staking::get_coldkey_balance() tests
 ************************************************************/
@@ -2915,6 +3524,13 @@ fn test_delegate_take_can_be_increased() {
             hotkey0,
             u16::MAX / 8
         ));
+        assert_eq!(
+            SubtensorModule::get_hotkey_take(&hotkey0),
+            SubtensorModule::get_min_take()
+        );
+
+        // The new take is applied after the notice period.
+        step_block(InitialTakeIncreaseDelay::get() as u16);
         assert_eq!(SubtensorModule::get_hotkey_take(&hotkey0), u16::MAX / 8);
     });
 }
@@ -2997,6 +3613,7 @@ fn test_delegate_take_can_be_increased_to_limit() {
             hotkey0,
             InitialDefaultTake::get()
         ));
+        step_block(InitialTakeIncreaseDelay::get() as u16);
         assert_eq!(
             SubtensorModule::get_hotkey_take(&hotkey0),
             InitialDefaultTake::get()
@@ -3057,82 +3674,338 @@ fn test_delegate_take_can_not_be_increased_beyond_limit() {
         assert_ok!(SubtensorModule::do_become_delegate(
             <<Test as Config>::RuntimeOrigin>::signed(coldkey0),
             hotkey0,
-            SubtensorModule::get_min_take()
+            SubtensorModule::get_min_take()
+        ));
+        assert_eq!(
+            SubtensorModule::get_hotkey_take(&hotkey0),
+            SubtensorModule::get_min_take()
+        );
+
+        // Coldkey / hotkey 0 tries to increase take to InitialDefaultTake+1
+        // (Disable this check if InitialDefaultTake is u16::MAX)
+        if InitialDefaultTake::get() != u16::MAX {
+            assert_eq!(
+                SubtensorModule::do_increase_take(
+                    <<Test as Config>::RuntimeOrigin>::signed(coldkey0),
+                    hotkey0,
+                    InitialDefaultTake::get() + 1
+                ),
+                Err(Error::<Test>::DelegateTakeTooHigh.into())
+            );
+        }
+        assert_eq!(
+            SubtensorModule::get_hotkey_take(&hotkey0),
+            SubtensorModule::get_min_take()
+        );
+    });
+}
+
+// Test rate-limiting on increase_take
+#[test]
+fn test_rate_limits_enforced_on_increase_take() {
+    new_test_ext(1).execute_with(|| {
+        // Make account
+        let hotkey0 = U256::from(1);
+        let coldkey0 = U256::from(3);
+
+        // Add balance
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey0, 100000);
+
+        // Register the neuron to a new network
+        let netuid = 1;
+        add_network(netuid, 0, 0);
+        register_ok_neuron(netuid, hotkey0, coldkey0, 124124);
+
+        // Coldkey / hotkey 0 become delegates with 9% take
+        assert_ok!(SubtensorModule::do_become_delegate(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey0),
+            hotkey0,
+            SubtensorModule::get_min_take()
+        ));
+        assert_eq!(
+            SubtensorModule::get_hotkey_take(&hotkey0),
+            SubtensorModule::get_min_take()
+        );
+
+        // Coldkey / hotkey 0 increases take to 12.5%
+        assert_eq!(
+            SubtensorModule::do_increase_take(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey0),
+                hotkey0,
+                u16::MAX / 8
+            ),
+            Err(Error::<Test>::DelegateTxRateLimitExceeded.into())
+        );
+        assert_eq!(
+            SubtensorModule::get_hotkey_take(&hotkey0),
+            SubtensorModule::get_min_take()
+        );
+
+        step_block(1 + InitialTxDelegateTakeRateLimit::get() as u16);
+
+        // Can increase after waiting
+        assert_ok!(SubtensorModule::do_increase_take(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey0),
+            hotkey0,
+            u16::MAX / 8
+        ));
+        step_block(InitialTakeIncreaseDelay::get() as u16);
+        assert_eq!(SubtensorModule::get_hotkey_take(&hotkey0), u16::MAX / 8);
+    });
+}
+
+// Verify a take increase is announced and applied once the notice period has passed
+#[test]
+fn test_increase_take_is_announced_then_applied() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey0 = U256::from(1);
+        let coldkey0 = U256::from(3);
+        add_network(1, 0, 0);
+        register_ok_neuron(1, hotkey0, coldkey0, 124124);
+        assert_ok!(SubtensorModule::do_become_delegate(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey0),
+            hotkey0,
+            SubtensorModule::get_min_take()
+        ));
+        step_block(1 + InitialTxDelegateTakeRateLimit::get() as u16);
+
+        let block = SubtensorModule::get_current_block_as_u64();
+        let effective_block = block + InitialTakeIncreaseDelay::get();
+        assert_ok!(SubtensorModule::do_increase_take(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey0),
+            hotkey0,
+            u16::MAX / 8
+        ));
+        System::assert_last_event(
+            Event::TakeIncreaseAnnounced {
+                coldkey: coldkey0,
+                hotkey: hotkey0,
+                take: u16::MAX / 8,
+                effective_block,
+            }
+            .into(),
+        );
+        assert_eq!(
+            PendingTakeIncreases::<Test>::get(hotkey0),
+            Some((u16::MAX / 8, effective_block))
+        );
+
+        // Nothing changes before the effective block.
+        run_to_block(effective_block - 1);
+        assert_eq!(
+            SubtensorModule::get_hotkey_take(&hotkey0),
+            SubtensorModule::get_min_take()
+        );
+
+        run_to_block(effective_block);
+        assert_eq!(SubtensorModule::get_hotkey_take(&hotkey0), u16::MAX / 8);
+        assert_eq!(PendingTakeIncreases::<Test>::get(hotkey0), None);
+        assert!(TakeIncreasesAtBlock::<Test>::get(effective_block).is_empty());
+        System::assert_has_event(Event::TakeIncreased(coldkey0, hotkey0, u16::MAX / 8).into());
+        System::assert_has_event(
+            Event::TakeIncreaseApplied {
+                hotkey: hotkey0,
+                take: u16::MAX / 8,
+            }
+            .into(),
+        );
+    });
+}
+
+// Verify applying announced take increases is charged per announcement
+#[test]
+fn test_apply_pending_take_increases_charges_weight_per_entry() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey0 = U256::from(1);
+        let coldkey0 = U256::from(3);
+        add_network(1, 0, 0);
+        register_ok_neuron(1, hotkey0, coldkey0, 124124);
+        assert_ok!(SubtensorModule::do_become_delegate(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey0),
+            hotkey0,
+            SubtensorModule::get_min_take()
+        ));
+        step_block(1 + InitialTxDelegateTakeRateLimit::get() as u16);
+
+        let effective_block =
+            SubtensorModule::get_current_block_as_u64() + InitialTakeIncreaseDelay::get();
+        let idle_weight = SubtensorModule::apply_pending_take_increases(effective_block);
+
+        assert_ok!(SubtensorModule::do_increase_take(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey0),
+            hotkey0,
+            u16::MAX / 8
+        ));
+        let applied_weight = SubtensorModule::apply_pending_take_increases(effective_block);
+
+        assert_eq!(SubtensorModule::get_hotkey_take(&hotkey0), u16::MAX / 8);
+        assert!(applied_weight.ref_time() > idle_weight.ref_time());
+        assert_eq!(
+            applied_weight.saturating_sub(idle_weight),
+            <Test as frame_system::Config>::DbWeight::get().reads_writes(4, 2)
+        );
+    });
+}
+
+// Verify a new announcement replaces the pending one
+#[test]
+fn test_increase_take_announcement_replaces_pending() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey0 = U256::from(1);
+        let coldkey0 = U256::from(3);
+        add_network(1, 0, 0);
+        register_ok_neuron(1, hotkey0, coldkey0, 124124);
+        assert_ok!(SubtensorModule::do_become_delegate(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey0),
+            hotkey0,
+            SubtensorModule::get_min_take()
+        ));
+        step_block(1 + InitialTxDelegateTakeRateLimit::get() as u16);
+
+        let first_effective_block =
+            SubtensorModule::get_current_block_as_u64() + InitialTakeIncreaseDelay::get();
+        assert_ok!(SubtensorModule::do_increase_take(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey0),
+            hotkey0,
+            u16::MAX / 8
+        ));
+        step_block(1 + InitialTxDelegateTakeRateLimit::get() as u16);
+        assert_ok!(SubtensorModule::do_increase_take(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey0),
+            hotkey0,
+            u16::MAX / 7
         ));
-        assert_eq!(
-            SubtensorModule::get_hotkey_take(&hotkey0),
-            SubtensorModule::get_min_take()
-        );
+        assert!(TakeIncreasesAtBlock::<Test>::get(first_effective_block).is_empty());
 
-        // Coldkey / hotkey 0 tries to increase take to InitialDefaultTake+1
-        // (Disable this check if InitialDefaultTake is u16::MAX)
-        if InitialDefaultTake::get() != u16::MAX {
-            assert_eq!(
-                SubtensorModule::do_increase_take(
-                    <<Test as Config>::RuntimeOrigin>::signed(coldkey0),
-                    hotkey0,
-                    InitialDefaultTake::get() + 1
-                ),
-                Err(Error::<Test>::DelegateTakeTooHigh.into())
-            );
-        }
+        // The first announcement is not applied.
+        run_to_block(first_effective_block);
         assert_eq!(
             SubtensorModule::get_hotkey_take(&hotkey0),
             SubtensorModule::get_min_take()
         );
+
+        step_block(InitialTakeIncreaseDelay::get() as u16);
+        assert_eq!(SubtensorModule::get_hotkey_take(&hotkey0), u16::MAX / 7);
     });
 }
 
-// Test rate-limiting on increase_take
+// Verify a take decrease drops the pending take increase
 #[test]
-fn test_rate_limits_enforced_on_increase_take() {
+fn test_decrease_take_cancels_pending_increase() {
     new_test_ext(1).execute_with(|| {
-        // Make account
         let hotkey0 = U256::from(1);
         let coldkey0 = U256::from(3);
-
-        // Add balance
-        SubtensorModule::add_balance_to_coldkey_account(&coldkey0, 100000);
-
-        // Register the neuron to a new network
-        let netuid = 1;
-        add_network(netuid, 0, 0);
-        register_ok_neuron(netuid, hotkey0, coldkey0, 124124);
-
-        // Coldkey / hotkey 0 become delegates with 9% take
+        add_network(1, 0, 0);
+        register_ok_neuron(1, hotkey0, coldkey0, 124124);
         assert_ok!(SubtensorModule::do_become_delegate(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey0),
+            hotkey0,
+            u16::MAX / 10
+        ));
+        step_block(1 + InitialTxDelegateTakeRateLimit::get() as u16);
+
+        assert_ok!(SubtensorModule::do_increase_take(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey0),
+            hotkey0,
+            u16::MAX / 8
+        ));
+        assert_ok!(SubtensorModule::do_decrease_take(
             <<Test as Config>::RuntimeOrigin>::signed(coldkey0),
             hotkey0,
             SubtensorModule::get_min_take()
         ));
+        assert_eq!(PendingTakeIncreases::<Test>::get(hotkey0), None);
+
+        step_block(InitialTakeIncreaseDelay::get() as u16);
         assert_eq!(
             SubtensorModule::get_hotkey_take(&hotkey0),
             SubtensorModule::get_min_take()
         );
+    });
+}
 
-        // Coldkey / hotkey 0 increases take to 12.5%
-        assert_eq!(
-            SubtensorModule::do_increase_take(
-                <<Test as Config>::RuntimeOrigin>::signed(coldkey0),
-                hotkey0,
-                u16::MAX / 8
-            ),
-            Err(Error::<Test>::DelegateTxRateLimitExceeded.into())
-        );
-        assert_eq!(
-            SubtensorModule::get_hotkey_take(&hotkey0),
+// Verify the take is applied immediately without a notice period
+#[test]
+fn test_increase_take_without_delay_applies_immediately() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey0 = U256::from(1);
+        let coldkey0 = U256::from(3);
+        add_network(1, 0, 0);
+        register_ok_neuron(1, hotkey0, coldkey0, 124124);
+        assert_ok!(SubtensorModule::do_become_delegate(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey0),
+            hotkey0,
             SubtensorModule::get_min_take()
+        ));
+        step_block(1 + InitialTxDelegateTakeRateLimit::get() as u16);
+        SubtensorModule::set_take_increase_delay(0);
+
+        assert_ok!(SubtensorModule::do_increase_take(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey0),
+            hotkey0,
+            u16::MAX / 8
+        ));
+        assert_eq!(SubtensorModule::get_hotkey_take(&hotkey0), u16::MAX / 8);
+        assert_eq!(PendingTakeIncreases::<Test>::get(hotkey0), None);
+        System::assert_has_event(Event::TakeIncreased(coldkey0, hotkey0, u16::MAX / 8).into());
+        System::assert_last_event(
+            Event::TakeIncreaseApplied {
+                hotkey: hotkey0,
+                take: u16::MAX / 8,
+            }
+            .into(),
         );
+    });
+}
 
+// Verify pending take increases are shown in the delegate info
+#[test]
+fn test_pending_take_in_delegate_info() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey0 = U256::from(1);
+        let coldkey0 = U256::from(3);
+        add_network(1, 0, 0);
+        register_ok_neuron(1, hotkey0, coldkey0, 124124);
+        assert_ok!(SubtensorModule::do_become_delegate(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey0),
+            hotkey0,
+            SubtensorModule::get_min_take()
+        ));
         step_block(1 + InitialTxDelegateTakeRateLimit::get() as u16);
 
-        // Can increase after waiting
+        // The pending take is encoded ahead of the nomination settings.
+        let no_pending_take: Option<(codec::Compact<u16>, codec::Compact<u64>)> = None;
+        let tail = (
+            no_pending_take,
+            false,
+            Vec::<U256>::new(),
+            codec::Compact(0u32),
+            codec::Compact(0u64),
+        )
+            .encode();
+        let info = SubtensorModule::get_delegate(hotkey0.encode()).unwrap();
+        assert!(info.encode().ends_with(&tail));
+
         assert_ok!(SubtensorModule::do_increase_take(
             <<Test as Config>::RuntimeOrigin>::signed(coldkey0),
             hotkey0,
             u16::MAX / 8
         ));
-        assert_eq!(SubtensorModule::get_hotkey_take(&hotkey0), u16::MAX / 8);
+        let effective_block =
+            SubtensorModule::get_current_block_as_u64() + InitialTakeIncreaseDelay::get();
+        let tail = (
+            Some((
+                codec::Compact(u16::MAX / 8),
+                codec::Compact(effective_block),
+            )),
+            false,
+            Vec::<U256>::new(),
+            codec::Compact(0u32),
+            codec::Compact(0u64),
+        )
+            .encode();
+        let info = SubtensorModule::get_delegate(hotkey0.encode()).unwrap();
+        assert!(info.encode().ends_with(&tail));
     });
 }
 
@@ -4719,3 +5592,309 @@ fn test_do_schedule_coldkey_swap_regular_user_passes_min_balance() {
         );
     });
 }
+
+/***********************************************************
+    stake_info::get_stake_info_for_coldkey() and delegate_info::get_delegated() tests
+************************************************************/
+
+#[test]
+fn test_get_stake_info_for_coldkey() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        let coldkey = U256::from(1);
+        let owned_hotkey = U256::from(2);
+        let delegate_hotkey = U256::from(3);
+        let delegate_owner = U256::from(4);
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, owned_hotkey, coldkey, 0);
+        register_ok_neuron(netuid, delegate_hotkey, delegate_owner, 1);
+        SubtensorModule::delegate_hotkey(&delegate_hotkey, 0);
+
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &owned_hotkey, 1_000);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(
+            &coldkey,
+            &delegate_hotkey,
+            2_000,
+        );
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(
+            &delegate_owner,
+            &delegate_hotkey,
+            3_000,
+        );
+
+        let stake_info = SubtensorModule::get_stake_info_for_coldkey(coldkey.encode());
+        assert_eq!(stake_info.len(), 2);
+        assert_eq!(
+            stake_info
+                .iter()
+                .map(|info| info.encode())
+                .collect::<Vec<_>>(),
+            vec![
+                (owned_hotkey, coldkey, codec::Compact(1_000u64)).encode(),
+                (delegate_hotkey, coldkey, codec::Compact(2_000u64)).encode(),
+            ]
+        );
+
+        let stake_infos =
+            SubtensorModule::get_stake_info_for_coldkeys(vec![coldkey.encode(), vec![0; 3]]);
+        assert_eq!(stake_infos.len(), 1);
+        assert_eq!(stake_infos[0].1, stake_info);
+
+        assert!(SubtensorModule::get_stake_info_for_coldkey(U256::from(5).encode()).is_empty());
+    });
+}
+
+#[test]
+fn test_get_delegated() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        let coldkey = U256::from(1);
+        let delegate_hotkey = U256::from(2);
+        let other_delegate_hotkey = U256::from(3);
+        let non_delegate_hotkey = U256::from(4);
+        let owner = U256::from(5);
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, delegate_hotkey, owner, 0);
+        register_ok_neuron(netuid, other_delegate_hotkey, owner, 1);
+        register_ok_neuron(netuid, non_delegate_hotkey, owner, 2);
+        SubtensorModule::delegate_hotkey(&delegate_hotkey, 0);
+        SubtensorModule::delegate_hotkey(&other_delegate_hotkey, 0);
+
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(
+            &coldkey,
+            &delegate_hotkey,
+            1_000,
+        );
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(
+            &coldkey,
+            &non_delegate_hotkey,
+            1_000,
+        );
+
+        // Only delegates the coldkey has stake on are returned.
+        let delegated = SubtensorModule::get_delegated(coldkey.encode());
+        assert_eq!(delegated.len(), 1);
+        assert_eq!(
+            delegated[0].0,
+            SubtensorModule::get_delegate(delegate_hotkey.encode()).unwrap()
+        );
+        assert_eq!(delegated[0].1, codec::Compact(1_000u64));
+    });
+}
+
+#[test]
+fn test_check_staking_hotkeys_index() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey, 1_000);
+        assert_ok!(SubtensorModule::check_staking_hotkeys_index());
+
+        // Zero stake entries do not need to be indexed.
+        Stake::<Test>::insert(hotkey, U256::from(3), 0);
+        assert_ok!(SubtensorModule::check_staking_hotkeys_index());
+
+        StakingHotkeys::<Test>::remove(coldkey);
+        assert!(SubtensorModule::check_staking_hotkeys_index().is_err());
+
+        // The ownership index covers the stake of the owner.
+        OwnedHotkeys::<Test>::insert(coldkey, vec![hotkey]);
+        assert_ok!(SubtensorModule::check_staking_hotkeys_index());
+    });
+}
+
+/***********************************************************
+    staking amount based rate limit tests
+************************************************************/
+
+#[test]
+fn test_amount_rate_limit_caps_add_stake() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, coldkey, 0);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, 100_000);
+        SubtensorModule::set_stake_rate_limit_mode(StakeRateLimitMode::Amount);
+        SubtensorModule::set_stake_amount_window(10);
+        SubtensorModule::set_max_stake_amount_per_window(1_000);
+
+        // The number of operations is not limited.
+        SubtensorModule::set_target_stakes_per_interval(1);
+        assert_ok!(SubtensorModule::add_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            hotkey,
+            600
+        ));
+        assert_noop!(
+            SubtensorModule::add_stake(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                hotkey,
+                401
+            ),
+            Error::<Test>::StakeRateLimitExceeded
+        );
+        assert_ok!(SubtensorModule::add_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            hotkey,
+            400
+        ));
+        assert_eq!(
+            SubtensorModule::get_stake_amount_this_window_for_coldkey_hotkey(&coldkey, &hotkey),
+            1_000
+        );
+    });
+}
+
+#[test]
+fn test_amount_rate_limit_caps_remove_stake() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, coldkey, 0);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, 100_000);
+        SubtensorModule::set_stake_rate_limit_mode(StakeRateLimitMode::Amount);
+        SubtensorModule::set_stake_amount_window(10);
+        SubtensorModule::set_max_stake_amount_per_window(1_000);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey, 5_000);
+
+        assert_noop!(
+            SubtensorModule::remove_stake(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                hotkey,
+                1_001
+            ),
+            Error::<Test>::UnstakeRateLimitExceeded
+        );
+        assert_ok!(SubtensorModule::remove_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            hotkey,
+            1_000
+        ));
+    });
+}
+
+#[test]
+fn test_amount_rate_limit_window_slides() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, coldkey, 0);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, 100_000);
+        SubtensorModule::set_stake_rate_limit_mode(StakeRateLimitMode::Amount);
+        SubtensorModule::set_stake_amount_window(10);
+        SubtensorModule::set_max_stake_amount_per_window(1_000);
+
+        assert_ok!(SubtensorModule::add_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            hotkey,
+            1_000
+        ));
+
+        // At the start of the next window the previous one still counts in full.
+        run_to_block(10);
+        assert_eq!(
+            SubtensorModule::get_stake_amount_this_window_for_coldkey_hotkey(&coldkey, &hotkey),
+            1_000
+        );
+
+        // Halfway through, half of the previous window is left in the sliding window.
+        run_to_block(15);
+        assert_eq!(
+            SubtensorModule::get_stake_amount_this_window_for_coldkey_hotkey(&coldkey, &hotkey),
+            500
+        );
+        assert_noop!(
+            SubtensorModule::add_stake(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                hotkey,
+                501
+            ),
+            Error::<Test>::StakeRateLimitExceeded
+        );
+        assert_ok!(SubtensorModule::add_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            hotkey,
+            500
+        ));
+
+        // Two windows later nothing is left.
+        run_to_block(30);
+        assert_eq!(
+            SubtensorModule::get_stake_amount_this_window_for_coldkey_hotkey(&coldkey, &hotkey),
+            0
+        );
+    });
+}
+
+#[test]
+fn test_count_rate_limit_ignores_amount() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, coldkey, 0);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, 100_000);
+        SubtensorModule::set_stake_rate_limit_mode(StakeRateLimitMode::Amount);
+        SubtensorModule::set_stake_amount_window(10);
+        SubtensorModule::set_max_stake_amount_per_window(1);
+        SubtensorModule::set_stake_rate_limit_mode(StakeRateLimitMode::Count);
+        System::assert_last_event(Event::StakeRateLimitModeSet(StakeRateLimitMode::Count).into());
+
+        assert_ok!(SubtensorModule::add_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            hotkey,
+            10_000
+        ));
+        // The moved amount is only tracked by the amount based limiter.
+        assert_eq!(
+            SubtensorModule::get_stake_amount_this_window_for_coldkey_hotkey(&coldkey, &hotkey),
+            0
+        );
+        assert!(!TotalHotkeyColdkeyStakeAmountThisWindow::<Test>::contains_key(coldkey, hotkey));
+    });
+}
+
+#[test]
+fn test_swap_stake_amounts_this_window() {
+    new_test_ext(1).execute_with(|| {
+        let old_hotkey = U256::from(1);
+        let new_hotkey = U256::from(2);
+        let old_coldkey = U256::from(3);
+        let new_coldkey = U256::from(4);
+        let mut weight = Weight::zero();
+
+        // Hotkey swap, the stake has already been moved to the new hotkey.
+        Stake::<Test>::insert(new_hotkey, old_coldkey, 1_000);
+        TotalHotkeyColdkeyStakeAmountThisWindow::<Test>::insert(
+            old_coldkey,
+            old_hotkey,
+            (0, 10, 5),
+        );
+        SubtensorModule::swap_stake_amounts_this_window(&old_hotkey, &new_hotkey, &mut weight);
+        assert!(
+            !TotalHotkeyColdkeyStakeAmountThisWindow::<Test>::contains_key(old_coldkey, old_hotkey)
+        );
+        assert_eq!(
+            TotalHotkeyColdkeyStakeAmountThisWindow::<Test>::get(old_coldkey, new_hotkey),
+            (0, 10, 5)
+        );
+
+        // Coldkey swap.
+        SubtensorModule::swap_stake_amounts_this_window_for_coldkey(
+            &old_coldkey,
+            &new_coldkey,
+            &mut weight,
+        );
+        assert!(
+            !TotalHotkeyColdkeyStakeAmountThisWindow::<Test>::contains_key(old_coldkey, new_hotkey)
+        );
+        assert_eq!(
+            TotalHotkeyColdkeyStakeAmountThisWindow::<Test>::get(new_coldkey, new_hotkey),
+            (0, 10, 5)
+        );
+    });
+}
@@ -624,8 +624,8 @@ fn test_swap_stake_weight_update() {
         // Perform the swap
         SubtensorModule::swap_stake(&old_hotkey, &new_hotkey, &mut weight);
 
-        // Verify the weight update
-        let expected_weight = <Test as frame_system::Config>::DbWeight::get().writes(4);
+        // Verify the weight update, moving the nominator count reads one and writes two
+        let expected_weight = <Test as frame_system::Config>::DbWeight::get().reads_writes(1, 6);
         assert_eq!(weight, expected_weight);
     });
 }
@@ -845,6 +845,47 @@ fn test_swap_loaded_emission_success() {
     });
 }
 
+#[test]
+fn test_swap_hotkey_during_staged_epoch() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        let tempo: u16 = 10;
+        let old_hotkey = U256::from(1);
+        let new_hotkey = U256::from(2);
+        let coldkey = U256::from(3);
+        let swap_cost = 1_000_000_000u64;
+
+        add_network(netuid, tempo, 0);
+        SubtensorModule::set_emission_values(&[netuid], vec![1_000_000_000]).unwrap();
+        register_ok_neuron(netuid, old_hotkey, coldkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &old_hotkey, 1_000);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, swap_cost);
+
+        // The epoch at block 8 is staged from block 3 with the old hotkey.
+        run_to_block(3);
+        assert_eq!(
+            StagedEpochHotkeys::<Test>::get(netuid),
+            vec![(0, old_hotkey)]
+        );
+        assert_ok!(SubtensorModule::do_swap_hotkey(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            &old_hotkey,
+            &new_hotkey
+        ));
+        assert_eq!(
+            StagedEpochHotkeys::<Test>::get(netuid),
+            vec![(0, new_hotkey)]
+        );
+
+        // The epoch pays the new hotkey.
+        run_to_block(8);
+        let tuples = SubtensorModule::get_loaded_emission_tuples(netuid).unwrap();
+        assert_eq!(tuples.len(), 1);
+        assert_eq!(tuples[0].0, new_hotkey);
+        assert!(tuples[0].1 + tuples[0].2 > 0);
+    });
+}
+
 #[test]
 fn test_swap_loaded_emission_weight_update() {
     new_test_ext(1).execute_with(|| {
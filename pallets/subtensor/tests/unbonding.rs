@@ -0,0 +1,393 @@
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::arithmetic_side_effects)]
+
+mod mock;
+use codec::Encode;
+use frame_support::traits::Hooks;
+use frame_support::weights::Weight;
+use frame_support::{assert_noop, assert_ok};
+use frame_system::Config;
+use mock::*;
+use pallet_subtensor::*;
+use sp_core::U256;
+
+#[test]
+fn test_remove_stake_without_unbonding_period_credits_immediately() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, coldkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey, 10_000);
+
+        assert_ok!(SubtensorModule::remove_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            hotkey,
+            10_000
+        ));
+
+        assert_eq!(SubtensorModule::get_coldkey_balance(&coldkey), 10_000);
+        assert!(UnbondingQueue::<Test>::get(coldkey).is_empty());
+    });
+}
+
+#[test]
+fn test_remove_stake_with_unbonding_period_queues_stake() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, coldkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey, 10_000);
+        SubtensorModule::set_unbonding_period(100);
+
+        assert_ok!(SubtensorModule::remove_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            hotkey,
+            4_000
+        ));
+
+        assert_eq!(SubtensorModule::get_coldkey_balance(&coldkey), 0);
+        assert_eq!(
+            SubtensorModule::get_stake_for_coldkey_and_hotkey(&coldkey, &hotkey),
+            6_000
+        );
+        assert_eq!(UnbondingQueue::<Test>::get(coldkey), vec![(4_000, 101)]);
+        assert_eq!(ColdkeysToUnbondAtBlock::<Test>::get(101), vec![coldkey]);
+        System::assert_has_event(
+            Event::StakeUnbonding {
+                coldkey,
+                hotkey,
+                amount: 4_000,
+                unlock_block: 101,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn test_remove_stake_same_block_merges_chunks() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, coldkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey, 10_000);
+        SubtensorModule::set_unbonding_period(100);
+
+        assert_ok!(SubtensorModule::remove_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            hotkey,
+            1_000
+        ));
+        assert_ok!(SubtensorModule::remove_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            hotkey,
+            2_000
+        ));
+
+        assert_eq!(UnbondingQueue::<Test>::get(coldkey), vec![(3_000, 101)]);
+    });
+}
+
+#[test]
+fn test_network_unbonding_period_overrides_global() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+        add_network(netuid, 13, 0);
+        register_ok_neuron(netuid, hotkey, coldkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey, 10_000);
+        SubtensorModule::set_unbonding_period(10);
+        SubtensorModule::set_network_unbonding_period(netuid, 50);
+
+        assert_eq!(
+            SubtensorModule::get_unbonding_period_for_hotkey(&hotkey),
+            50
+        );
+        assert_eq!(
+            SubtensorModule::get_unbonding_period_for_hotkey(&U256::from(3)),
+            10
+        );
+
+        assert_ok!(SubtensorModule::remove_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            hotkey,
+            1_000
+        ));
+        assert_eq!(UnbondingQueue::<Test>::get(coldkey), vec![(1_000, 51)]);
+    });
+}
+
+#[test]
+fn test_claim_unbonded() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, coldkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey, 10_000);
+        SubtensorModule::set_unbonding_period(10);
+
+        assert_ok!(SubtensorModule::remove_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            hotkey,
+            5_000
+        ));
+
+        // Not matured yet.
+        run_to_block(10);
+        assert_noop!(
+            SubtensorModule::claim_unbonded(<<Test as Config>::RuntimeOrigin>::signed(coldkey)),
+            Error::<Test>::NoUnbondedStakeToClaim
+        );
+
+        run_to_block(11);
+        assert_ok!(SubtensorModule::claim_unbonded(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey)
+        ));
+        assert_eq!(SubtensorModule::get_coldkey_balance(&coldkey), 5_000);
+        assert!(UnbondingQueue::<Test>::get(coldkey).is_empty());
+        System::assert_last_event(
+            Event::UnbondedStakeReleased {
+                coldkey,
+                amount: 5_000,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn test_on_idle_releases_unbonded_stake() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, coldkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey, 10_000);
+        SubtensorModule::set_unbonding_period(10);
+        SubtensorModule::set_target_stakes_per_interval(10);
+
+        assert_ok!(SubtensorModule::remove_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            hotkey,
+            5_000
+        ));
+        run_to_block(5);
+        assert_ok!(SubtensorModule::remove_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            hotkey,
+            1_000
+        ));
+
+        // Not enough weight left in the block, nothing is released.
+        run_to_block(11);
+        SubtensorModule::on_idle(System::block_number(), Weight::zero());
+        assert_eq!(SubtensorModule::get_coldkey_balance(&coldkey), 0);
+
+        // Only the first chunk has matured.
+        SubtensorModule::on_idle(System::block_number(), Weight::MAX);
+        assert_eq!(SubtensorModule::get_coldkey_balance(&coldkey), 5_000);
+        assert_eq!(UnbondingQueue::<Test>::get(coldkey), vec![(1_000, 15)]);
+        assert_eq!(UnbondingReleaseCursor::<Test>::get(), 12);
+
+        run_to_block(15);
+        SubtensorModule::on_idle(System::block_number(), Weight::MAX);
+        assert_eq!(SubtensorModule::get_coldkey_balance(&coldkey), 6_000);
+        assert!(UnbondingQueue::<Test>::get(coldkey).is_empty());
+        assert!(ColdkeysToUnbondAtBlock::<Test>::get(15).is_empty());
+    });
+}
+
+#[test]
+fn test_on_idle_resets_cursor_after_full_pass() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, coldkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey, 10_000);
+        SubtensorModule::set_unbonding_period(10);
+        SubtensorModule::set_target_stakes_per_interval(10);
+
+        assert_ok!(SubtensorModule::remove_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            hotkey,
+            1_000
+        ));
+        assert_eq!(UnbondingReleaseCursor::<Test>::get(), 1);
+        assert_eq!(UnbondingLastUnlockBlock::<Test>::get(), 11);
+
+        // Releasing the last chunk resets the cursor.
+        run_to_block(11);
+        SubtensorModule::on_idle(System::block_number(), Weight::MAX);
+        assert_eq!(SubtensorModule::get_coldkey_balance(&coldkey), 1_000);
+        assert_eq!(UnbondingReleaseCursor::<Test>::get(), 0);
+        assert_eq!(UnbondingLastUnlockBlock::<Test>::get(), 0);
+
+        // Nothing is walked while nothing unbonds.
+        run_to_block(50);
+        SubtensorModule::on_idle(System::block_number(), Weight::MAX);
+        assert_eq!(UnbondingReleaseCursor::<Test>::get(), 0);
+
+        // The next unbonding starts a new pass from its own block.
+        assert_ok!(SubtensorModule::remove_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            hotkey,
+            2_000
+        ));
+        assert_eq!(UnbondingReleaseCursor::<Test>::get(), 50);
+        run_to_block(60);
+        SubtensorModule::on_idle(System::block_number(), Weight::MAX);
+        assert_eq!(SubtensorModule::get_coldkey_balance(&coldkey), 3_000);
+        assert_eq!(UnbondingReleaseCursor::<Test>::get(), 0);
+    });
+}
+
+#[test]
+fn test_unbonding_queue_full() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, coldkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey, 10_000);
+        SubtensorModule::set_unbonding_period(100);
+        SubtensorModule::set_target_stakes_per_interval(10);
+        MaxUnbondingChunks::<Test>::put(2);
+
+        assert_ok!(SubtensorModule::remove_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            hotkey,
+            1_000
+        ));
+        run_to_block(2);
+        assert_ok!(SubtensorModule::remove_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            hotkey,
+            1_000
+        ));
+        run_to_block(3);
+        assert_noop!(
+            SubtensorModule::remove_stake(
+                <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+                hotkey,
+                1_000
+            ),
+            Error::<Test>::UnbondingQueueFull
+        );
+    });
+}
+
+#[test]
+fn test_swap_coldkey_moves_unbonding_queue() {
+    new_test_ext(1).execute_with(|| {
+        let old_coldkey = U256::from(1);
+        let new_coldkey = U256::from(3);
+        let hotkey = U256::from(2);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, old_coldkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&old_coldkey, &hotkey, 10_000);
+        SubtensorModule::set_unbonding_period(10);
+
+        assert_ok!(SubtensorModule::remove_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(old_coldkey),
+            hotkey,
+            5_000
+        ));
+
+        assert_ok!(SubtensorModule::perform_swap_coldkey(
+            &old_coldkey,
+            &new_coldkey
+        ));
+        assert!(UnbondingQueue::<Test>::get(old_coldkey).is_empty());
+        assert_eq!(UnbondingQueue::<Test>::get(new_coldkey), vec![(5_000, 11)]);
+
+        run_to_block(11);
+        SubtensorModule::on_idle(System::block_number(), Weight::MAX);
+        assert_eq!(SubtensorModule::get_coldkey_balance(&new_coldkey), 5_000);
+        assert_eq!(SubtensorModule::get_coldkey_balance(&old_coldkey), 0);
+    });
+}
+
+#[test]
+fn test_swap_coldkey_merges_unbonding_queues() {
+    new_test_ext(1).execute_with(|| {
+        let old_coldkey = U256::from(1);
+        let new_coldkey = U256::from(3);
+        let hotkey = U256::from(2);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, old_coldkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&old_coldkey, &hotkey, 10_000);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&new_coldkey, &hotkey, 10_000);
+        SubtensorModule::set_unbonding_period(10);
+        SubtensorModule::set_target_stakes_per_interval(10);
+
+        assert_ok!(SubtensorModule::remove_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(old_coldkey),
+            hotkey,
+            1_000
+        ));
+        run_to_block(3);
+        assert_ok!(SubtensorModule::remove_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(old_coldkey),
+            hotkey,
+            2_000
+        ));
+        run_to_block(5);
+        assert_ok!(SubtensorModule::remove_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(new_coldkey),
+            hotkey,
+            500
+        ));
+        MaxUnbondingChunks::<Test>::put(1);
+
+        // The matured chunk is released, the pending chunks are folded into the last one.
+        run_to_block(12);
+        assert_ok!(SubtensorModule::perform_swap_coldkey(
+            &old_coldkey,
+            &new_coldkey
+        ));
+        assert!(UnbondingQueue::<Test>::get(old_coldkey).is_empty());
+        assert_eq!(UnbondingQueue::<Test>::get(new_coldkey), vec![(2_500, 15)]);
+        assert_eq!(SubtensorModule::get_coldkey_balance(&new_coldkey), 1_000);
+        assert!(!ColdkeysToUnbondAtBlock::<Test>::get(11).contains(&new_coldkey));
+        assert!(!ColdkeysToUnbondAtBlock::<Test>::get(13).contains(&new_coldkey));
+        assert_eq!(ColdkeysToUnbondAtBlock::<Test>::get(15), vec![new_coldkey]);
+        assert_eq!(UnbondingLastUnlockBlock::<Test>::get(), 15);
+
+        run_to_block(15);
+        SubtensorModule::on_idle(System::block_number(), Weight::MAX);
+        assert_eq!(SubtensorModule::get_coldkey_balance(&new_coldkey), 3_500);
+        assert_eq!(UnbondingReleaseCursor::<Test>::get(), 0);
+    });
+}
+
+#[test]
+fn test_get_unbonding_for_coldkey() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+        add_network(1, 13, 0);
+        register_ok_neuron(1, hotkey, coldkey, 0);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(&coldkey, &hotkey, 10_000);
+        SubtensorModule::set_unbonding_period(10);
+
+        assert!(SubtensorModule::get_unbonding_for_coldkey(coldkey.encode()).is_empty());
+
+        assert_ok!(SubtensorModule::remove_stake(
+            <<Test as Config>::RuntimeOrigin>::signed(coldkey),
+            hotkey,
+            5_000
+        ));
+
+        assert_eq!(
+            SubtensorModule::get_unbonding_for_coldkey(coldkey.encode()).len(),
+            1
+        );
+        assert!(SubtensorModule::get_unbonding_for_coldkey(vec![0; 3]).is_empty());
+    });
+}
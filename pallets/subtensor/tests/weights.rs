@@ -1,18 +1,24 @@
 #![allow(clippy::indexing_slicing)]
 
 mod mock;
+use codec::{Compact, Encode};
 use frame_support::{
-    assert_err, assert_ok,
+    assert_err, assert_noop, assert_ok,
     dispatch::{DispatchClass, DispatchInfo, DispatchResult, GetDispatchInfo, Pays},
     pallet_prelude::{InvalidTransaction, TransactionValidityError},
+    traits::{Get, Hooks},
+    weights::Weight,
 };
 use mock::*;
-use pallet_subtensor::{Error, Owner};
+use pallet_subtensor::{
+    Error, Owner, WeightCommits, WeightCommitsSweepCursor, MAX_WEIGHTS_BATCH_SIZE,
+};
 use sp_core::{H256, U256};
 use sp_runtime::{
     traits::{BlakeTwo256, DispatchInfoOf, Hash, SignedExtension},
     DispatchError,
 };
+use sp_std::collections::vec_deque::VecDeque;
 use substrate_fixed::types::I32F32;
 
 /***************************
@@ -535,6 +541,226 @@ fn test_weights_version_key() {
     });
 }
 
+// Test ensures that the maximum and banned version keys of a subnet are enforced.
+#[test]
+fn test_weights_version_key_policy() {
+    new_test_ext(0).execute_with(|| {
+        let hotkey = U256::from(55);
+        let coldkey = U256::from(66);
+        let netuid: u16 = 1;
+        add_network(netuid, 0, 0);
+        register_ok_neuron(netuid, hotkey, coldkey, 2143124);
+        SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+
+        SubtensorModule::set_weights_version_key(netuid, 10);
+        SubtensorModule::set_weights_max_version_key(netuid, 20);
+        SubtensorModule::set_banned_weights_version_keys(netuid, vec![15, 12, 15]);
+        assert_eq!(
+            SubtensorModule::get_banned_weights_version_keys(netuid),
+            vec![12, 15]
+        );
+
+        assert_eq!(
+            SubtensorModule::check_version_key(netuid, 9),
+            Err(Error::<Test>::IncorrectWeightVersionKey.into())
+        );
+        assert_eq!(
+            SubtensorModule::check_version_key(netuid, 21),
+            Err(Error::<Test>::WeightVersionKeyTooHigh.into())
+        );
+        assert_eq!(
+            SubtensorModule::check_version_key(netuid, 15),
+            Err(Error::<Test>::WeightVersionKeyBanned.into())
+        );
+        assert_ok!(SubtensorModule::check_version_key(netuid, 10));
+        assert_ok!(SubtensorModule::check_version_key(netuid, 20));
+
+        assert_eq!(
+            SubtensorModule::set_weights(
+                RuntimeOrigin::signed(hotkey),
+                netuid,
+                vec![0],
+                vec![1],
+                12
+            ),
+            Err(Error::<Test>::WeightVersionKeyBanned.into())
+        );
+        assert_ok!(SubtensorModule::set_weights(
+            RuntimeOrigin::signed(hotkey),
+            netuid,
+            vec![0],
+            vec![1],
+            13
+        ));
+
+        // A max version key of 0 and an empty ban list leave the policy open ended.
+        SubtensorModule::set_weights_max_version_key(netuid, 0);
+        SubtensorModule::set_banned_weights_version_keys(netuid, vec![]);
+        assert_ok!(SubtensorModule::check_version_key(netuid, 15));
+        assert_ok!(SubtensorModule::check_version_key(netuid, u64::MAX));
+
+        // The policy is exposed at the end of the subnet hyperparams, before the weights window.
+        SubtensorModule::set_weights_max_version_key(netuid, 20);
+        SubtensorModule::set_banned_weights_version_keys(netuid, vec![12]);
+        let tail = (Compact(20u64), vec![Compact(12u64)], Compact(0u64)).encode();
+        let hyperparams = SubtensorModule::get_subnet_hyperparams(netuid);
+        assert!(hyperparams.is_some_and(|h| h.encode().ends_with(&tail)));
+    });
+}
+
+// Test ensures that weights are only accepted within the weights window before an epoch.
+#[test]
+fn test_weights_window() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(55);
+        let netuid: u16 = 1;
+        add_network(netuid, 10, 0);
+        register_ok_neuron(netuid, hotkey, U256::from(66), 2143124);
+        SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+        SubtensorModule::set_weights_window(netuid, 3);
+
+        // With tempo 10 the epoch runs at block 19 and is staged from block 14, the three
+        // blocks before staging are open and weights set while it is staged wait a full tempo.
+        let open: Vec<bool> = (9..=14)
+            .map(|block| SubtensorModule::check_weights_window(netuid, block))
+            .collect();
+        assert_eq!(open, vec![false, false, true, true, true, false]);
+
+        run_to_block(10);
+        assert_eq!(
+            SubtensorModule::set_weights(
+                RuntimeOrigin::signed(hotkey),
+                netuid,
+                vec![0],
+                vec![1],
+                0
+            ),
+            Err(Error::<Test>::WeightsWindowClosed.into())
+        );
+        run_to_block(11);
+        assert_ok!(SubtensorModule::set_weights(
+            RuntimeOrigin::signed(hotkey),
+            netuid,
+            vec![0],
+            vec![1],
+            0
+        ));
+
+        // A window of 0 accepts weights at any block.
+        SubtensorModule::set_weights_window(netuid, 0);
+        assert!((9..=14).all(|block| SubtensorModule::check_weights_window(netuid, block)));
+    });
+}
+
+// Test ensures that weights set on the last block of the weights window are gathered by the
+// epoch staged right after it.
+#[test]
+fn test_weights_window_closes_when_epoch_is_staged() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        let tempo: u16 = 10;
+        let validator = U256::from(55);
+        let server = U256::from(56);
+        add_network(netuid, tempo, 0);
+        register_ok_neuron(netuid, validator, U256::from(66), 2143124);
+        register_ok_neuron(netuid, server, U256::from(67), 3124124);
+        SubtensorModule::increase_stake_on_coldkey_hotkey_account(
+            &U256::from(66),
+            &validator,
+            1_000,
+        );
+        SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+        SubtensorModule::set_weights_window(netuid, 3);
+        let lead: u64 = SubtensorModule::get_staged_epoch_lead_blocks(tempo);
+
+        // The epoch at block 8 grants validator permits, the next one runs at block 19 and
+        // is staged from block 14.
+        run_to_block(13);
+        assert_eq!(
+            SubtensorModule::blocks_until_next_epoch(netuid, tempo, 14),
+            lead
+        );
+        assert!(SubtensorModule::get_validator_permit_for_uid(netuid, 0));
+        assert_ok!(SubtensorModule::set_weights(
+            RuntimeOrigin::signed(validator),
+            netuid,
+            vec![1],
+            vec![u16::MAX],
+            0
+        ));
+
+        // Once the epoch is staged the window is closed.
+        run_to_block(14);
+        assert!(pallet_subtensor::StagedEpoch::<Test>::contains_key(netuid));
+        assert_eq!(
+            SubtensorModule::set_weights(
+                RuntimeOrigin::signed(validator),
+                netuid,
+                vec![0],
+                vec![u16::MAX],
+                0
+            ),
+            Err(Error::<Test>::WeightsWindowClosed.into())
+        );
+
+        // The staged epoch pays the server the validator weighted on the last open block.
+        run_to_block(19);
+        assert!(!pallet_subtensor::StagedEpoch::<Test>::contains_key(netuid));
+        assert_eq!(SubtensorModule::get_incentive_for_uid(netuid, 1), u16::MAX);
+        assert_eq!(SubtensorModule::get_incentive_for_uid(netuid, 0), 0);
+    });
+}
+
+// Test ensures that check_weights reports every failing check without writing state.
+#[test]
+fn test_check_weights() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(55);
+        let netuid: u16 = 1;
+        add_network(netuid, 0, 0);
+        register_ok_neuron(netuid, hotkey, U256::from(66), 2143124);
+        register_ok_neuron(netuid, U256::from(77), U256::from(88), 3124124);
+        SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+        SubtensorModule::set_weights_version_key(netuid, 10);
+
+        // Several checks fail at once.
+        let (failures, _) =
+            SubtensorModule::check_weights(netuid, &hotkey, &[1, 1, 5], &[1, 2, 3], 9);
+        assert_eq!(
+            failures,
+            vec![
+                Error::<Test>::UidsLengthExceedUidsInSubNet.into(),
+                Error::<Test>::IncorrectWeightVersionKey.into(),
+                Error::<Test>::NeuronNoValidatorPermit.into(),
+                Error::<Test>::DuplicateUids.into(),
+                Error::<Test>::UidVecContainInvalidOne.into(),
+            ]
+        );
+        let (failures, _) = SubtensorModule::check_weights(netuid, &U256::from(99), &[0], &[1], 10);
+        assert_eq!(
+            failures,
+            vec![DispatchError::from(
+                Error::<Test>::HotKeyNotRegisteredInSubNet
+            )]
+        );
+
+        // A valid self weight returns the weights that would be stored.
+        let (failures, weights) = SubtensorModule::check_weights(netuid, &hotkey, &[0], &[10], 10);
+        assert!(failures.is_empty());
+        assert_eq!(weights, vec![(0, u16::MAX)]);
+        assert!(SubtensorModule::get_weights_sparse(netuid)[0].is_empty());
+
+        // The runtime api entry point decodes the hotkey.
+        assert!(
+            SubtensorModule::get_weights_check(netuid, vec![1, 2], vec![0], vec![10], 10).is_none()
+        );
+        assert!(
+            SubtensorModule::get_weights_check(netuid, hotkey.encode(), vec![0], vec![10], 10)
+                .is_some()
+        );
+    });
+}
+
 // Test ensures that uid has validator permit to set non-self weights.
 #[test]
 fn test_weights_err_setting_weights_too_fast() {
@@ -1877,3 +2103,958 @@ fn commit_reveal_set_weights(
 
     Ok(())
 }
+
+/***************************
+  pub fn batch_set_weights() tests
+*****************************/
+
+#[test]
+#[cfg(not(tarpaulin))]
+fn test_batch_set_weights_dispatch_info_ok() {
+    new_test_ext(0).execute_with(|| {
+        let weights = vec![(1, vec![1, 1], vec![1, 1], 0), (2, vec![1], vec![1], 0)];
+        let call = RuntimeCall::SubtensorModule(SubtensorCall::batch_set_weights { weights });
+        let dispatch_info = call.get_dispatch_info();
+
+        assert_eq!(dispatch_info.class, DispatchClass::Normal);
+        assert_eq!(dispatch_info.pays_fee, Pays::No);
+    });
+}
+
+#[test]
+fn test_batch_set_weights_validate() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        add_network(1, 0, 0);
+        add_network(2, 0, 0);
+        register_ok_neuron(1, hotkey, coldkey, 10);
+        register_ok_neuron(2, hotkey, coldkey, 20);
+        SubtensorModule::set_weights_set_rate_limit(1, 0);
+        step_block(1);
+        assert_ok!(SubtensorModule::set_weights(
+            RuntimeOrigin::signed(hotkey),
+            1,
+            vec![0],
+            vec![1],
+            0
+        ));
+        step_block(1);
+
+        let info: DispatchInfo =
+            DispatchInfoOf::<<Test as frame_system::Config>::RuntimeCall>::default();
+        let extension = pallet_subtensor::SubtensorSignedExtension::<Test>::new();
+
+        // An empty batch is rejected.
+        let call =
+            RuntimeCall::SubtensorModule(SubtensorCall::batch_set_weights { weights: vec![] });
+        assert_err!(
+            extension.validate(&hotkey, &call, &info, 10),
+            TransactionValidityError::Invalid(InvalidTransaction::Call)
+        );
+
+        // So is a batch with more than MAX_WEIGHTS_BATCH_SIZE entries.
+        let call = RuntimeCall::SubtensorModule(SubtensorCall::batch_set_weights {
+            weights: vec![(1, vec![0], vec![1], 0); MAX_WEIGHTS_BATCH_SIZE as usize + 1],
+        });
+        assert_err!(
+            extension.validate(&hotkey, &call, &info, 10),
+            TransactionValidityError::Invalid(InvalidTransaction::Call)
+        );
+
+        // So is a hotkey below the minimum stake.
+        let call = RuntimeCall::SubtensorModule(SubtensorCall::batch_set_weights {
+            weights: vec![(1, vec![0], vec![1], 0), (2, vec![0], vec![1], 0)],
+        });
+        SubtensorModule::set_weights_min_stake(1_000);
+        assert_err!(
+            extension.validate(&hotkey, &call, &info, 10),
+            TransactionValidityError::Invalid(InvalidTransaction::Call)
+        );
+
+        // The batch takes the priority of its most recently updated subnet.
+        SubtensorModule::increase_stake_on_hotkey_account(&hotkey, 1_000);
+        let priority_1 = SubtensorModule::get_priority_set_weights(&hotkey, 1);
+        let priority_2 = SubtensorModule::get_priority_set_weights(&hotkey, 2);
+        assert!(priority_1 < priority_2);
+        assert_eq!(
+            extension
+                .validate(&hotkey, &call, &info, 10)
+                .map(|valid| (valid.priority, valid.longevity)),
+            Ok((priority_1, 1))
+        );
+    });
+}
+
+#[test]
+fn test_batch_set_weights_applies_entries_independently() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        add_network(1, 0, 0);
+        add_network(2, 0, 0);
+        register_ok_neuron(1, hotkey, coldkey, 10);
+        register_ok_neuron(2, hotkey, coldkey, 20);
+        SubtensorModule::set_weights_set_rate_limit(1, 0);
+        SubtensorModule::set_commit_reveal_weights_enabled(2, true);
+        step_block(1);
+
+        assert_ok!(SubtensorModule::batch_set_weights(
+            RuntimeOrigin::signed(hotkey),
+            vec![
+                (1, vec![0], vec![1], 0),
+                (2, vec![0], vec![1], 0),
+                (3, vec![0], vec![1], 0),
+            ]
+        ));
+
+        // Only the first entry is applied, the failures are reported by netuid.
+        assert_eq!(
+            SubtensorModule::get_last_update_for_uid(1, 0),
+            SubtensorModule::get_current_block_as_u64()
+        );
+        assert_eq!(SubtensorModule::get_last_update_for_uid(2, 0), 1);
+        System::assert_has_event(pallet_subtensor::Event::WeightsSet(1, 0).into());
+        System::assert_last_event(
+            pallet_subtensor::Event::BatchWeightsCompleted {
+                hotkey,
+                results: vec![
+                    (1, Ok(())),
+                    (2, Err(Error::<Test>::CommitRevealEnabled.into())),
+                    (3, Err(Error::<Test>::SubNetworkDoesNotExist.into())),
+                ],
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn test_batch_set_weights_refunds_unused_weight() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        add_network(1, 0, 0);
+        add_network(2, 0, 0);
+        register_ok_neuron(1, hotkey, coldkey, 10);
+        register_ok_neuron(2, hotkey, coldkey, 20);
+        SubtensorModule::set_weights_set_rate_limit(1, 0);
+        SubtensorModule::set_commit_reveal_weights_enabled(2, true);
+        step_block(1);
+
+        let weights = vec![(1, vec![0], vec![1], 0), (2, vec![0], vec![1], 0)];
+        let call = RuntimeCall::SubtensorModule(SubtensorCall::batch_set_weights {
+            weights: weights.clone(),
+        });
+        let actual_weight =
+            SubtensorModule::batch_set_weights(RuntimeOrigin::signed(hotkey), weights)
+                .expect("Batch dispatched.")
+                .actual_weight
+                .expect("Actual weight returned.");
+
+        // The entry is charged for the one uid it sets, the rejected one for a read.
+        assert_eq!(
+            actual_weight,
+            SubtensorModule::get_batch_set_weights_entry_weight(1)
+                + <Test as frame_system::Config>::DbWeight::get().reads(1)
+        );
+        assert!(actual_weight.ref_time() < call.get_dispatch_info().weight.ref_time());
+    });
+}
+
+#[test]
+fn test_batch_set_weights_rejects_invalid_size() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        assert_noop!(
+            SubtensorModule::batch_set_weights(RuntimeOrigin::signed(hotkey), vec![]),
+            Error::<Test>::InvalidWeightsBatchSize
+        );
+        assert_noop!(
+            SubtensorModule::batch_set_weights(
+                RuntimeOrigin::signed(hotkey),
+                vec![(1, vec![0], vec![1], 0); MAX_WEIGHTS_BATCH_SIZE as usize + 1]
+            ),
+            Error::<Test>::InvalidWeightsBatchSize
+        );
+    });
+}
+
+#[test]
+fn test_batch_set_weights_no_signature() {
+    new_test_ext(0).execute_with(|| {
+        let result = SubtensorModule::batch_set_weights(
+            RuntimeOrigin::none(),
+            vec![(1, vec![0], vec![1], 0)],
+        );
+        assert_eq!(result, Err(DispatchError::BadOrigin.into()));
+    });
+}
+
+/***************************
+  pub fn batch_commit_weights() and batch_reveal_weights() tests
+*****************************/
+
+#[test]
+fn test_batch_commit_reveal_weights() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let coldkey = U256::from(2);
+        let uids: Vec<u16> = vec![0];
+        let weight_values: Vec<u16> = vec![10];
+        let salt: Vec<u16> = vec![1, 2, 3, 4, 5, 6, 7, 8];
+        let version_key: u64 = 0;
+        let commit_hash = |netuid: u16| -> H256 {
+            BlakeTwo256::hash_of(&(
+                hotkey,
+                netuid,
+                uids.clone(),
+                weight_values.clone(),
+                salt.clone(),
+                version_key,
+            ))
+        };
+
+        for netuid in 1..=3 {
+            add_network(netuid, 0, 0);
+            register_ok_neuron(netuid, hotkey, coldkey, netuid as u64 * 10);
+            SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+            SubtensorModule::set_commit_reveal_weights_interval(netuid, 5);
+        }
+        SubtensorModule::set_commit_reveal_weights_enabled(1, true);
+        SubtensorModule::set_commit_reveal_weights_enabled(2, true);
+
+        // Commit reveal is disabled on the third subnet.
+        assert_ok!(SubtensorModule::batch_commit_weights(
+            RuntimeOrigin::signed(hotkey),
+            vec![
+                (1, commit_hash(1)),
+                (2, commit_hash(2)),
+                (3, commit_hash(3))
+            ]
+        ));
+        System::assert_last_event(
+            pallet_subtensor::Event::BatchWeightsCommitCompleted {
+                hotkey,
+                results: vec![
+                    (1, Ok(())),
+                    (2, Ok(())),
+                    (3, Err(Error::<Test>::CommitRevealDisabled.into())),
+                ],
+            }
+            .into(),
+        );
+        assert!(pallet_subtensor::WeightCommits::<Test>::contains_key(
+            1, hotkey
+        ));
+        assert!(!pallet_subtensor::WeightCommits::<Test>::contains_key(
+            3, hotkey
+        ));
+
+        step_block(5);
+
+        // The second reveal does not match its commit.
+        assert_ok!(SubtensorModule::batch_reveal_weights(
+            RuntimeOrigin::signed(hotkey),
+            vec![
+                (
+                    1,
+                    uids.clone(),
+                    weight_values.clone(),
+                    salt.clone(),
+                    version_key
+                ),
+                (2, uids.clone(), vec![20], salt.clone(), version_key),
+            ]
+        ));
+        System::assert_last_event(
+            pallet_subtensor::Event::BatchWeightsRevealCompleted {
+                hotkey,
+                results: vec![
+                    (1, Ok(())),
+                    (
+                        2,
+                        Err(Error::<Test>::InvalidRevealCommitHashNotMatch.into()),
+                    ),
+                ],
+            }
+            .into(),
+        );
+        assert_eq!(
+            SubtensorModule::get_last_update_for_uid(1, 0),
+            SubtensorModule::get_current_block_as_u64()
+        );
+        assert_eq!(SubtensorModule::get_last_update_for_uid(2, 0), 1);
+    });
+}
+
+#[test]
+fn test_batch_commit_reveal_weights_validate() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        let info: DispatchInfo =
+            DispatchInfoOf::<<Test as frame_system::Config>::RuntimeCall>::default();
+        let extension = pallet_subtensor::SubtensorSignedExtension::<Test>::new();
+
+        // Empty batches are rejected.
+        let call =
+            RuntimeCall::SubtensorModule(SubtensorCall::batch_commit_weights { commits: vec![] });
+        assert_err!(
+            extension.validate(&hotkey, &call, &info, 10),
+            TransactionValidityError::Invalid(InvalidTransaction::Call)
+        );
+        let call =
+            RuntimeCall::SubtensorModule(SubtensorCall::batch_reveal_weights { reveals: vec![] });
+        assert_err!(
+            extension.validate(&hotkey, &call, &info, 10),
+            TransactionValidityError::Invalid(InvalidTransaction::Call)
+        );
+
+        let call = RuntimeCall::SubtensorModule(SubtensorCall::batch_commit_weights {
+            commits: vec![(1, H256::zero())],
+        });
+        assert_eq!(
+            extension
+                .validate(&hotkey, &call, &info, 10)
+                .map(|valid| valid.longevity),
+            Ok(1)
+        );
+
+        // Hotkeys below the minimum stake are rejected.
+        SubtensorModule::set_weights_min_stake(1_000);
+        assert_err!(
+            extension.validate(&hotkey, &call, &info, 10),
+            TransactionValidityError::Invalid(InvalidTransaction::Call)
+        );
+    });
+}
+
+#[test]
+fn test_batch_commit_reveal_weights_size_and_refund() {
+    new_test_ext(1).execute_with(|| {
+        let hotkey = U256::from(1);
+        add_network(1, 0, 0);
+        register_ok_neuron(1, hotkey, U256::from(2), 10);
+        SubtensorModule::set_commit_reveal_weights_enabled(1, true);
+
+        assert_noop!(
+            SubtensorModule::batch_commit_weights(RuntimeOrigin::signed(hotkey), vec![]),
+            Error::<Test>::InvalidWeightsBatchSize
+        );
+        assert_noop!(
+            SubtensorModule::batch_reveal_weights(
+                RuntimeOrigin::signed(hotkey),
+                vec![(1, vec![0], vec![1], vec![1], 0); MAX_WEIGHTS_BATCH_SIZE as usize + 1]
+            ),
+            Error::<Test>::InvalidWeightsBatchSize
+        );
+
+        // The entry on a subnet without commit reveal is only charged a read.
+        let commits = vec![(1, H256::zero()), (2, H256::zero())];
+        let call = RuntimeCall::SubtensorModule(SubtensorCall::batch_commit_weights {
+            commits: commits.clone(),
+        });
+        let actual_weight =
+            SubtensorModule::batch_commit_weights(RuntimeOrigin::signed(hotkey), commits)
+                .expect("Batch dispatched.")
+                .actual_weight
+                .expect("Actual weight returned.");
+        assert_eq!(
+            call.get_dispatch_info()
+                .weight
+                .saturating_sub(actual_weight),
+            Weight::from_parts(46_000_000, 0)
+                + <Test as frame_system::Config>::DbWeight::get().writes(1)
+        );
+    });
+}
+
+/***************************
+  pending weight commits tests
+*****************************/
+
+#[test]
+fn test_commits_pipelined_across_intervals() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        let hotkey = U256::from(1);
+        add_network(netuid, 0, 0);
+        register_ok_neuron(netuid, hotkey, U256::from(2), 100000);
+        SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+        SubtensorModule::set_commit_reveal_weights_interval(netuid, 10);
+        SubtensorModule::set_commit_reveal_weights_enabled(netuid, true);
+        let commit_hash_1: H256 = BlakeTwo256::hash_of(&(
+            hotkey,
+            netuid,
+            vec![0u16],
+            vec![10u16],
+            vec![1u16, 2, 3],
+            0u64,
+        ));
+        let commit_hash_2 = BlakeTwo256::hash_of(&(
+            hotkey,
+            netuid,
+            vec![0u16],
+            vec![10u16],
+            vec![1u16, 2, 3],
+            1u64,
+        ));
+
+        assert_ok!(SubtensorModule::commit_weights(
+            RuntimeOrigin::signed(hotkey),
+            netuid,
+            commit_hash_1
+        ));
+        step_block(10);
+
+        // Committing in the next interval keeps the first commit pending.
+        assert_ok!(SubtensorModule::commit_weights(
+            RuntimeOrigin::signed(hotkey),
+            netuid,
+            commit_hash_2
+        ));
+        assert_eq!(
+            pallet_subtensor::WeightCommits::<Test>::get(netuid, hotkey).map(|c| c.len()),
+            Some(2)
+        );
+
+        // Only the first commit can be revealed in this interval.
+        assert_err!(
+            SubtensorModule::reveal_weights(
+                RuntimeOrigin::signed(hotkey),
+                netuid,
+                vec![0],
+                vec![10],
+                vec![1, 2, 3],
+                1,
+            ),
+            Error::<Test>::InvalidRevealCommitTempo
+        );
+        assert_ok!(SubtensorModule::reveal_weights(
+            RuntimeOrigin::signed(hotkey),
+            netuid,
+            vec![0],
+            vec![10],
+            vec![1, 2, 3],
+            0,
+        ));
+        assert_eq!(
+            pallet_subtensor::WeightCommits::<Test>::get(netuid, hotkey).map(|c| c.len()),
+            Some(1)
+        );
+
+        step_block(10);
+        assert_ok!(SubtensorModule::reveal_weights(
+            RuntimeOrigin::signed(hotkey),
+            netuid,
+            vec![0],
+            vec![10],
+            vec![1, 2, 3],
+            1,
+        ));
+        assert!(!pallet_subtensor::WeightCommits::<Test>::contains_key(
+            netuid, hotkey
+        ));
+    });
+}
+
+#[test]
+fn test_expired_commits_are_removed() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        let hotkey = U256::from(1);
+        add_network(netuid, 0, 0);
+        register_ok_neuron(netuid, hotkey, U256::from(2), 100000);
+        SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+        SubtensorModule::set_commit_reveal_weights_interval(netuid, 10);
+        SubtensorModule::set_commit_reveal_weights_enabled(netuid, true);
+        let commit_hash: H256 = BlakeTwo256::hash_of(&(
+            hotkey,
+            netuid,
+            vec![0u16],
+            vec![10u16],
+            vec![1u16, 2, 3],
+            0u64,
+        ));
+
+        assert_ok!(SubtensorModule::commit_weights(
+            RuntimeOrigin::signed(hotkey),
+            netuid,
+            commit_hash
+        ));
+        assert!(!SubtensorModule::is_commit_expired(netuid, 1));
+
+        // The reveal window of the first commit is [10, 20).
+        step_block(19);
+        assert!(SubtensorModule::is_commit_expired(netuid, 1));
+        assert_err!(
+            SubtensorModule::reveal_weights(
+                RuntimeOrigin::signed(hotkey),
+                netuid,
+                vec![0],
+                vec![10],
+                vec![1, 2, 3],
+                0,
+            ),
+            Error::<Test>::InvalidRevealCommitTempo
+        );
+
+        // The next commit drops the expired one.
+        assert_ok!(SubtensorModule::commit_weights(
+            RuntimeOrigin::signed(hotkey),
+            netuid,
+            commit_hash
+        ));
+        assert_eq!(
+            pallet_subtensor::WeightCommits::<Test>::get(netuid, hotkey).map(Vec::from),
+            Some(vec![(commit_hash, 20)])
+        );
+    });
+}
+
+#[test]
+fn test_too_many_unrevealed_commits() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        let hotkey = U256::from(1);
+        // Without an interval commits never expire.
+        add_network(netuid, 0, 0);
+        register_ok_neuron(netuid, hotkey, U256::from(2), 100000);
+        SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+        SubtensorModule::set_commit_reveal_weights_interval(netuid, 0);
+        SubtensorModule::set_commit_reveal_weights_enabled(netuid, true);
+        let commit_hash: H256 = BlakeTwo256::hash_of(&(
+            hotkey,
+            netuid,
+            vec![0u16],
+            vec![10u16],
+            vec![1u16, 2, 3],
+            0u64,
+        ));
+
+        for _ in 0..MaxWeightCommits::get() {
+            assert_ok!(SubtensorModule::commit_weights(
+                RuntimeOrigin::signed(hotkey),
+                netuid,
+                commit_hash
+            ));
+        }
+        assert_err!(
+            SubtensorModule::commit_weights(RuntimeOrigin::signed(hotkey), netuid, commit_hash),
+            Error::<Test>::TooManyUnrevealedCommits
+        );
+
+        // Revealing frees a slot.
+        assert_ok!(SubtensorModule::reveal_weights(
+            RuntimeOrigin::signed(hotkey),
+            netuid,
+            vec![0],
+            vec![10],
+            vec![1, 2, 3],
+            0,
+        ));
+        assert_ok!(SubtensorModule::commit_weights(
+            RuntimeOrigin::signed(hotkey),
+            netuid,
+            commit_hash
+        ));
+    });
+}
+
+#[test]
+fn test_on_idle_removes_expired_commits() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        add_network(netuid, 0, 0);
+        SubtensorModule::set_commit_reveal_weights_interval(netuid, 10);
+        for i in 1..=3u8 {
+            WeightCommits::<Test>::insert(
+                netuid,
+                U256::from(i),
+                VecDeque::from(vec![(H256::repeat_byte(i), 1u64)]),
+            );
+        }
+        WeightCommits::<Test>::insert(
+            netuid,
+            U256::from(4),
+            VecDeque::from(vec![
+                (H256::repeat_byte(4), 1u64),
+                (H256::repeat_byte(5), 15u64),
+            ]),
+        );
+
+        // The commits made at block 1 could be revealed until block 20.
+        run_to_block(20);
+
+        // Only one hotkey fits in the weight, the sweep stops there and keeps a cursor.
+        let one_entry = <Test as frame_system::Config>::DbWeight::get().reads_writes(3, 2);
+        SubtensorModule::remove_expired_weight_commits_on_idle(one_entry);
+        let pending_commits =
+            || -> usize { WeightCommits::<Test>::iter_values().map(|c| c.len()).sum() };
+        assert!(WeightCommitsSweepCursor::<Test>::get().is_some());
+        assert_eq!(pending_commits(), 4);
+        SubtensorModule::remove_expired_weight_commits_on_idle(one_entry);
+        assert_eq!(pending_commits(), 3);
+
+        // The sweep resumes from the cursor and starts over once it reaches the end.
+        SubtensorModule::on_idle(System::block_number(), Weight::MAX);
+        assert!(WeightCommitsSweepCursor::<Test>::get().is_none());
+        assert_eq!(pending_commits(), 1);
+        assert_eq!(
+            WeightCommits::<Test>::get(netuid, U256::from(4)).map(Vec::from),
+            Some(vec![(H256::repeat_byte(5), 15)])
+        );
+        System::assert_has_event(
+            pallet_subtensor::Event::WeightsCommitExpired {
+                netuid,
+                hotkey: U256::from(4),
+                commit_hash: H256::repeat_byte(4),
+                commit_block: 1,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn test_commit_expired_events_only_after_stored_removal() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        let hotkey = U256::from(1);
+        add_network(netuid, 0, 0);
+        register_ok_neuron(netuid, hotkey, U256::from(2), 100000);
+        SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+        SubtensorModule::set_commit_reveal_weights_interval(netuid, 10);
+        SubtensorModule::set_commit_reveal_weights_enabled(netuid, true);
+        let commit_hash: H256 = BlakeTwo256::hash_of(&(
+            hotkey,
+            netuid,
+            vec![0u16],
+            vec![10u16],
+            vec![1u16, 2, 3],
+            0u64,
+        ));
+        let expired_hash = H256::repeat_byte(7);
+        let expired_event = pallet_subtensor::Event::WeightsCommitExpired {
+            netuid,
+            hotkey,
+            commit_hash: expired_hash,
+            commit_block: 1,
+        };
+        let has_expired_event = || {
+            System::events()
+                .iter()
+                .any(|record| record.event == expired_event.clone().into())
+        };
+
+        // An expired commit ahead of a full queue of live commits made at block 15.
+        let mut commits: Vec<(H256, u64)> = vec![(expired_hash, 1)];
+        for _ in 0..MaxWeightCommits::get() {
+            commits.push((commit_hash, 15));
+        }
+        WeightCommits::<Test>::insert(netuid, hotkey, VecDeque::from(commits.clone()));
+        run_to_block(20);
+        System::reset_events();
+
+        // The commit fails, so the expired commit is neither removed nor reported.
+        assert_err!(
+            SubtensorModule::commit_weights(RuntimeOrigin::signed(hotkey), netuid, commit_hash),
+            Error::<Test>::TooManyUnrevealedCommits
+        );
+        assert_eq!(
+            WeightCommits::<Test>::get(netuid, hotkey).map(Vec::from),
+            Some(commits)
+        );
+        assert!(!has_expired_event());
+
+        // Once the commit is stored the removal is reported ahead of it.
+        WeightCommits::<Test>::insert(
+            netuid,
+            hotkey,
+            VecDeque::from(vec![(expired_hash, 1u64), (commit_hash, 15u64)]),
+        );
+        assert_ok!(SubtensorModule::commit_weights(
+            RuntimeOrigin::signed(hotkey),
+            netuid,
+            commit_hash
+        ));
+        assert!(has_expired_event());
+        System::assert_last_event(
+            pallet_subtensor::Event::WeightsCommitted {
+                netuid,
+                hotkey,
+                commit_hash,
+                block: 20,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn test_commit_reveal_weights_events() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        let hotkey = U256::from(1);
+        add_network(netuid, 0, 0);
+        register_ok_neuron(netuid, hotkey, U256::from(2), 100000);
+        SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+        SubtensorModule::set_commit_reveal_weights_interval(netuid, 10);
+        SubtensorModule::set_commit_reveal_weights_enabled(netuid, true);
+        let commit_hash: H256 = BlakeTwo256::hash_of(&(
+            hotkey,
+            netuid,
+            vec![0u16],
+            vec![10u16],
+            vec![1u16, 2, 3],
+            0u64,
+        ));
+
+        assert_ok!(SubtensorModule::commit_weights(
+            RuntimeOrigin::signed(hotkey),
+            netuid,
+            commit_hash
+        ));
+        System::assert_last_event(
+            pallet_subtensor::Event::WeightsCommitted {
+                netuid,
+                hotkey,
+                commit_hash,
+                block: 1,
+            }
+            .into(),
+        );
+
+        step_block(10);
+        assert_ok!(SubtensorModule::reveal_weights(
+            RuntimeOrigin::signed(hotkey),
+            netuid,
+            vec![0],
+            vec![10],
+            vec![1, 2, 3],
+            0,
+        ));
+        System::assert_has_event(pallet_subtensor::Event::WeightsSet(netuid, 0).into());
+        System::assert_last_event(
+            pallet_subtensor::Event::WeightsRevealed {
+                netuid,
+                hotkey,
+                commit_hash,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn test_get_pending_weight_commits() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        let hotkey = U256::from(1);
+        add_network(netuid, 0, 0);
+        register_ok_neuron(netuid, hotkey, U256::from(2), 100000);
+        SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+        SubtensorModule::set_commit_reveal_weights_interval(netuid, 10);
+        SubtensorModule::set_commit_reveal_weights_enabled(netuid, true);
+        let commit_hash: H256 = BlakeTwo256::hash_of(&(
+            hotkey,
+            netuid,
+            vec![0u16],
+            vec![10u16],
+            vec![1u16, 2, 3],
+            0u64,
+        ));
+        assert!(SubtensorModule::get_pending_weight_commits(netuid).is_empty());
+
+        assert_ok!(SubtensorModule::commit_weights(
+            RuntimeOrigin::signed(hotkey),
+            netuid,
+            commit_hash
+        ));
+
+        // The commit made at block 1 can be revealed in [10, 20).
+        assert_eq!(SubtensorModule::get_reveal_window(netuid, 1), (10, 20));
+        let expected = vec![(
+            hotkey,
+            commit_hash,
+            Compact(1u64),
+            Compact(10u64),
+            Compact(20u64),
+        )];
+        assert_eq!(
+            SubtensorModule::get_pending_weight_commits(netuid).encode(),
+            expected.encode()
+        );
+
+        // Expired commits are not listed.
+        run_to_block(20);
+        assert!(SubtensorModule::get_pending_weight_commits(netuid).is_empty());
+    });
+}
+
+/***************************
+  pub fn set_weights_delta() tests
+*****************************/
+
+#[test]
+#[cfg(not(tarpaulin))]
+fn test_set_weights_delta_dispatch_info_ok() {
+    new_test_ext(0).execute_with(|| {
+        let call = |changes: Vec<(u16, u16)>| {
+            RuntimeCall::SubtensorModule(SubtensorCall::set_weights_delta {
+                netuid: 1,
+                changes,
+                version_key: 0,
+            })
+            .get_dispatch_info()
+        };
+        let dispatch_info = call(vec![(1, 1)]);
+
+        assert_eq!(dispatch_info.class, DispatchClass::Normal);
+        assert_eq!(dispatch_info.pays_fee, Pays::No);
+        // The weight grows with the number of changes.
+        assert!(call(vec![(1, 1), (2, 2)])
+            .weight
+            .any_gt(dispatch_info.weight));
+    });
+}
+
+#[test]
+fn test_set_weights_delta_upserts_and_removes() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        let hotkey = U256::from(1);
+        add_network(netuid, 0, 0);
+        register_ok_neuron(netuid, hotkey, U256::from(2), 10);
+        register_ok_neuron(netuid, U256::from(3), U256::from(4), 20);
+        register_ok_neuron(netuid, U256::from(5), U256::from(6), 30);
+        SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+        SubtensorModule::set_validator_permit_for_uid(netuid, 0, true);
+        assert_ok!(SubtensorModule::set_weights(
+            RuntimeOrigin::signed(hotkey),
+            netuid,
+            vec![0, 1],
+            vec![1, 1],
+            0
+        ));
+        assert_eq!(
+            pallet_subtensor::Weights::<Test>::get(netuid, 0),
+            vec![(0, u16::MAX), (1, u16::MAX)]
+        );
+
+        // Remove uid 1 and add uid 2, charged for the stored row and the changes.
+        let actual_weight = SubtensorModule::set_weights_delta(
+            RuntimeOrigin::signed(hotkey),
+            netuid,
+            vec![(1, 0), (2, 100)],
+            0,
+        )
+        .expect("Delta applied.")
+        .actual_weight;
+        assert_eq!(
+            actual_weight,
+            Some(SubtensorModule::get_set_weights_delta_weight(2, 2))
+        );
+        assert_eq!(
+            pallet_subtensor::Weights::<Test>::get(netuid, 0),
+            vec![(0, u16::MAX), (2, 100)]
+        );
+        System::assert_last_event(pallet_subtensor::Event::WeightsSet(netuid, 0).into());
+
+        // The row is max-upscaled again once the largest weight is removed.
+        assert_ok!(SubtensorModule::set_weights_delta(
+            RuntimeOrigin::signed(hotkey),
+            netuid,
+            vec![(0, 0), (1, 20)],
+            0
+        ));
+        assert_eq!(
+            pallet_subtensor::Weights::<Test>::get(netuid, 0),
+            vec![(1, 13107), (2, u16::MAX)]
+        );
+    });
+}
+
+#[test]
+fn test_set_weights_delta_checks() {
+    new_test_ext(1).execute_with(|| {
+        let netuid: u16 = 1;
+        let hotkey = U256::from(1);
+        add_network(netuid, 0, 0);
+        register_ok_neuron(netuid, hotkey, U256::from(2), 10);
+        register_ok_neuron(netuid, U256::from(3), U256::from(4), 20);
+        register_ok_neuron(netuid, U256::from(5), U256::from(6), 30);
+        SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+        SubtensorModule::set_validator_permit_for_uid(netuid, 0, true);
+        assert_ok!(SubtensorModule::set_weights(
+            RuntimeOrigin::signed(hotkey),
+            netuid,
+            vec![0, 1],
+            vec![1, 1],
+            0
+        ));
+
+        assert_err!(
+            SubtensorModule::set_weights_delta(
+                RuntimeOrigin::signed(hotkey),
+                netuid,
+                vec![(2, 1), (2, 2)],
+                0
+            ),
+            Error::<Test>::DuplicateUids
+        );
+        assert_err!(
+            SubtensorModule::set_weights_delta(
+                RuntimeOrigin::signed(hotkey),
+                netuid,
+                vec![(3, 1)],
+                0
+            ),
+            Error::<Test>::UidVecContainInvalidOne
+        );
+
+        // The whole row is checked, not only the changes.
+        SubtensorModule::set_min_allowed_weights(netuid, 2);
+        assert_err!(
+            SubtensorModule::set_weights_delta(
+                RuntimeOrigin::signed(hotkey),
+                netuid,
+                vec![(0, 0)],
+                0
+            ),
+            Error::<Test>::WeightVecLengthIsLow
+        );
+        SubtensorModule::set_max_weight_limit(netuid, u16::MAX / 4);
+        assert_err!(
+            SubtensorModule::set_weights_delta(
+                RuntimeOrigin::signed(hotkey),
+                netuid,
+                vec![(2, 1)],
+                0
+            ),
+            Error::<Test>::MaxWeightExceeded
+        );
+        SubtensorModule::set_validator_permit_for_uid(netuid, 0, false);
+        assert_err!(
+            SubtensorModule::set_weights_delta(
+                RuntimeOrigin::signed(hotkey),
+                netuid,
+                vec![(2, 1)],
+                0
+            ),
+            Error::<Test>::NeuronNoValidatorPermit
+        );
+
+        // Weights must be committed on commit reveal subnets.
+        SubtensorModule::set_commit_reveal_weights_enabled(netuid, true);
+        assert_err!(
+            SubtensorModule::set_weights_delta(
+                RuntimeOrigin::signed(hotkey),
+                netuid,
+                vec![(2, 1)],
+                0
+            ),
+            Error::<Test>::CommitRevealEnabled
+        );
+    });
+}
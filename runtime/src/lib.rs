@@ -141,7 +141,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     //   `spec_version`, and `authoring_version` are the same between Wasm and native.
     // This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
     //   the compatible custom types.
-    spec_version: 194,
+    spec_version: 221,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
     transaction_version: 1,
@@ -316,6 +316,8 @@ impl Contains<RuntimeCall> for SafeModeWhitelistedCalls {
                 | RuntimeCall::SubtensorModule(
                     pallet_subtensor::Call::schedule_coldkey_swap { .. }
                         | pallet_subtensor::Call::set_weights { .. }
+                        | pallet_subtensor::Call::batch_set_weights { .. }
+                        | pallet_subtensor::Call::set_weights_delta { .. }
                         | pallet_subtensor::Call::set_root_weights { .. }
                         | pallet_subtensor::Call::serve_axon { .. }
                 )
@@ -614,6 +616,7 @@ pub enum ProxyType {
     Governance, // Both above governance
     Staking,
     Registration,
+    Transfer, // Transfers of TAO and stake to other coldkeys
 }
 impl Default for ProxyType {
     fn default() -> Self {
@@ -624,12 +627,30 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
     fn filter(&self, c: &RuntimeCall) -> bool {
         match self {
             ProxyType::Any => true,
-            ProxyType::NonTransfer => !matches!(c, RuntimeCall::Balances(..)),
+            ProxyType::NonTransfer => !matches!(
+                c,
+                RuntimeCall::Balances(..)
+                    | RuntimeCall::SubtensorModule(pallet_subtensor::Call::transfer_stake { .. })
+                    | RuntimeCall::SubtensorModule(
+                        pallet_subtensor::Call::set_reward_destination { .. }
+                    )
+            ),
             ProxyType::NonFungibile => !matches!(
                 c,
                 RuntimeCall::Balances(..)
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::add_stake { .. })
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::remove_stake { .. })
+                    | RuntimeCall::SubtensorModule(pallet_subtensor::Call::move_stake { .. })
+                    | RuntimeCall::SubtensorModule(
+                        pallet_subtensor::Call::add_stake_multiple { .. }
+                    )
+                    | RuntimeCall::SubtensorModule(
+                        pallet_subtensor::Call::remove_stake_multiple { .. }
+                    )
+                    | RuntimeCall::SubtensorModule(
+                        pallet_subtensor::Call::set_reward_destination { .. }
+                    )
+                    | RuntimeCall::SubtensorModule(pallet_subtensor::Call::transfer_stake { .. })
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::burned_register { .. })
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::root_register { .. })
             ),
@@ -656,12 +677,29 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
                 c,
                 RuntimeCall::SubtensorModule(pallet_subtensor::Call::add_stake { .. })
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::remove_stake { .. })
+                    | RuntimeCall::SubtensorModule(pallet_subtensor::Call::move_stake { .. })
+                    | RuntimeCall::SubtensorModule(
+                        pallet_subtensor::Call::add_stake_multiple { .. }
+                    )
+                    | RuntimeCall::SubtensorModule(
+                        pallet_subtensor::Call::remove_stake_multiple { .. }
+                    )
+                    | RuntimeCall::SubtensorModule(pallet_subtensor::Call::claim_unbonded { .. })
             ),
             ProxyType::Registration => matches!(
                 c,
                 RuntimeCall::SubtensorModule(pallet_subtensor::Call::burned_register { .. })
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::register { .. })
             ),
+            ProxyType::Transfer => matches!(
+                c,
+                RuntimeCall::Balances(BalancesCall::transfer_keep_alive { .. })
+                    | RuntimeCall::Balances(BalancesCall::transfer_allow_death { .. })
+                    | RuntimeCall::SubtensorModule(pallet_subtensor::Call::transfer_stake { .. })
+                    | RuntimeCall::SubtensorModule(
+                        pallet_subtensor::Call::set_reward_destination { .. }
+                    )
+            ),
         }
     }
     fn is_superset(&self, o: &Self) -> bool {
@@ -669,6 +707,7 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
             (x, y) if x == y => true,
             (ProxyType::Any, _) => true,
             (_, ProxyType::Any) => false,
+            (ProxyType::NonTransfer, ProxyType::Transfer) => false,
             (ProxyType::NonTransfer, _) => true,
             (ProxyType::Governance, ProxyType::Triumvirate | ProxyType::Senate) => true,
             _ => false,
@@ -869,6 +908,7 @@ parameter_types! {
     pub const SubtensorInitialMaxBurn: u64 = 100_000_000_000; // 100 tao
     pub const SubtensorInitialTxRateLimit: u64 = 1000;
     pub const SubtensorInitialTxDelegateTakeRateLimit: u64 = 216000; // 30 days at 12 seconds per block
+    pub const SubtensorInitialTakeIncreaseDelay: u64 = 50400; // 7 days at 12 seconds per block
     pub const SubtensorInitialRAORecycledForRegistration: u64 = 0; // 0 rao
     pub const SubtensorInitialSenateRequiredStakePercentage: u64 = 1; // 1 percent of total stake
     pub const SubtensorInitialNetworkImmunity: u64 = 7 * 7200;
@@ -879,11 +919,14 @@ parameter_types! {
     pub const SubtensorInitialNetworkLockReductionInterval: u64 = 14 * 7200;
     pub const SubtensorInitialNetworkRateLimit: u64 = 7200;
     pub const SubtensorInitialTargetStakesPerInterval: u16 = 1;
+    pub const SubtensorInitialStakeRateLimitMode: pallet_subtensor::StakeRateLimitMode = pallet_subtensor::StakeRateLimitMode::Count;
+    pub const SubtensorInitialMaxStakeAmountPerWindow: u64 = u64::MAX; // Uncapped until set by governance
     pub const SubtensorInitialHotkeySwapCost: u64 = 1_000_000_000;
     pub const InitialAlphaHigh: u16 = 58982; // Represents 0.9 as per the production default
     pub const InitialAlphaLow: u16 = 45875; // Represents 0.7 as per the production default
     pub const InitialLiquidAlphaOn: bool = false; // Default value for LiquidAlphaOn
     pub const SubtensorInitialBaseDifficulty: u64 = 10_000_000; // Base difficulty
+    pub const SubtensorMaxWeightCommits: u32 = 10; // Pending weight commits per hotkey and subnet
 }
 
 impl pallet_subtensor::Config for Runtime {
@@ -925,6 +968,7 @@ impl pallet_subtensor::Config for Runtime {
     type InitialMinBurn = SubtensorInitialMinBurn;
     type InitialTxRateLimit = SubtensorInitialTxRateLimit;
     type InitialTxDelegateTakeRateLimit = SubtensorInitialTxDelegateTakeRateLimit;
+    type InitialTakeIncreaseDelay = SubtensorInitialTakeIncreaseDelay;
     type InitialRAORecycledForRegistration = SubtensorInitialRAORecycledForRegistration;
     type InitialSenateRequiredStakePercentage = SubtensorInitialSenateRequiredStakePercentage;
     type InitialNetworkImmunityPeriod = SubtensorInitialNetworkImmunity;
@@ -935,11 +979,14 @@ impl pallet_subtensor::Config for Runtime {
     type InitialSubnetLimit = SubtensorInitialSubnetLimit;
     type InitialNetworkRateLimit = SubtensorInitialNetworkRateLimit;
     type InitialTargetStakesPerInterval = SubtensorInitialTargetStakesPerInterval;
+    type InitialStakeRateLimitMode = SubtensorInitialStakeRateLimitMode;
+    type InitialMaxStakeAmountPerWindow = SubtensorInitialMaxStakeAmountPerWindow;
     type HotkeySwapCost = SubtensorInitialHotkeySwapCost;
     type AlphaHigh = InitialAlphaHigh;
     type AlphaLow = InitialAlphaLow;
     type LiquidAlphaOn = InitialLiquidAlphaOn;
     type InitialBaseDifficulty = SubtensorInitialBaseDifficulty;
+    type MaxWeightCommits = SubtensorMaxWeightCommits;
 }
 
 use sp_runtime::BoundedVec;
@@ -1227,6 +1274,62 @@ impl
     ) -> Result<(), DispatchError> {
         SubtensorModule::do_set_alpha_values(origin, netuid, alpha_low, alpha_high)
     }
+
+    fn set_unbonding_period(unbonding_period: u64) {
+        SubtensorModule::set_unbonding_period(unbonding_period);
+    }
+
+    fn set_network_unbonding_period(netuid: u16, unbonding_period: u64) {
+        SubtensorModule::set_network_unbonding_period(netuid, unbonding_period);
+    }
+
+    fn set_max_unbonding_chunks(max_unbonding_chunks: u32) {
+        SubtensorModule::set_max_unbonding_chunks(max_unbonding_chunks);
+    }
+
+    fn set_take_increase_delay(take_increase_delay: u64) {
+        SubtensorModule::set_take_increase_delay(take_increase_delay);
+    }
+
+    fn set_stake_rate_limit_mode(mode: pallet_subtensor::StakeRateLimitMode) {
+        SubtensorModule::set_stake_rate_limit_mode(mode);
+    }
+
+    fn set_stake_amount_window(stake_amount_window: u64) {
+        SubtensorModule::set_stake_amount_window(stake_amount_window);
+    }
+
+    fn set_max_stake_amount_per_window(max_stake_amount: u64) {
+        SubtensorModule::set_max_stake_amount_per_window(max_stake_amount);
+    }
+
+    fn set_weights_max_version_key(netuid: u16, weights_max_version_key: u64) {
+        SubtensorModule::set_weights_max_version_key(netuid, weights_max_version_key);
+    }
+
+    fn set_banned_weights_version_keys(netuid: u16, banned_version_keys: Vec<u64>) {
+        SubtensorModule::set_banned_weights_version_keys(netuid, banned_version_keys);
+    }
+
+    fn set_weights_window(netuid: u16, weights_window: u64) {
+        SubtensorModule::set_weights_window(netuid, weights_window);
+    }
+
+    fn set_epoch_history_depth(netuid: u16, depth: u16) {
+        SubtensorModule::set_epoch_history_depth(netuid, depth);
+    }
+
+    fn set_consensus_mechanism(netuid: u16, mechanism: pallet_subtensor::ConsensusMechanism) {
+        SubtensorModule::set_consensus_mechanism(netuid, mechanism);
+    }
+
+    fn get_weights_version_key(netuid: u16) -> u64 {
+        SubtensorModule::get_weights_version_key(netuid)
+    }
+
+    fn get_weights_max_version_key(netuid: u16) -> u64 {
+        SubtensorModule::get_weights_max_version_key(netuid)
+    }
 }
 
 impl pallet_admin_utils::Config for Runtime {
@@ -1620,6 +1723,16 @@ impl_runtime_apis! {
                 vec![]
             }
         }
+
+        fn get_validator_agreement(netuid: u16) -> Vec<u8> {
+            let result = SubtensorModule::get_validator_agreement(netuid);
+            result.encode()
+        }
+
+        fn get_epoch_history(netuid: u16, uid: u16, count: u16) -> Vec<u8> {
+            let result = SubtensorModule::get_epoch_history(netuid, uid, count);
+            result.encode()
+        }
     }
 
     impl subtensor_custom_rpc_runtime_api::SubnetInfoRuntimeApi<Block> for Runtime {
@@ -1647,6 +1760,11 @@ impl_runtime_apis! {
                 vec![]
             }
         }
+
+        fn get_pending_weight_commits(netuid: u16) -> Vec<u8> {
+            let result = SubtensorModule::get_pending_weight_commits(netuid);
+            result.encode()
+        }
     }
 
     impl subtensor_custom_rpc_runtime_api::StakeInfoRuntimeApi<Block> for Runtime {
@@ -1659,6 +1777,16 @@ impl_runtime_apis! {
             let result = SubtensorModule::get_stake_info_for_coldkeys( coldkey_account_vecs );
             result.encode()
         }
+
+        fn get_unbonding_for_coldkey( coldkey_account_vec: Vec<u8> ) -> Vec<u8> {
+            let result = SubtensorModule::get_unbonding_for_coldkey( coldkey_account_vec );
+            result.encode()
+        }
+
+        fn get_rewards_for_coldkey( coldkey_account_vec: Vec<u8> ) -> Vec<u8> {
+            let result = SubtensorModule::get_rewards_for_coldkey( coldkey_account_vec );
+            result.encode()
+        }
     }
 
     impl subtensor_custom_rpc_runtime_api::SubnetRegistrationRuntimeApi<Block> for Runtime {
@@ -1693,6 +1821,36 @@ impl_runtime_apis! {
                 EpochReturnType::Incentive(value) => value.encode(),
             }
         }
+
+        fn check_weights( netuid: u16, hotkey_account_vec: Vec<u8>, uids: Vec<u16>, values: Vec<u16>, version_key: u64 ) -> Vec<u8> {
+            let _result = SubtensorModule::get_weights_check(netuid, hotkey_account_vec, uids, values, version_key);
+            if _result.is_some() {
+                let result = _result.expect("Could not get WeightsCheckInfo");
+                result.encode()
+            } else {
+                vec![]
+            }
+        }
+
+        fn simulate_epoch( netuid: u16, overrides: Vec<u8> ) -> Vec<u8> {
+            let _result = SubtensorModule::get_epoch_simulation(netuid, overrides);
+            if _result.is_some() {
+                let result = _result.expect("Could not get EpochSimulation");
+                result.encode()
+            } else {
+                vec![]
+            }
+        }
+
+        fn get_epoch_trace( netuid: u16 ) -> Vec<u8> {
+            let _result = SubtensorModule::get_epoch_trace(netuid);
+            if _result.is_some() {
+                let result = _result.expect("Could not get EpochTrace");
+                result.encode()
+            } else {
+                vec![]
+            }
+        }
     }
 }
 
@@ -109,6 +109,24 @@ fn call_add_stake() -> RuntimeCall {
     })
 }
 
+// stake transfer call
+fn call_transfer_stake() -> RuntimeCall {
+    let amount = 100;
+    RuntimeCall::SubtensorModule(pallet_subtensor::Call::transfer_stake {
+        destination_coldkey: AccountId::from(OTHER_ACCOUNT),
+        hotkey: AccountId::from(DELEGATE),
+        amount,
+    })
+}
+
+// reward destination call, redirecting the emission to another account
+fn call_set_reward_destination() -> RuntimeCall {
+    RuntimeCall::SubtensorModule(pallet_subtensor::Call::set_reward_destination {
+        hotkey: AccountId::from(DELEGATE),
+        destination: pallet_subtensor::RewardDestination::Account(AccountId::from(OTHER_ACCOUNT)),
+    })
+}
+
 // register call, account as hotkey, delegate as coldkey
 fn call_register() -> RuntimeCall {
     let block_number: u64 = 1;
@@ -171,6 +189,7 @@ fn test_proxy_pallet() {
         ProxyType::Governance,
         ProxyType::Staking,
         ProxyType::Registration,
+        ProxyType::Transfer,
     ];
 
     let calls = [
@@ -182,6 +201,8 @@ fn test_proxy_pallet() {
         call_triumvirate,
         call_senate,
         call_add_stake,
+        call_transfer_stake,
+        call_set_reward_destination,
         call_register,
     ];
 
@@ -200,3 +221,13 @@ fn test_proxy_pallet() {
         }
     }
 }
+
+#[test]
+fn test_set_reward_destination_requires_transfer_proxy() {
+    let call = call_set_reward_destination();
+    assert!(ProxyType::Any.filter(&call));
+    assert!(ProxyType::Transfer.filter(&call));
+    assert!(!ProxyType::Staking.filter(&call));
+    assert!(!ProxyType::NonTransfer.filter(&call));
+    assert!(!ProxyType::NonFungibile.filter(&call));
+}